@@ -0,0 +1,285 @@
+use std::{
+    collections::{HashMap, HashSet},
+    error::Error,
+    fmt::{self, Debug, Display, Formatter},
+    hash::Hash,
+    sync::Arc,
+};
+
+use crate::LeveledHashMap;
+
+/// Possible errors come from converting an adjacency list into a `LeveledHashMap`.
+pub enum AdjacencyError<K> {
+    /// A key appears in more than one row.
+    KeyDuplicated { key: Arc<K> },
+    /// The parent key of a row does not appear as the key of any row.
+    UnknownParent { key: Arc<K>, parent: Arc<K> },
+    /// Some keys cannot be reached from any row without a parent key, because they are in cycles or under cycles. Every cycle is a key sequence in which the parent key of each key is the next one, and the parent key of the last key is the first one.
+    Cycle { cycles: Vec<Vec<Arc<K>>> },
+}
+
+impl<K: Debug> Debug for AdjacencyError<K> {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter) -> Result<(), fmt::Error> {
+        match self {
+            AdjacencyError::KeyDuplicated {
+                key,
+            } => {
+                let mut s = f.debug_struct("KeyDuplicated");
+                s.field("Key", key);
+                s.finish()
+            },
+            AdjacencyError::UnknownParent {
+                key,
+                parent,
+            } => {
+                let mut s = f.debug_struct("UnknownParent");
+                s.field("Key", key);
+                s.field("Parent", parent);
+                s.finish()
+            },
+            AdjacencyError::Cycle {
+                cycles,
+            } => {
+                let mut s = f.debug_struct("Cycle");
+                s.field("Cycles", cycles);
+                s.finish()
+            },
+        }
+    }
+}
+
+impl<K: Debug> Display for AdjacencyError<K> {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter) -> Result<(), fmt::Error> {
+        match self {
+            AdjacencyError::KeyDuplicated {
+                key,
+            } => f.write_fmt(format_args!("The key {:?} appears more than once.", key)),
+            AdjacencyError::UnknownParent {
+                key,
+                parent,
+            } => f.write_fmt(format_args!(
+                "The parent key {:?} of the key {:?} does not exist.",
+                parent, key
+            )),
+            AdjacencyError::Cycle {
+                cycles,
+            } => f.write_fmt(format_args!("{} cycles are found.", cycles.len())),
+        }
+    }
+}
+
+impl<K: Debug> Error for AdjacencyError<K> {}
+
+/// Find the cycles in an adjacency list made of `(key, parent_key)` rows before converting it with `LeveledHashMap::from_adjacency`, in order to locate the bad parent keys. Every cycle is a key sequence in which the parent key of each key is the next one, and the parent key of the last key is the first one. Parent keys which do not appear as the key of any row are ignored, and only the first row of a duplicated key is used.
+/// ```
+/// use leveled_hash_map::detect_cycles;
+///
+/// let rows = vec![
+///     ("food", None),
+///     ("dessert", Some("food")),
+///     ("a", Some("b")),
+///     ("b", Some("c")),
+///     ("c", Some("a")),
+///     ("d", Some("a")),
+///     ("e", Some("e")),
+/// ];
+///
+/// let cycles = detect_cycles(rows.iter().map(|(k, p)| (k, p.as_ref())));
+///
+/// assert_eq!(vec![vec![&"a", &"b", &"c"], vec![&"e"]], cycles);
+/// ```
+pub fn detect_cycles<'a, K: Eq + Hash + 'a, I: IntoIterator<Item = (&'a K, Option<&'a K>)>>(
+    rows: I,
+) -> Vec<Vec<&'a K>> {
+    let mut indices: HashMap<&K, usize> = HashMap::new();
+    let mut keys: Vec<&K> = Vec::new();
+    let mut parent_keys: Vec<Option<&K>> = Vec::new();
+
+    for (key, parent_key) in rows {
+        if !indices.contains_key(key) {
+            indices.insert(key, keys.len());
+            keys.push(key);
+            parent_keys.push(parent_key);
+        }
+    }
+
+    let parents: Vec<Option<usize>> = parent_keys
+        .into_iter()
+        .map(|parent_key| parent_key.and_then(|parent_key| indices.get(parent_key).copied()))
+        .collect();
+
+    find_cycles(&parents)
+        .into_iter()
+        .map(|cycle| cycle.into_iter().map(|i| keys[i]).collect())
+        .collect()
+}
+
+/// Find the cycles formed by following the parents of the rows. Cycles are ordered by the first row visited in them.
+fn find_cycles(parents: &[Option<usize>]) -> Vec<Vec<usize>> {
+    // 0: not visited, 1: on the current walk, 2: done
+    let mut states = vec![0u8; parents.len()];
+    let mut cycles = Vec::new();
+    let mut walk = Vec::new();
+
+    for start in 0..parents.len() {
+        let mut current = Some(start);
+
+        while let Some(i) = current {
+            match states[i] {
+                0 => {
+                    states[i] = 1;
+                    walk.push(i);
+                    current = parents[i];
+                },
+                1 => {
+                    let position = walk.iter().position(|j| *j == i).unwrap();
+
+                    cycles.push(walk[position..].to_vec());
+
+                    break;
+                },
+                _ => break,
+            }
+        }
+
+        for i in walk.drain(..) {
+            states[i] = 2;
+        }
+    }
+
+    cycles
+}
+
+impl<K: Eq + Hash, V> LeveledHashMap<K, V> {
+    /// Convert an adjacency list, which is made of `(key, parent_key, value)` rows like a table in a relational database, into a map. Rows without parent keys are placed at Level 0, and the levels of the other rows are resolved by following their parent keys, so the rows can be in any order. Keys need to be unique among all rows.
+    /// ```
+    /// use std::sync::Arc;
+    ///
+    /// use leveled_hash_map::{AdjacencyError, LeveledHashMap};
+    ///
+    /// let map = LeveledHashMap::from_adjacency(vec![
+    ///     ("cake", Some("dessert"), 3),
+    ///     ("food", None, 1),
+    ///     ("dessert", Some("food"), 2),
+    /// ])
+    /// .unwrap();
+    ///
+    /// assert_eq!(
+    ///     Some(&3),
+    ///     map.get(&[Arc::new("food"), Arc::new("dessert"), Arc::new("cake")])
+    /// );
+    ///
+    /// match LeveledHashMap::from_adjacency(vec![("dessert", Some("food"), 2)]) {
+    ///     Err(AdjacencyError::UnknownParent {
+    ///         key,
+    ///         parent,
+    ///     }) => {
+    ///         assert_eq!(Arc::new("dessert"), key);
+    ///         assert_eq!(Arc::new("food"), parent);
+    ///     },
+    ///     _ => unreachable!(),
+    /// }
+    ///
+    /// assert!(matches!(
+    ///     LeveledHashMap::from_adjacency(vec![
+    ///         ("a", Some("b"), 1),
+    ///         ("b", Some("a"), 2)
+    ///     ]),
+    ///     Err(AdjacencyError::Cycle { cycles }) if cycles.len() == 1
+    /// ));
+    /// ```
+    pub fn from_adjacency<I: IntoIterator<Item = (K, Option<K>, V)>>(
+        rows: I,
+    ) -> Result<Self, AdjacencyError<K>> {
+        let rows = rows.into_iter();
+
+        let mut indices: HashMap<Arc<K>, usize> = HashMap::with_capacity(rows.size_hint().0);
+        let mut keys: Vec<Arc<K>> = Vec::with_capacity(rows.size_hint().0);
+        let mut parent_keys: Vec<Option<K>> = Vec::with_capacity(rows.size_hint().0);
+        let mut values: Vec<Option<V>> = Vec::with_capacity(rows.size_hint().0);
+
+        for (key, parent_key, value) in rows {
+            let key = Arc::new(key);
+
+            if indices.contains_key(&key) {
+                return Err(AdjacencyError::KeyDuplicated {
+                    key,
+                });
+            }
+
+            indices.insert(Arc::clone(&key), keys.len());
+            keys.push(key);
+            parent_keys.push(parent_key);
+            values.push(Some(value));
+        }
+
+        let mut parents: Vec<Option<usize>> = Vec::with_capacity(keys.len());
+        let mut children: Vec<Vec<usize>> = vec![Vec::new(); keys.len()];
+
+        // the rows in the order of a breadth-first traversal from the roots, with their levels
+        let mut order: Vec<(usize, usize)> = Vec::new();
+
+        for (i, parent_key) in parent_keys.into_iter().enumerate() {
+            match parent_key {
+                Some(parent_key) => match indices.get(&parent_key) {
+                    Some(&parent) => {
+                        parents.push(Some(parent));
+                        children[parent].push(i);
+                    },
+                    None => {
+                        return Err(AdjacencyError::UnknownParent {
+                            key:    Arc::clone(&keys[i]),
+                            parent: Arc::new(parent_key),
+                        })
+                    },
+                },
+                None => {
+                    parents.push(None);
+                    order.push((i, 0));
+                },
+            }
+        }
+
+        let mut i = 0;
+
+        while i < order.len() {
+            let (index, level) = order[i];
+
+            order.extend(children[index].iter().map(|c| (*c, level + 1)));
+
+            i += 1;
+        }
+
+        if order.len() < keys.len() {
+            return Err(AdjacencyError::Cycle {
+                cycles: find_cycles(&parents)
+                    .into_iter()
+                    .map(|cycle| cycle.into_iter().map(|i| Arc::clone(&keys[i])).collect())
+                    .collect(),
+            });
+        }
+
+        let mut map = LeveledHashMap::new();
+
+        for (index, level) in order {
+            let key = Arc::clone(&keys[index]);
+            let parent_key = parents[index].map(|parent| Arc::clone(&keys[parent]));
+
+            if map.pool.len() == level {
+                map.pool.push(HashMap::new());
+                map.sub.push(HashMap::new());
+            }
+
+            if let Some(parent_key) = parent_key.as_ref() {
+                map.sub[level - 1].get_mut(parent_key).unwrap().insert(Arc::clone(&key));
+            }
+
+            map.sub[level].insert(Arc::clone(&key), HashSet::new());
+            map.pool[level].insert(key, (parent_key, values[index].take().unwrap()));
+        }
+
+        Ok(map)
+    }
+}
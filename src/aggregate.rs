@@ -0,0 +1,146 @@
+use std::{
+    collections::{HashMap, HashSet},
+    hash::Hash,
+    mem,
+    sync::Arc,
+};
+
+use crate::LeveledHashMap;
+
+/// Cached per-subtree aggregates of a `LeveledHashMap`, created by `LeveledHashMap::aggregate`. The cache is not updated automatically. After changing the map (including editing values through `get_mut`), call `invalidate` with the key chains of the changed entries, and then `recompute` to refresh only the invalidated entries and their ancestors.
+#[derive(Debug)]
+pub struct Aggregates<K: Eq + Hash, A> {
+    values: Vec<HashMap<Arc<K>, (A, Vec<Arc<K>>)>>,
+    dirty:  Vec<HashSet<Arc<K>>>,
+}
+
+impl<K: Eq + Hash, V> LeveledHashMap<K, V> {
+    /// Compute an aggregate for every entry from its key, its value and the aggregates of its children, bottom-up.
+    /// ```
+    /// use std::sync::Arc;
+    ///
+    /// use leveled_hash_map::LeveledHashMap;
+    ///
+    /// let mut map: LeveledHashMap<&'static str, u32> = LeveledHashMap::new();
+    ///
+    /// map.insert(&[Arc::new("food")], 1).unwrap();
+    /// map.insert(&[Arc::new("food"), Arc::new("dessert")], 2).unwrap();
+    /// map.insert(&[Arc::new("food"), Arc::new("meat")], 3).unwrap();
+    ///
+    /// // the sum of the values in every subtree
+    /// let sum = |_: &&str, v: &u32, children: &[&u32]| {
+    ///     v + children.iter().copied().sum::<u32>()
+    /// };
+    ///
+    /// let mut sums = map.aggregate(sum);
+    ///
+    /// assert_eq!(Some(&6), sums.get(&[Arc::new("food")]));
+    ///
+    /// *map.get_mut(&[Arc::new("food"), Arc::new("meat")]).unwrap() = 10;
+    ///
+    /// sums.invalidate(&[Arc::new("food"), Arc::new("meat")]);
+    /// sums.recompute(&map, sum);
+    ///
+    /// assert_eq!(Some(&13), sums.get(&[Arc::new("food")]));
+    /// ```
+    pub fn aggregate<A, F: FnMut(&K, &V, &[&A]) -> A>(&self, mut f: F) -> Aggregates<K, A> {
+        let mut aggregates = Aggregates {
+            values: Vec::new(), dirty: Vec::new()
+        };
+
+        if let Some(map) = self.pool.first() {
+            for key in map.keys() {
+                aggregates.compute(self, 0, key, &mut f);
+            }
+        }
+
+        aggregates
+    }
+}
+
+impl<K: Eq + Hash, A> Aggregates<K, A> {
+    /// Get the cached aggregate of an entry by a key chain which starts at Level 0. Only the last key is looked up, so the key chain is not validated.
+    #[inline]
+    pub fn get(&self, key_chain: &[Arc<K>]) -> Option<&A> {
+        let level = key_chain.len().checked_sub(1)?;
+
+        self.values.get(level)?.get(&key_chain[level]).map(|(a, _)| a)
+    }
+
+    /// Mark an entry and all of its ancestors as stale by a key chain which starts at Level 0. Use it after inserting, removing or editing the entry.
+    pub fn invalidate(&mut self, key_chain: &[Arc<K>]) {
+        while self.dirty.len() < key_chain.len() {
+            self.dirty.push(HashSet::new());
+        }
+
+        for (level, key) in key_chain.iter().enumerate() {
+            self.dirty[level].insert(Arc::clone(key));
+        }
+    }
+
+    /// Check whether there are stale entries which need to be recomputed.
+    #[inline]
+    pub fn is_stale(&self) -> bool {
+        self.dirty.iter().any(|d| !d.is_empty())
+    }
+
+    /// Recompute the stale entries, deepest first. Entries which no longer exist in the map are dropped along with their cached descendants.
+    pub fn recompute<V, F: FnMut(&K, &V, &[&A]) -> A>(
+        &mut self,
+        map: &LeveledHashMap<K, V>,
+        mut f: F,
+    ) {
+        let dirty = mem::take(&mut self.dirty);
+
+        for (level, keys) in dirty.into_iter().enumerate().rev() {
+            for key in keys {
+                if map.pool.get(level).map(|m| m.contains_key(&key)).unwrap_or(false) {
+                    if let Some(m) = self.values.get_mut(level) {
+                        m.remove(&key);
+                    }
+
+                    self.compute(map, level, &key, &mut f);
+                } else {
+                    self.drop_cached(level, &key);
+                }
+            }
+        }
+    }
+
+    fn drop_cached(&mut self, level: usize, key: &Arc<K>) {
+        if let Some((_, children)) = self.values.get_mut(level).and_then(|m| m.remove(key)) {
+            for c in children {
+                self.drop_cached(level + 1, &c);
+            }
+        }
+    }
+
+    fn compute<V, F: FnMut(&K, &V, &[&A]) -> A>(
+        &mut self,
+        map: &LeveledHashMap<K, V>,
+        level: usize,
+        key: &Arc<K>,
+        f: &mut F,
+    ) {
+        let children: Vec<Arc<K>> = map.sub[level][key].iter().map(Arc::clone).collect();
+
+        for c in children.iter() {
+            if self.values.get(level + 1).map(|m| !m.contains_key(c)).unwrap_or(true) {
+                self.compute(map, level + 1, c, f);
+            }
+        }
+
+        let a = {
+            let children_aggregates: Vec<&A> =
+                children.iter().map(|c| &self.values[level + 1][c].0).collect();
+
+            f(key.as_ref(), &map.pool[level][key].1, &children_aggregates)
+        };
+
+        while self.values.len() <= level {
+            self.values.push(HashMap::new());
+        }
+
+        self.values[level].insert(Arc::clone(key), (a, children));
+    }
+}
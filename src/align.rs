@@ -0,0 +1,132 @@
+use std::{
+    collections::{HashMap, HashSet},
+    hash::Hash,
+    sync::Arc,
+};
+
+use crate::{LeveledHashMap, LeveledHashMapError};
+
+/// The result of aligning two `LeveledHashMap` instances, produced by `LeveledHashMap::align`.
+#[derive(Debug)]
+pub struct Alignment<'a, K, V, K2, V2> {
+    /// Pairs of entries whose whole key chains correspond to each other, as `((left_key_chain, left_value), (right_key_chain, right_value))`, ordered by level.
+    pub matched:         Vec<((Vec<Arc<K>>, &'a V), (Vec<Arc<K2>>, &'a V2))>,
+    /// Key chains of the left map which have no counterpart in the right map, ordered by level.
+    pub unmatched_left:  Vec<Vec<Arc<K>>>,
+    /// Key chains of the right map which have no counterpart in the left map, ordered by level.
+    pub unmatched_right: Vec<Vec<Arc<K2>>>,
+}
+
+impl<K: Eq + Hash, V> LeveledHashMap<K, V> {
+    /// Align this map with another map whose keys are related to the keys of this map by a mapping closure. An entry of this map matches an entry of the other map if they are at the same level, the mapped key equals the other key, and their parents match as well. The mapping needs to be one-to-one at every level, so a `LeveledHashMapError::KeyDuplicated` error with one of the keys is returned if two keys at the same level are mapped to the same key.
+    /// ```
+    /// use std::sync::Arc;
+    ///
+    /// use leveled_hash_map::LeveledHashMap;
+    ///
+    /// let mut legacy: LeveledHashMap<u32, &'static str> = LeveledHashMap::new();
+    ///
+    /// legacy.insert(&[Arc::new(1)], "food").unwrap();
+    /// legacy.insert(&[Arc::new(1), Arc::new(11)], "dessert").unwrap();
+    /// legacy.insert(&[Arc::new(2)], "animal").unwrap();
+    ///
+    /// let mut current: LeveledHashMap<String, &'static str> =
+    ///     LeveledHashMap::new();
+    ///
+    /// current.insert(&[Arc::new("C1".to_string())], "food").unwrap();
+    /// current
+    ///     .insert(
+    ///         &[Arc::new("C1".to_string()), Arc::new("C11".to_string())],
+    ///         "dessert",
+    ///     )
+    ///     .unwrap();
+    /// current.insert(&[Arc::new("C3".to_string())], "plant").unwrap();
+    ///
+    /// let alignment = legacy.align(&current, |k| format!("C{}", k)).unwrap();
+    ///
+    /// assert_eq!(2, alignment.matched.len());
+    /// assert_eq!(vec![vec![Arc::new(2)]], alignment.unmatched_left);
+    /// assert_eq!(
+    ///     vec![vec![Arc::new("C3".to_string())]],
+    ///     alignment.unmatched_right
+    /// );
+    ///
+    /// assert!(legacy.align(&current, |_| "C1".to_string()).is_err());
+    /// ```
+    pub fn align<'a, K2: Eq + Hash, V2, F: FnMut(&K) -> K2>(
+        &'a self,
+        other: &'a LeveledHashMap<K2, V2>,
+        mut mapping: F,
+    ) -> Result<Alignment<'a, K, V, K2, V2>, LeveledHashMapError<K>> {
+        let mut matched = Vec::new();
+        let mut unmatched_left = Vec::new();
+        let mut unmatched_right = Vec::new();
+
+        // the counterparts of the matched keys at the previous level
+        let mut counterparts: HashMap<Arc<K>, Arc<K2>> = HashMap::new();
+
+        for (level, map) in self.pool.iter().enumerate() {
+            let other_map = other.pool.get(level);
+
+            let mut next_counterparts = HashMap::new();
+            let mut mapped_keys = HashSet::with_capacity(map.len());
+
+            for (key, (parent, value)) in map {
+                let mapped_key = mapping(key.as_ref());
+
+                let found = other_map.and_then(|m| m.get_key_value(&mapped_key)).filter(
+                    |(_, (other_parent, _))| match (parent, other_parent) {
+                        (Some(parent), Some(other_parent)) => {
+                            counterparts.get(parent).map(|p| p.eq(other_parent)).unwrap_or(false)
+                        },
+                        (None, None) => true,
+                        _ => false,
+                    },
+                );
+
+                match found {
+                    Some((other_key, (_, other_value))) => {
+                        next_counterparts.insert(Arc::clone(key), Arc::clone(other_key));
+
+                        matched.push((
+                            (self.key_chain_of(level, key), value),
+                            (other.key_chain_of(level, other_key), other_value),
+                        ));
+                    },
+                    None => unmatched_left.push(self.key_chain_of(level, key)),
+                }
+
+                if !mapped_keys.insert(mapped_key) {
+                    return Err(LeveledHashMapError::KeyDuplicated {
+                        level,
+                        key: Arc::clone(key),
+                    });
+                }
+            }
+
+            if let Some(other_map) = other_map {
+                let matched_keys: HashSet<&Arc<K2>> = next_counterparts.values().collect();
+
+                for other_key in other_map.keys() {
+                    if !matched_keys.contains(other_key) {
+                        unmatched_right.push(other.key_chain_of(level, other_key));
+                    }
+                }
+            }
+
+            counterparts = next_counterparts;
+        }
+
+        for (level, other_map) in other.pool.iter().enumerate().skip(self.pool.len()) {
+            for other_key in other_map.keys() {
+                unmatched_right.push(other.key_chain_of(level, other_key));
+            }
+        }
+
+        Ok(Alignment {
+            matched,
+            unmatched_left,
+            unmatched_right,
+        })
+    }
+}
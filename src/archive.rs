@@ -0,0 +1,146 @@
+use std::{borrow::Borrow, collections::HashMap, hash::Hash};
+
+use rkyv::{Archive, Archived, Deserialize, Serialize};
+
+use crate::{flat::unwrap_key, FlatSnapshot, LeveledHashMap, LeveledHashMapError};
+
+/// A form of `LeveledHashMap` without `Arc`s for the `rkyv` zero-copy serialization framework. `levels[n]` maps the keys at Level n to their parent keys and values.
+///
+/// The archived form, `ArchivedLeveledArchive`, keeps one hash table per level, so it can be queried in place with `ArchivedLeveledArchive::get` right after `rkyv::access`, without rebuilding any `HashMap` or `Arc`. Convert it back with `LeveledHashMap::from_archive` when a mutable map is needed.
+#[derive(Debug, Clone, PartialEq, Eq, Default, Archive, Serialize, Deserialize)]
+pub struct LeveledArchive<K: Eq + Hash, V> {
+    /// The entries at each level.
+    pub levels: Vec<HashMap<K, (Option<K>, V)>>,
+}
+
+impl<K: Archive + Eq + Hash, V: Archive> ArchivedLeveledArchive<K, V>
+where
+    Archived<K>: Eq + Hash,
+{
+    /// Get an archived value by a key chain which starts at Level 0, checking the parent key of every entry along the key chain. For example, an archive of `LeveledHashMap<String, V>` can be queried with `&[&str]`.
+    /// ```
+    /// use std::sync::Arc;
+    ///
+    /// use leveled_hash_map::{
+    ///     ArchivedLeveledArchive, LeveledArchive, LeveledHashMap,
+    /// };
+    ///
+    /// let mut map: LeveledHashMap<String, u32> = LeveledHashMap::new();
+    ///
+    /// map.insert(&[Arc::new("food".to_string())], 1).unwrap();
+    /// map.insert(
+    ///     &[Arc::new("food".to_string()), Arc::new("dessert".to_string())],
+    ///     2,
+    /// )
+    /// .unwrap();
+    ///
+    /// let bytes =
+    ///     rkyv::to_bytes::<rkyv::rancor::Error>(&map.to_archive()).unwrap();
+    ///
+    /// let archived = rkyv::access::<
+    ///     ArchivedLeveledArchive<String, u32>,
+    ///     rkyv::rancor::Error,
+    /// >(&bytes)
+    /// .unwrap();
+    ///
+    /// assert_eq!(
+    ///     Some(2),
+    ///     archived.get::<str>(&["food", "dessert"]).map(|v| v.to_native())
+    /// );
+    /// assert_eq!(None, archived.get::<str>(&["dessert"]));
+    ///
+    /// let archive: LeveledArchive<String, u32> =
+    ///     rkyv::deserialize::<_, rkyv::rancor::Error>(archived).unwrap();
+    ///
+    /// let map = LeveledHashMap::from_archive(archive).unwrap();
+    ///
+    /// assert_eq!(
+    ///     Some(&2),
+    ///     map.get(&[
+    ///         Arc::new("food".to_string()),
+    ///         Arc::new("dessert".to_string())
+    ///     ])
+    /// );
+    /// ```
+    pub fn get<Q: ?Sized + Hash + Eq>(&self, key_chain: &[&Q]) -> Option<&Archived<V>>
+    where
+        Archived<K>: Borrow<Q>, {
+        let mut parent: Option<&Q> = None;
+        let mut found = None;
+
+        for (level, key) in key_chain.iter().enumerate() {
+            let entry = self.levels.get(level)?.get(*key)?;
+
+            if entry.0.as_ref().map(|pk| pk.borrow()) != parent {
+                return None;
+            }
+
+            parent = Some(*key);
+            found = Some(&entry.1);
+        }
+
+        found
+    }
+
+    /// Get the number of levels in the archive.
+    #[inline]
+    pub fn level_count(&self) -> usize {
+        self.levels.len()
+    }
+}
+
+impl<K: Eq + Hash, V> LeveledHashMap<K, V> {
+    /// Create a `LeveledArchive` of the map by cloning the keys and the values, for serializing it with `rkyv`.
+    #[cfg_attr(docsrs, doc(cfg(feature = "rkyv")))]
+    pub fn to_archive(&self) -> LeveledArchive<K, V>
+    where
+        K: Clone,
+        V: Clone, {
+        LeveledArchive {
+            levels: self
+                .pool
+                .iter()
+                .map(|map| {
+                    map.iter()
+                        .map(|(k, (pk, v))| {
+                            (
+                                k.as_ref().clone(),
+                                (pk.as_ref().map(|pk| pk.as_ref().clone()), v.clone()),
+                            )
+                        })
+                        .collect()
+                })
+                .collect(),
+        }
+    }
+
+    /// Convert a `LeveledArchive` into a map. Like `from_flat_snapshot`, the parent links are validated, so an archive from an untrusted source cannot produce a broken map.
+    #[cfg_attr(docsrs, doc(cfg(feature = "rkyv")))]
+    pub fn from_archive(archive: LeveledArchive<K, V>) -> Result<Self, LeveledHashMapError<K>> {
+        LeveledHashMap::from_flat_snapshot(FlatSnapshot {
+            levels: archive
+                .levels
+                .into_iter()
+                .map(|map| map.into_iter().map(|(k, (pk, v))| (k, pk, v)).collect())
+                .collect(),
+        })
+    }
+}
+
+impl<K: Eq + Hash + Clone, V> From<LeveledHashMap<K, V>> for LeveledArchive<K, V> {
+    /// The values are moved, and the keys are cloned because they are shared inside the map.
+    #[inline]
+    fn from(map: LeveledHashMap<K, V>) -> Self {
+        LeveledArchive {
+            levels: map
+                .pool
+                .into_iter()
+                .map(|map| {
+                    map.into_iter()
+                        .map(|(k, (pk, v))| (unwrap_key(k), (pk.map(unwrap_key), v)))
+                        .collect()
+                })
+                .collect(),
+        }
+    }
+}
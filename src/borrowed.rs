@@ -0,0 +1,136 @@
+use std::{
+    borrow::Borrow,
+    hash::{Hash, Hasher},
+    sync::Arc,
+};
+
+use crate::LeveledHashMap;
+
+/// A key which can be viewed as a borrowed form `Q`. `Arc<K>` can be borrowed as `dyn BorrowedKey<Q>` so that the levels can be queried with `&Q` without constructing `Arc<K>`s.
+pub trait BorrowedKey<Q: ?Sized> {
+    fn borrowed_key(&self) -> &Q;
+}
+
+impl<K: Borrow<Q>, Q: ?Sized> BorrowedKey<Q> for Arc<K> {
+    #[inline]
+    fn borrowed_key(&self) -> &Q {
+        self.as_ref().borrow()
+    }
+}
+
+impl<Q: ?Sized> BorrowedKey<Q> for &Q {
+    #[inline]
+    fn borrowed_key(&self) -> &Q {
+        self
+    }
+}
+
+impl<'a, Q: ?Sized + Hash> Hash for dyn BorrowedKey<Q> + 'a {
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.borrowed_key().hash(state)
+    }
+}
+
+impl<'a, Q: ?Sized + Eq> PartialEq for dyn BorrowedKey<Q> + 'a {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.borrowed_key() == other.borrowed_key()
+    }
+}
+
+impl<'a, Q: ?Sized + Eq> Eq for dyn BorrowedKey<Q> + 'a {}
+
+impl<'a, K: Borrow<Q> + 'a, Q: ?Sized + 'a> Borrow<dyn BorrowedKey<Q> + 'a> for Arc<K> {
+    #[inline]
+    fn borrow(&self) -> &(dyn BorrowedKey<Q> + 'a) {
+        self
+    }
+}
+
+impl<K: Eq + Hash, V> LeveledHashMap<K, V> {
+    /// Get a value by a key chain of borrowed keys which starts at Level 0, without constructing `Arc`s. For example, a `LeveledHashMap<String, V>` can be queried with `&[&str]`.
+    /// ```
+    /// use std::sync::Arc;
+    ///
+    /// use leveled_hash_map::LeveledHashMap;
+    ///
+    /// let mut map: LeveledHashMap<String, u8> = LeveledHashMap::new();
+    ///
+    /// map.insert(&[Arc::new("food".to_string())], 1).unwrap();
+    /// map.insert(
+    ///     &[Arc::new("food".to_string()), Arc::new("dessert".to_string())],
+    ///     2,
+    /// )
+    /// .unwrap();
+    ///
+    /// assert_eq!(Some(&2), map.get_ref::<str>(&["food", "dessert"]));
+    /// assert_eq!(None, map.get_ref::<str>(&["dessert"]));
+    /// ```
+    #[inline]
+    pub fn get_ref<Q: ?Sized + Hash + Eq>(&self, key_chain: &[&Q]) -> Option<&V>
+    where
+        K: Borrow<Q>, {
+        let (_key, value) = self.get_ref_inner(key_chain)?;
+
+        #[cfg(feature = "stats")]
+        self.record_access(key_chain.len() - 1, _key, false);
+
+        Some(value)
+    }
+
+    /// Get a mutable value by a key chain of borrowed keys which starts at Level 0, without constructing `Arc`s. For example, a `LeveledHashMap<String, V>` can be queried with `&[&str]`.
+    /// ```
+    /// use std::sync::Arc;
+    ///
+    /// use leveled_hash_map::LeveledHashMap;
+    ///
+    /// let mut map: LeveledHashMap<String, u8> = LeveledHashMap::new();
+    ///
+    /// map.insert(&[Arc::new("food".to_string())], 1).unwrap();
+    ///
+    /// *map.get_ref_mut::<str>(&["food"]).unwrap() += 1;
+    ///
+    /// assert_eq!(Some(&2), map.get_ref::<str>(&["food"]));
+    /// ```
+    pub fn get_ref_mut<Q: ?Sized + Hash + Eq>(&mut self, key_chain: &[&Q]) -> Option<&mut V>
+    where
+        K: Borrow<Q>, {
+        let key = Arc::clone(self.get_ref_inner(key_chain)?.0);
+
+        let level = key_chain.len() - 1;
+
+        if let Some(generations) = self.generations.as_mut() {
+            generations.mark_changed(level, &key);
+        }
+
+        #[cfg(feature = "stats")]
+        self.record_access(level, &key, true);
+
+        self.pool[level].get_mut(&key).map(|(_, v)| v)
+    }
+
+    /// Find an entry by a key chain of borrowed keys which starts at Level 0, and get its stored key and its value.
+    pub(crate) fn get_ref_inner<Q: ?Sized + Hash + Eq>(
+        &self,
+        key_chain: &[&Q],
+    ) -> Option<(&Arc<K>, &V)>
+    where
+        K: Borrow<Q>, {
+        let mut parent: Option<&Q> = None;
+        let mut found = None;
+
+        for (level, key) in key_chain.iter().enumerate() {
+            let (k, (pk, v)) = self.pool.get(level)?.get_key_value(key as &dyn BorrowedKey<Q>)?;
+
+            if pk.as_ref().map(|pk| pk.as_ref().borrow()) != parent {
+                return None;
+            }
+
+            parent = Some(*key);
+            found = Some((k, v));
+        }
+
+        found
+    }
+}
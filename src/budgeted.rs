@@ -0,0 +1,90 @@
+use std::{hash::Hash, sync::Arc};
+
+use crate::LeveledHashMap;
+
+/// A continuation token of `LeveledHashMap::traverse_budgeted`, which holds the entries waiting to be visited.
+#[derive(Debug, Clone)]
+pub struct TraversalToken<K> {
+    stack: Vec<(usize, Arc<K>)>,
+}
+
+impl<K> TraversalToken<K> {
+    /// Get the number of entries waiting to be visited. Their descendants are not counted.
+    #[inline]
+    pub fn pending(&self) -> usize {
+        self.stack.len()
+    }
+}
+
+impl<K: Eq + Hash, V> LeveledHashMap<K, V> {
+    /// Visit the entries with their key chains in pre-order (parents before their children), but stop after `budget` steps and return a token for resuming the traversal later. Pass `None` to start a new traversal. It returns `None` once the traversal is finished.
+    ///
+    /// The map can be changed between two calls. Entries which have been removed (or moved to another level) before being visited are skipped, and each of them still takes a step. Entries which are inserted under entries which have already been visited are not visited.
+    /// ```
+    /// use std::sync::Arc;
+    ///
+    /// use leveled_hash_map::LeveledHashMap;
+    ///
+    /// let mut map: LeveledHashMap<&'static str, u8> = LeveledHashMap::new();
+    ///
+    /// map.insert(&[Arc::new("food")], 1).unwrap();
+    /// map.insert(&[Arc::new("food"), Arc::new("dessert")], 2).unwrap();
+    /// map.insert(&[Arc::new("food"), Arc::new("meat")], 3).unwrap();
+    ///
+    /// let mut sum = 0;
+    /// let mut ticks = 0;
+    ///
+    /// let mut token = None;
+    ///
+    /// loop {
+    ///     ticks += 1;
+    ///
+    ///     token = map.traverse_budgeted(token, 2, |_, v| sum += v);
+    ///
+    ///     if token.is_none() {
+    ///         break;
+    ///     }
+    /// }
+    ///
+    /// assert_eq!(6, sum);
+    /// assert_eq!(2, ticks);
+    /// ```
+    pub fn traverse_budgeted<F: FnMut(&[Arc<K>], &V)>(
+        &self,
+        token: Option<TraversalToken<K>>,
+        budget: usize,
+        mut f: F,
+    ) -> Option<TraversalToken<K>> {
+        let mut token = match token {
+            Some(token) => token,
+            None => TraversalToken {
+                stack: self
+                    .pool
+                    .first()
+                    .map(|m| m.keys().map(|k| (0, Arc::clone(k))).collect())
+                    .unwrap_or_default(),
+            },
+        };
+
+        for _ in 0..budget {
+            let (level, key) = match token.stack.pop() {
+                Some(entry) => entry,
+                None => break,
+            };
+
+            if !self.pool.get(level).map(|m| m.contains_key(&key)).unwrap_or(false) {
+                continue;
+            }
+
+            f(&self.key_chain_of(level, &key), &self.pool[level][&key].1);
+
+            token.stack.extend(self.sub[level][&key].iter().map(|c| (level + 1, Arc::clone(c))));
+        }
+
+        if token.stack.is_empty() {
+            None
+        } else {
+            Some(token)
+        }
+    }
+}
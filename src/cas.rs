@@ -0,0 +1,119 @@
+use std::{hash::Hash, mem, sync::Arc};
+
+use crate::{LeveledHashMap, LeveledHashMapError};
+
+impl<K: Eq + Hash, V> LeveledHashMap<K, V> {
+    /// Get the generation stamp of an entry by a key chain which starts at Level 0. It returns `None` if generation tracking is not enabled or the entry does not exist. The stamp changes every time the entry is inserted or mutably accessed, so it can be used as a version token for `compare_generation_and_update`.
+    /// ```
+    /// use std::sync::Arc;
+    ///
+    /// use leveled_hash_map::LeveledHashMap;
+    ///
+    /// let mut map: LeveledHashMap<&'static str, u8> = LeveledHashMap::new();
+    ///
+    /// map.track_generations();
+    ///
+    /// map.insert(&[Arc::new("food")], 1).unwrap();
+    ///
+    /// assert_eq!(Some(1), map.entry_generation(&[Arc::new("food")]));
+    /// ```
+    pub fn entry_generation(&self, key_chain: &[Arc<K>]) -> Option<u64> {
+        let generations = self.generations.as_ref()?;
+
+        self.get_inner(key_chain, 0).ok()?;
+
+        let level = key_chain.len() - 1;
+
+        generations.changed.get(level).and_then(|m| m.get(&key_chain[level])).copied()
+    }
+
+    /// Replace the value of an entry by a key chain which starts at Level 0, only if the current value satisfies a predicate. It returns `Ok(old_value)` if the value is replaced, or `Err(value)` to give the new value back if the predicate rejects the current value.
+    /// ```
+    /// use std::sync::Arc;
+    ///
+    /// use leveled_hash_map::LeveledHashMap;
+    ///
+    /// let mut map: LeveledHashMap<&'static str, u32> = LeveledHashMap::new();
+    ///
+    /// map.insert(&[Arc::new("stock")], 10).unwrap();
+    ///
+    /// assert_eq!(
+    ///     Ok(10),
+    ///     map.compare_and_update(&[Arc::new("stock")], |v| *v == 10, 9).unwrap()
+    /// );
+    /// assert_eq!(
+    ///     Err(8),
+    ///     map.compare_and_update(&[Arc::new("stock")], |v| *v == 10, 8).unwrap()
+    /// );
+    /// assert_eq!(Some(&9), map.get(&[Arc::new("stock")]));
+    /// ```
+    pub fn compare_and_update<F: FnOnce(&V) -> bool>(
+        &mut self,
+        key_chain: &[Arc<K>],
+        predicate: F,
+        value: V,
+    ) -> Result<Result<V, V>, LeveledHashMapError<K>> {
+        let (_, current) = self.get_inner(key_chain, 0)?;
+
+        if !predicate(current) {
+            return Ok(Err(value));
+        }
+
+        Ok(Ok(self.replace_value(key_chain, value)))
+    }
+
+    /// Replace the value of an entry by a key chain which starts at Level 0, only if its generation stamp still equals an expected one obtained from `entry_generation`. It returns `Ok(old_value)` if the value is replaced, or `Err(value)` to give the new value back if the entry has been changed in the meantime or generation tracking is not enabled.
+    /// ```
+    /// use std::sync::Arc;
+    ///
+    /// use leveled_hash_map::LeveledHashMap;
+    ///
+    /// let mut map: LeveledHashMap<&'static str, u32> = LeveledHashMap::new();
+    ///
+    /// map.track_generations();
+    ///
+    /// map.insert(&[Arc::new("stock")], 10).unwrap();
+    ///
+    /// let token = map.entry_generation(&[Arc::new("stock")]).unwrap();
+    ///
+    /// assert_eq!(
+    ///     Ok(10),
+    ///     map.compare_generation_and_update(&[Arc::new("stock")], token, 9)
+    ///         .unwrap()
+    /// );
+    /// assert_eq!(
+    ///     Err(8),
+    ///     map.compare_generation_and_update(&[Arc::new("stock")], token, 8)
+    ///         .unwrap()
+    /// );
+    /// ```
+    pub fn compare_generation_and_update(
+        &mut self,
+        key_chain: &[Arc<K>],
+        generation: u64,
+        value: V,
+    ) -> Result<Result<V, V>, LeveledHashMapError<K>> {
+        self.get_inner(key_chain, 0)?;
+
+        if self.entry_generation(key_chain) != Some(generation) {
+            return Ok(Err(value));
+        }
+
+        Ok(Ok(self.replace_value(key_chain, value)))
+    }
+
+    /// Replace the value of an existing entry, stamping and counting it as a write.
+    fn replace_value(&mut self, key_chain: &[Arc<K>], value: V) -> V {
+        let level = key_chain.len() - 1;
+        let key = &key_chain[level];
+
+        if let Some(generations) = self.generations.as_mut() {
+            generations.mark_changed(level, key);
+        }
+
+        #[cfg(feature = "stats")]
+        self.record_access(level, key, true);
+
+        mem::replace(&mut self.pool[level].get_mut(key).unwrap().1, value)
+    }
+}
@@ -0,0 +1,103 @@
+use std::{cmp::Ordering, hash::Hash, sync::Arc, vec};
+
+pub use unicase::UniCase;
+
+use crate::LeveledHashMap;
+
+/// Compare two strings by Unicode case folding first, and fall back to the byte order so that the result is total.
+#[inline]
+fn cmp_caseless(a: &str, b: &str) -> Ordering {
+    UniCase::new(a).cmp(&UniCase::new(b)).then_with(|| a.cmp(b))
+}
+
+impl<K: Eq + Hash, V> LeveledHashMap<K, V> {
+    /// Get the keys at a specific level sorted by Unicode case folding, so that `"apple"`, `"Banana"` and `"cherry"` come out in that order. Keys which only differ in case are sorted by their bytes. To make the keys themselves case-insensitive, use `UniCase<String>` as the key type.
+    /// ```
+    /// use std::sync::Arc;
+    ///
+    /// use leveled_hash_map::{LeveledHashMap, UniCase};
+    ///
+    /// let mut map: LeveledHashMap<String, u8> = LeveledHashMap::new();
+    ///
+    /// map.insert(&[Arc::new("cherry".to_string())], 1).unwrap();
+    /// map.insert(&[Arc::new("Banana".to_string())], 2).unwrap();
+    /// map.insert(&[Arc::new("apple".to_string())], 3).unwrap();
+    ///
+    /// let keys = map.sorted_keys_caseless(0);
+    /// let keys: Vec<&str> = keys.iter().map(|k| k.as_str()).collect();
+    ///
+    /// assert_eq!(vec!["apple", "Banana", "cherry"], keys);
+    ///
+    /// let mut caseless: LeveledHashMap<UniCase<String>, u8> =
+    ///     LeveledHashMap::new();
+    ///
+    /// caseless
+    ///     .insert(&[Arc::new(UniCase::new("Dessert".to_string()))], 1)
+    ///     .unwrap();
+    ///
+    /// assert_eq!(
+    ///     Some(&1),
+    ///     caseless.get(&[Arc::new(UniCase::new("DESSERT".to_string()))])
+    /// );
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "unicase")))]
+    pub fn sorted_keys_caseless(&self, level: usize) -> Vec<Arc<K>>
+    where
+        K: AsRef<str>, {
+        let mut keys: Vec<Arc<K>> = match self.pool.get(level) {
+            Some(map) => map.keys().cloned().collect(),
+            None => return Vec::new(),
+        };
+
+        keys.sort_by(|a, b| cmp_caseless(a.as_ref().as_ref(), b.as_ref().as_ref()));
+
+        keys
+    }
+
+    /// Get an iterator over all entries in depth-first order, where siblings are sorted by Unicode case folding. It yields `(key_chain, value)` tuples, and every key chain starts at Level 0. The order is computed eagerly.
+    /// ```
+    /// use std::sync::Arc;
+    ///
+    /// use leveled_hash_map::LeveledHashMap;
+    ///
+    /// let mut map: LeveledHashMap<&'static str, u8> = LeveledHashMap::new();
+    ///
+    /// map.insert(&[Arc::new("Food")], 1).unwrap();
+    /// map.insert(&[Arc::new("Food"), Arc::new("meat")], 2).unwrap();
+    /// map.insert(&[Arc::new("Food"), Arc::new("Dessert")], 3).unwrap();
+    /// map.insert(&[Arc::new("animal")], 4).unwrap();
+    ///
+    /// let values: Vec<u8> = map.iter_sorted_caseless().map(|(_, v)| *v).collect();
+    ///
+    /// assert_eq!(vec![4, 1, 3, 2], values);
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "unicase")))]
+    pub fn iter_sorted_caseless(&self) -> vec::IntoIter<(Vec<Arc<K>>, &V)>
+    where
+        K: AsRef<str>, {
+        let mut result = Vec::with_capacity(self.pool.iter().map(|m| m.len()).sum());
+
+        let mut path: Vec<Arc<K>> = Vec::new();
+        let mut stack: Vec<(usize, &Arc<K>)> =
+            self.pool.first().map(|m| m.keys().map(|k| (0, k)).collect()).unwrap_or_default();
+
+        // the stack is popped from the end, so it is sorted in reverse
+        stack.sort_by(|a, b| cmp_caseless(b.1.as_ref().as_ref(), a.1.as_ref().as_ref()));
+
+        while let Some((level, key)) = stack.pop() {
+            path.truncate(level);
+            path.push(Arc::clone(key));
+
+            result.push((path.clone(), &self.pool[level][key].1));
+
+            let mut children: Vec<(usize, &Arc<K>)> =
+                self.sub[level][key].iter().map(|c| (level + 1, c)).collect();
+
+            children.sort_by(|a, b| cmp_caseless(b.1.as_ref().as_ref(), a.1.as_ref().as_ref()));
+
+            stack.extend(children);
+        }
+
+        result.into_iter()
+    }
+}
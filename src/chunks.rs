@@ -0,0 +1,116 @@
+use std::{collections::hash_map, hash::Hash, iter::FusedIterator, sync::Arc};
+
+use crate::LeveledHashMap;
+
+/// A chunk of entries created by `LeveledHashMap::export_chunks`. It contains whole top-level subtrees, whose entries are ordered parents first, so it can be imported on its own by inserting the entries in order.
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[derive(Debug, Clone)]
+pub struct ExportChunk<'a, K, V> {
+    /// The key chains and the values of the entries.
+    pub entries: Vec<(Vec<Arc<K>>, &'a V)>,
+}
+
+/// An iterator over the chunks of a `LeveledHashMap`, created by `LeveledHashMap::export_chunks`.
+#[derive(Debug)]
+pub struct ExportChunks<'a, K: Eq + Hash, V> {
+    map:         &'a LeveledHashMap<K, V>,
+    roots:       Option<hash_map::Keys<'a, Arc<K>, (Option<Arc<K>>, V)>>,
+    max_entries: usize,
+    pending:     Option<Vec<(Vec<Arc<K>>, &'a V)>>,
+}
+
+impl<K: Eq + Hash, V> LeveledHashMap<K, V> {
+    /// Split the entries into independent chunks for exporting them in parallel. Top-level subtrees are grouped into chunks of at most `max_entries` entries, but a subtree is never split, so a chunk can be larger if it contains a single large subtree. Use `1` to get one chunk per top-level subtree. The chunks are created lazily.
+    /// ```
+    /// use std::sync::Arc;
+    ///
+    /// use leveled_hash_map::LeveledHashMap;
+    ///
+    /// let mut map: LeveledHashMap<&'static str, u8> = LeveledHashMap::new();
+    ///
+    /// map.insert(&[Arc::new("food")], 1).unwrap();
+    /// map.insert(&[Arc::new("food"), Arc::new("dessert")], 2).unwrap();
+    /// map.insert(&[Arc::new("animal")], 3).unwrap();
+    /// map.insert(&[Arc::new("plant")], 4).unwrap();
+    ///
+    /// assert_eq!(3, map.export_chunks(1).count());
+    /// assert_eq!(1, map.export_chunks(4).count());
+    ///
+    /// let mut copy = LeveledHashMap::new();
+    ///
+    /// for chunk in map.export_chunks(2) {
+    ///     assert!(chunk.entries.len() <= 2);
+    ///
+    ///     for (key_chain, v) in chunk.entries {
+    ///         copy.insert(&key_chain, *v).unwrap();
+    ///     }
+    /// }
+    ///
+    /// assert_eq!(Some(&2), copy.get(&[Arc::new("food"), Arc::new("dessert")]));
+    /// ```
+    #[inline]
+    pub fn export_chunks(&self, max_entries: usize) -> ExportChunks<'_, K, V> {
+        ExportChunks {
+            map:         self,
+            roots:       self.pool.first().map(|m| m.keys()),
+            max_entries: max_entries.max(1),
+            pending:     None,
+        }
+    }
+
+    /// Collect the entries of the subtree of a top-level entry, parents first.
+    fn subtree_entries(&self, root: &Arc<K>) -> Vec<(Vec<Arc<K>>, &V)> {
+        let mut entries = Vec::new();
+
+        let mut path: Vec<Arc<K>> = Vec::new();
+        let mut stack = vec![(0, root)];
+
+        while let Some((level, key)) = stack.pop() {
+            path.truncate(level);
+            path.push(Arc::clone(key));
+
+            entries.push((path.clone(), &self.pool[level][key].1));
+
+            stack.extend(self.sub[level][key].iter().map(|c| (level + 1, c)));
+        }
+
+        entries
+    }
+}
+
+impl<'a, K: Eq + Hash, V> Iterator for ExportChunks<'a, K, V> {
+    type Item = ExportChunk<'a, K, V>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut entries = self.pending.take().unwrap_or_default();
+
+        if let Some(roots) = self.roots.as_mut() {
+            while entries.len() < self.max_entries {
+                let root = match roots.next() {
+                    Some(root) => root,
+                    None => break,
+                };
+
+                let subtree = self.map.subtree_entries(root);
+
+                if !entries.is_empty() && entries.len() + subtree.len() > self.max_entries {
+                    self.pending = Some(subtree);
+
+                    break;
+                }
+
+                entries.extend(subtree);
+            }
+        }
+
+        if entries.is_empty() {
+            None
+        } else {
+            Some(ExportChunk {
+                entries,
+            })
+        }
+    }
+}
+
+impl<'a, K: Eq + Hash, V> FusedIterator for ExportChunks<'a, K, V> {}
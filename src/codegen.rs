@@ -0,0 +1,355 @@
+//! A helper for build scripts to turn a fixed hierarchy in a JSON or CSV file into Rust code which constructs a `LeveledHashMap<&'static str, V>`, so the hierarchy does not need to be parsed at runtime.
+//!
+//! The hierarchy is validated when the code is generated, so the generated function can only fail if the code is edited by hand. Wrap the function with `std::sync::OnceLock` to build the map only once.
+//!
+//! ```no_run
+//! // build.rs
+//! use std::{env, fs, path::Path};
+//!
+//! use leveled_hash_map::codegen::Codegen;
+//!
+//! let csv = fs::read_to_string("countries.csv").unwrap();
+//! let code = Codegen::new("countries").generate_csv(&csv).unwrap();
+//!
+//! fs::write(
+//!     Path::new(&env::var("OUT_DIR").unwrap()).join("countries.rs"),
+//!     code,
+//! )
+//! .unwrap();
+//!
+//! println!("cargo:rerun-if-changed=countries.csv");
+//!
+//! // src/main.rs
+//! // include!(concat!(env!("OUT_DIR"), "/countries.rs"));
+//! ```
+
+use std::{
+    collections::HashMap,
+    error::Error,
+    fmt::{self, Debug, Display, Formatter, Write},
+    sync::Arc,
+};
+
+use serde_json::Value;
+
+use crate::{LeveledHashMap, LeveledHashMapError, Node};
+
+/// Possible errors come from generating code with `Codegen`.
+pub enum CodegenError {
+    /// The JSON input cannot be deserialized.
+    Json(serde_json::Error),
+    /// A line of the CSV input is malformed.
+    Csv { line: usize, message: &'static str },
+    /// A value is not a string, a number or a boolean, so it cannot be written as a Rust literal.
+    UnsupportedValue { key_chain: Vec<String> },
+    /// The hierarchy cannot be built into a map.
+    Map(LeveledHashMapError<String>),
+}
+
+impl Debug for CodegenError {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter) -> Result<(), fmt::Error> {
+        match self {
+            CodegenError::Json(err) => Debug::fmt(err, f),
+            CodegenError::Csv {
+                line,
+                message,
+            } => {
+                let mut s = f.debug_struct("Csv");
+                s.field("Line", line);
+                s.field("Message", message);
+                s.finish()
+            },
+            CodegenError::UnsupportedValue {
+                key_chain,
+            } => {
+                let mut s = f.debug_struct("UnsupportedValue");
+                s.field("KeyChain", key_chain);
+                s.finish()
+            },
+            CodegenError::Map(err) => Debug::fmt(err, f),
+        }
+    }
+}
+
+impl Display for CodegenError {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter) -> Result<(), fmt::Error> {
+        match self {
+            CodegenError::Json(err) => Display::fmt(err, f),
+            CodegenError::Csv {
+                line,
+                message,
+            } => f.write_fmt(format_args!("Line {}: {}", line, message)),
+            CodegenError::UnsupportedValue {
+                key_chain,
+            } => f.write_fmt(format_args!(
+                "The value of {:?} is not a string, a number or a boolean.",
+                key_chain
+            )),
+            CodegenError::Map(err) => Display::fmt(err, f),
+        }
+    }
+}
+
+impl Error for CodegenError {}
+
+impl From<serde_json::Error> for CodegenError {
+    #[inline]
+    fn from(err: serde_json::Error) -> Self {
+        CodegenError::Json(err)
+    }
+}
+
+impl From<LeveledHashMapError<String>> for CodegenError {
+    #[inline]
+    fn from(err: LeveledHashMapError<String>) -> Self {
+        CodegenError::Map(err)
+    }
+}
+
+/// Options of the generated code. The generated function is public, takes no arguments and returns a `LeveledHashMap<&'static str, V>`, where `V` is `&'static str` by default.
+#[derive(Debug, Clone)]
+pub struct Codegen {
+    fn_name:    String,
+    value_type: String,
+    separator:  char,
+}
+
+impl Codegen {
+    /// Create the options to generate a function with a specific name.
+    #[inline]
+    pub fn new<S: Into<String>>(fn_name: S) -> Codegen {
+        Codegen {
+            fn_name:    fn_name.into(),
+            value_type: String::from("&'static str"),
+            separator:  '/',
+        }
+    }
+
+    /// Set the value type of the generated map. It needs to match the literals of the values, such as `u32` for JSON numbers without fractions.
+    #[inline]
+    pub fn value_type<S: Into<String>>(mut self, value_type: S) -> Codegen {
+        self.value_type = value_type.into();
+
+        self
+    }
+
+    /// Set the separator of the keys in the paths of the CSV input. The default separator is `/`.
+    #[inline]
+    pub fn separator(mut self, separator: char) -> Codegen {
+        self.separator = separator;
+
+        self
+    }
+
+    /// Generate code from JSON in the nested representation of `Node`, which is an object of keys to objects with a `value` and optional `children`. Values need to be strings, numbers or booleans.
+    /// ```
+    /// use leveled_hash_map::codegen::Codegen;
+    ///
+    /// let json = r#"{"asia": {"value": 1, "children": {"japan": {"value": 2}}}}"#;
+    ///
+    /// let code =
+    ///     Codegen::new("regions").value_type("u8").generate_json(json).unwrap();
+    ///
+    /// assert!(code.contains(
+    ///     "pub fn regions() -> ::leveled_hash_map::LeveledHashMap<&'static str, \
+    ///      u8> {"
+    /// ));
+    /// assert!(code.contains(r#"(&["asia", "japan"], 2),"#));
+    /// ```
+    pub fn generate_json(&self, json: &str) -> Result<String, CodegenError> {
+        let tree: HashMap<String, Node<String, Value>> = serde_json::from_str(json)?;
+
+        let map = LeveledHashMap::from_tree(tree)?;
+
+        let mut entries = Vec::new();
+
+        for (level, pool) in map.pool.iter().enumerate() {
+            for (key, (_, value)) in pool.iter() {
+                let key_chain = string_key_chain(&map, level, key);
+
+                let literal = match value {
+                    Value::String(s) => format!("{:?}", s),
+                    Value::Number(n) => n.to_string(),
+                    Value::Bool(b) => b.to_string(),
+                    _ => {
+                        return Err(CodegenError::UnsupportedValue {
+                            key_chain,
+                        })
+                    },
+                };
+
+                entries.push((key_chain, literal));
+            }
+        }
+
+        Ok(self.render(entries))
+    }
+
+    /// Generate code from CSV with one `path,value` record per line and no header. A path is made of keys joined by the separator, and parents can be listed after their children. Fields can be quoted with `"`, and the values are written as string literals.
+    /// ```
+    /// use leveled_hash_map::codegen::Codegen;
+    ///
+    /// let csv =
+    ///     "asia/japan,Japan\nasia,Asia\n\"europe\",\"Europe, the continent\"\n";
+    ///
+    /// let code = Codegen::new("regions").generate_csv(csv).unwrap();
+    ///
+    /// assert_eq!(
+    ///     concat!(
+    ///         "pub fn regions() -> ::leveled_hash_map::LeveledHashMap<&'static \
+    ///          str, &'static str> {\n",
+    ///         "    ::leveled_hash_map::LeveledHashMap::from_static_paths(&[\n",
+    ///         "        (&[\"asia\"], \"Asia\"),\n",
+    ///         "        (&[\"asia\", \"japan\"], \"Japan\"),\n",
+    ///         "        (&[\"europe\"], \"Europe, the continent\"),\n",
+    ///         "    ])\n",
+    ///         "    .unwrap()\n",
+    ///         "}\n",
+    ///     ),
+    ///     code
+    /// );
+    /// ```
+    pub fn generate_csv(&self, csv: &str) -> Result<String, CodegenError> {
+        let mut records = Vec::new();
+
+        for (i, s) in csv.lines().enumerate() {
+            let line = i + 1;
+
+            if s.trim().is_empty() {
+                continue;
+            }
+
+            let mut fields = parse_csv_line(s).map_err(|message| CodegenError::Csv {
+                line,
+                message,
+            })?;
+
+            if fields.len() != 2 {
+                return Err(CodegenError::Csv {
+                    line,
+                    message: "A record needs exactly two fields.",
+                });
+            }
+
+            let value = fields.pop().unwrap();
+            let path = fields.pop().unwrap();
+
+            let key_chain: Vec<Arc<String>> =
+                path.split(self.separator).map(|key| Arc::new(String::from(key))).collect();
+
+            records.push((key_chain, value));
+        }
+
+        records.sort_by_key(|(key_chain, _)| key_chain.len());
+
+        let mut map = LeveledHashMap::new();
+
+        for (key_chain, value) in records {
+            map.insert(&key_chain, value)?;
+        }
+
+        let mut entries = Vec::new();
+
+        for (level, pool) in map.pool.iter().enumerate() {
+            for (key, (_, value)) in pool.iter() {
+                entries.push((string_key_chain(&map, level, key), format!("{:?}", value)));
+            }
+        }
+
+        Ok(self.render(entries))
+    }
+
+    fn render(&self, mut entries: Vec<(Vec<String>, String)>) -> String {
+        entries.sort();
+
+        let mut code = String::new();
+
+        writeln!(
+            code,
+            "pub fn {}() -> ::leveled_hash_map::LeveledHashMap<&'static str, {}> {{",
+            self.fn_name, self.value_type
+        )
+        .unwrap();
+        code.push_str("    ::leveled_hash_map::LeveledHashMap::from_static_paths(&[\n");
+
+        for (key_chain, literal) in entries {
+            let keys: Vec<String> = key_chain.iter().map(|key| format!("{:?}", key)).collect();
+
+            writeln!(code, "        (&[{}], {}),", keys.join(", "), literal).unwrap();
+        }
+
+        code.push_str("    ])\n");
+        code.push_str("    .unwrap()\n");
+        code.push_str("}\n");
+
+        code
+    }
+}
+
+#[inline]
+fn string_key_chain<V>(
+    map: &LeveledHashMap<String, V>,
+    level: usize,
+    key: &Arc<String>,
+) -> Vec<String> {
+    map.key_chain_of(level, key).into_iter().map(|key| key.as_ref().clone()).collect()
+}
+
+/// Split a CSV line into fields. Quoted fields can contain separators and `""` for a quote.
+fn parse_csv_line(s: &str) -> Result<Vec<String>, &'static str> {
+    let mut fields = Vec::new();
+    let mut chars = s.chars().peekable();
+
+    loop {
+        let mut field = String::new();
+
+        if chars.peek() == Some(&'"') {
+            chars.next();
+
+            loop {
+                match chars.next() {
+                    Some('"') => {
+                        if chars.peek() == Some(&'"') {
+                            chars.next();
+                            field.push('"');
+                        } else {
+                            break;
+                        }
+                    },
+                    Some(c) => field.push(c),
+                    None => return Err("A quoted field is not closed."),
+                }
+            }
+
+            match chars.next() {
+                Some(',') => {
+                    fields.push(field);
+                },
+                None => {
+                    fields.push(field);
+
+                    return Ok(fields);
+                },
+                Some(_) => return Err("A quoted field is followed by unexpected characters."),
+            }
+        } else {
+            loop {
+                match chars.next() {
+                    Some(',') => {
+                        fields.push(field);
+
+                        break;
+                    },
+                    Some(c) => field.push(c),
+                    None => {
+                        fields.push(field);
+
+                        return Ok(fields);
+                    },
+                }
+            }
+        }
+    }
+}
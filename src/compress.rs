@@ -0,0 +1,173 @@
+use std::{borrow::Cow, hash::Hash, sync::Arc};
+
+use miniz_oxide::{deflate::compress_to_vec, inflate::decompress_to_vec};
+
+use crate::{LeveledHashMap, LeveledHashMapError};
+
+/// The default size in bytes above which `Compressed` values are compressed.
+pub const DEFAULT_COMPRESSION_THRESHOLD: usize = 1024;
+
+/// Types which can be stored in `Compressed` values.
+pub trait Codec: Sized {
+    /// Get the bytes of the value.
+    fn to_bytes(&self) -> Cow<'_, [u8]>;
+
+    /// Rebuild a value from the bytes obtained by `to_bytes`.
+    fn from_bytes(bytes: Vec<u8>) -> Self;
+}
+
+impl Codec for Vec<u8> {
+    #[inline]
+    fn to_bytes(&self) -> Cow<'_, [u8]> {
+        Cow::Borrowed(self)
+    }
+
+    #[inline]
+    fn from_bytes(bytes: Vec<u8>) -> Self {
+        bytes
+    }
+}
+
+impl Codec for String {
+    #[inline]
+    fn to_bytes(&self) -> Cow<'_, [u8]> {
+        Cow::Borrowed(self.as_bytes())
+    }
+
+    #[inline]
+    fn from_bytes(bytes: Vec<u8>) -> Self {
+        match String::from_utf8(bytes) {
+            Ok(s) => s,
+            Err(err) => String::from_utf8_lossy(err.as_bytes()).into_owned(),
+        }
+    }
+}
+
+/// A value which is compressed with DEFLATE if it is larger than a threshold, and decompressed on access. It can be used as the value type of a `LeveledHashMap` to store large text or binary payloads.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Compressed<T> {
+    repr: Repr<T>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Repr<T> {
+    Plain(T),
+    Deflated(Box<[u8]>),
+}
+
+impl<T: Codec> Compressed<T> {
+    /// Wrap a value, compressing it if it is larger than `DEFAULT_COMPRESSION_THRESHOLD` bytes.
+    #[inline]
+    pub fn new(value: T) -> Compressed<T> {
+        Self::with_threshold(value, DEFAULT_COMPRESSION_THRESHOLD)
+    }
+
+    /// Wrap a value, compressing it if it is larger than `threshold` bytes. The value is kept as it is if compressing does not make it smaller.
+    /// ```
+    /// use leveled_hash_map::Compressed;
+    ///
+    /// let text = "food ".repeat(100);
+    ///
+    /// let value = Compressed::with_threshold(text.clone(), 64);
+    ///
+    /// assert!(value.is_compressed());
+    /// assert!(value.stored_len() < text.len());
+    /// assert_eq!(text, *value.get());
+    ///
+    /// assert!(!Compressed::with_threshold("food".to_string(), 64).is_compressed());
+    /// ```
+    pub fn with_threshold(value: T, threshold: usize) -> Compressed<T> {
+        let bytes = value.to_bytes();
+
+        if bytes.len() > threshold {
+            let deflated = compress_to_vec(&bytes, 6);
+
+            if deflated.len() < bytes.len() {
+                return Compressed {
+                    repr: Repr::Deflated(deflated.into_boxed_slice())
+                };
+            }
+        }
+
+        Compressed {
+            repr: Repr::Plain(value)
+        }
+    }
+
+    /// Get the value, decompressing it if it is compressed.
+    #[inline]
+    pub fn get(&self) -> Cow<'_, T>
+    where
+        T: Clone, {
+        match &self.repr {
+            Repr::Plain(value) => Cow::Borrowed(value),
+            Repr::Deflated(bytes) => Cow::Owned(inflate(bytes)),
+        }
+    }
+
+    /// Unwrap the value, decompressing it if it is compressed.
+    #[inline]
+    pub fn into_inner(self) -> T {
+        match self.repr {
+            Repr::Plain(value) => value,
+            Repr::Deflated(bytes) => inflate(&bytes),
+        }
+    }
+
+    /// Whether the value is stored compressed.
+    #[inline]
+    pub fn is_compressed(&self) -> bool {
+        matches!(self.repr, Repr::Deflated(_))
+    }
+
+    /// Get the size in bytes of the stored data.
+    #[inline]
+    pub fn stored_len(&self) -> usize {
+        match &self.repr {
+            Repr::Plain(value) => value.to_bytes().len(),
+            Repr::Deflated(bytes) => bytes.len(),
+        }
+    }
+}
+
+#[inline]
+fn inflate<T: Codec>(bytes: &[u8]) -> T {
+    T::from_bytes(decompress_to_vec(bytes).expect("the compressed data is corrupted"))
+}
+
+impl<K: Eq + Hash, T: Codec> LeveledHashMap<K, Compressed<T>> {
+    /// Insert a value by a key chain which starts at Level 0, compressing it if it is larger than `DEFAULT_COMPRESSION_THRESHOLD` bytes. It returns the old value, decompressed, if any.
+    /// ```
+    /// use std::sync::Arc;
+    ///
+    /// use leveled_hash_map::{Compressed, LeveledHashMap};
+    ///
+    /// let mut map: LeveledHashMap<&'static str, Compressed<String>> =
+    ///     LeveledHashMap::new();
+    ///
+    /// let blob = "{\"food\": true}".repeat(1000);
+    ///
+    /// map.insert_compressed(&[Arc::new("food")], blob.clone()).unwrap();
+    ///
+    /// assert!(map.get(&[Arc::new("food")]).unwrap().is_compressed());
+    /// assert_eq!(blob, *map.get_decompressed(&[Arc::new("food")]).unwrap());
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "compress")))]
+    #[inline]
+    pub fn insert_compressed(
+        &mut self,
+        key_chain: &[Arc<K>],
+        value: T,
+    ) -> Result<Option<T>, LeveledHashMapError<K>> {
+        self.insert(key_chain, Compressed::new(value)).map(|v| v.map(Compressed::into_inner))
+    }
+
+    /// Get a value by a key chain which starts at Level 0, decompressing it if it is compressed.
+    #[cfg_attr(docsrs, doc(cfg(feature = "compress")))]
+    #[inline]
+    pub fn get_decompressed(&self, key_chain: &[Arc<K>]) -> Option<Cow<'_, T>>
+    where
+        T: Clone, {
+        self.get(key_chain).map(Compressed::get)
+    }
+}
@@ -0,0 +1,137 @@
+use std::{hash::Hash, sync::Arc};
+
+use crate::LeveledHashMap;
+
+/// The maximum number of siblings recorded in a `ContextSnapshot`.
+pub const CONTEXT_SIBLINGS_LIMIT: usize = 16;
+
+/// A small summary of how a key chain relates to a `LeveledHashMap`, for enriching errors or logs when a lookup fails. It is created by `LeveledHashMap::context_snapshot`.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ContextSnapshot<K> {
+    /// The longest prefix of the key chain which exists in the map.
+    pub existing_prefix:  Vec<Arc<K>>,
+    /// The first key of the key chain which does not exist under the existing prefix, or `None` if the whole key chain exists.
+    pub failing_key:      Option<Arc<K>>,
+    /// The key chain where the failing key actually exists, if it exists at the same level under another parent.
+    pub actual_key_chain: Option<Vec<Arc<K>>>,
+    /// At most `CONTEXT_SIBLINGS_LIMIT` keys at the level of the failing key which have the same parent as it was expected to have.
+    pub siblings:         Vec<Arc<K>>,
+    /// The total number of such siblings.
+    pub siblings_count:   usize,
+}
+
+impl<K> ContextSnapshot<K> {
+    /// The key of the nearest existing ancestor of the failing key, or `None` if the failing key is at Level 0.
+    #[inline]
+    pub fn nearest_ancestor(&self) -> Option<&Arc<K>> {
+        self.existing_prefix.last()
+    }
+
+    /// Whether the whole key chain exists in the map.
+    #[inline]
+    pub fn is_complete(&self) -> bool {
+        self.failing_key.is_none()
+    }
+}
+
+impl<K: Eq + Hash, V> LeveledHashMap<K, V> {
+    /// Find out how many keys at the start of a key chain which starts at Level 0 exist in the map, and get the value of the deepest existing entry. The value is `None` if not even the first key exists.
+    /// ```
+    /// use std::sync::Arc;
+    ///
+    /// use leveled_hash_map::LeveledHashMap;
+    ///
+    /// let mut map: LeveledHashMap<&'static str, u8> = LeveledHashMap::new();
+    ///
+    /// map.insert(&[Arc::new("food")], 1).unwrap();
+    /// map.insert(&[Arc::new("food"), Arc::new("dessert")], 2).unwrap();
+    ///
+    /// assert_eq!(
+    ///     (2, Some(&2)),
+    ///     map.deepest_existing_prefix(&[
+    ///         Arc::new("food"),
+    ///         Arc::new("dessert"),
+    ///         Arc::new("cake")
+    ///     ])
+    /// );
+    /// assert_eq!((0, None), map.deepest_existing_prefix(&[Arc::new("animal")]));
+    /// ```
+    pub fn deepest_existing_prefix(&self, key_chain: &[Arc<K>]) -> (usize, Option<&V>) {
+        let len = self.existing_prefix_len(key_chain);
+
+        if len == 0 {
+            (0, None)
+        } else {
+            (len, Some(&self.pool[len - 1][&key_chain[len - 1]].1))
+        }
+    }
+
+    /// Capture a `ContextSnapshot` describing how far a key chain which starts at Level 0 matches the map, which key fails to match, where that key actually lives, and which keys could have been meant instead.
+    /// ```
+    /// use std::sync::Arc;
+    ///
+    /// use leveled_hash_map::LeveledHashMap;
+    ///
+    /// let mut map: LeveledHashMap<&'static str, u8> = LeveledHashMap::new();
+    ///
+    /// map.insert(&[Arc::new("food")], 1).unwrap();
+    /// map.insert(&[Arc::new("food"), Arc::new("dessert")], 2).unwrap();
+    /// map.insert(&[Arc::new("animal")], 3).unwrap();
+    /// map.insert(&[Arc::new("animal"), Arc::new("dog")], 4).unwrap();
+    ///
+    /// let snapshot = map.context_snapshot(&[Arc::new("food"), Arc::new("dog")]);
+    ///
+    /// assert_eq!(vec![Arc::new("food")], snapshot.existing_prefix);
+    /// assert_eq!(Some(Arc::new("dog")), snapshot.failing_key);
+    /// assert_eq!(
+    ///     Some(vec![Arc::new("animal"), Arc::new("dog")]),
+    ///     snapshot.actual_key_chain
+    /// );
+    /// assert_eq!(vec![Arc::new("dessert")], snapshot.siblings);
+    /// ```
+    pub fn context_snapshot(&self, key_chain: &[Arc<K>]) -> ContextSnapshot<K> {
+        let level = self.existing_prefix_len(key_chain);
+
+        let existing_prefix = key_chain[..level].to_vec();
+
+        let failing_key = match key_chain.get(level) {
+            Some(key) => Arc::clone(key),
+            None => {
+                return ContextSnapshot {
+                    existing_prefix,
+                    failing_key: None,
+                    actual_key_chain: None,
+                    siblings: Vec::new(),
+                    siblings_count: 0,
+                }
+            },
+        };
+
+        let actual_key_chain = match self.pool.get(level) {
+            Some(map) => map.get_key_value(&failing_key).map(|(k, _)| self.key_chain_of(level, k)),
+            None => None,
+        };
+
+        let (siblings, siblings_count) = if level == 0 {
+            match self.pool.first() {
+                Some(map) => {
+                    (map.keys().take(CONTEXT_SIBLINGS_LIMIT).cloned().collect(), map.len())
+                },
+                None => (Vec::new(), 0),
+            }
+        } else {
+            let children = &self.sub[level - 1][&key_chain[level - 1]];
+
+            (children.iter().take(CONTEXT_SIBLINGS_LIMIT).cloned().collect(), children.len())
+        };
+
+        ContextSnapshot {
+            existing_prefix,
+            failing_key: Some(failing_key),
+            actual_key_chain,
+            siblings,
+            siblings_count,
+        }
+    }
+}
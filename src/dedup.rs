@@ -0,0 +1,229 @@
+use std::{
+    collections::{hash_map::DefaultHasher, HashMap},
+    hash::{Hash, Hasher},
+    sync::Arc,
+};
+
+use crate::{digest::combine, LeveledHashMap};
+
+/// A node of the shared-subtree representation of a `LeveledHashMap`, created by `LeveledHashMap::to_shared_tree`. Identical subtrees are represented by the same `Arc<SharedNode>`.
+#[derive(Debug)]
+pub struct SharedNode<'a, K, V> {
+    /// The value of the node.
+    pub value:    &'a V,
+    /// The children of the node, along with their keys.
+    pub children: Vec<(Arc<K>, Arc<SharedNode<'a, K, V>>)>,
+}
+
+impl<'a, K: Eq, V: Eq> SharedNode<'a, K, V> {
+    /// Check whether two nodes are identical, assuming that their children have been shared.
+    fn is_identical(&self, other: &Self) -> bool {
+        self.value == other.value
+            && self.children.len() == other.children.len()
+            && self
+                .children
+                .iter()
+                .all(|(k, n)| other.children.iter().any(|(ok, on)| k == ok && Arc::ptr_eq(n, on)))
+    }
+}
+
+type Buckets<'a, K, V> = HashMap<u64, Vec<Arc<SharedNode<'a, K, V>>>>;
+
+impl<K: Eq + Hash, V: Eq + Hash> LeveledHashMap<K, V> {
+    /// Convert the map into a shared-subtree representation, in which subtrees with equal values and equal descendants (compared by both keys and values) are stored only once. The keys of the subtree roots themselves are not compared. It returns the nodes at Level 0 along with their keys.
+    /// ```
+    /// use std::sync::Arc;
+    ///
+    /// use leveled_hash_map::LeveledHashMap;
+    ///
+    /// let mut map: LeveledHashMap<&'static str, u8> = LeveledHashMap::new();
+    ///
+    /// map.insert(&[Arc::new("shirt")], 1).unwrap();
+    /// map.insert(&[Arc::new("shirt"), Arc::new("options")], 0).unwrap();
+    /// map.insert(&[Arc::new("shirt"), Arc::new("options"), Arc::new("color")], 0)
+    ///     .unwrap();
+    /// map.insert(&[Arc::new("hat")], 2).unwrap();
+    /// map.insert(&[Arc::new("hat"), Arc::new("accessory")], 3).unwrap();
+    /// map.insert(
+    ///     &[Arc::new("hat"), Arc::new("accessory"), Arc::new("preset")],
+    ///     0,
+    /// )
+    /// .unwrap();
+    /// map.insert(
+    ///     &[
+    ///         Arc::new("hat"),
+    ///         Arc::new("accessory"),
+    ///         Arc::new("preset"),
+    ///         Arc::new("color"),
+    ///     ],
+    ///     0,
+    /// )
+    /// .unwrap();
+    ///
+    /// let roots = map.to_shared_tree();
+    ///
+    /// let shirt = &roots.iter().find(|(k, _)| *k.as_ref() == "shirt").unwrap().1;
+    /// let hat = &roots.iter().find(|(k, _)| *k.as_ref() == "hat").unwrap().1;
+    ///
+    /// let options = &shirt.children[0].1;
+    /// let preset = &hat.children[0].1.children[0].1;
+    ///
+    /// assert!(Arc::ptr_eq(options, preset));
+    /// ```
+    pub fn to_shared_tree(&self) -> Vec<(Arc<K>, Arc<SharedNode<'_, K, V>>)> {
+        let mut buckets = HashMap::new();
+
+        match self.pool.first() {
+            Some(map) => map
+                .keys()
+                .map(|k| {
+                    let (_, node) = self.share_node(0, k, &mut buckets, &mut |_, _, _| ());
+
+                    (Arc::clone(k), node)
+                })
+                .collect(),
+            None => Vec::new(),
+        }
+    }
+
+    /// Find groups of identical subtrees, whose roots have equal values and whose descendants are equal by both keys and values. Every group is reported as the key chains of the subtree roots. Leaves are not reported, and neither are the subtrees inside larger identical subtrees.
+    /// ```
+    /// use std::sync::Arc;
+    ///
+    /// use leveled_hash_map::LeveledHashMap;
+    ///
+    /// let mut map: LeveledHashMap<&'static str, u8> = LeveledHashMap::new();
+    ///
+    /// map.insert(&[Arc::new("shirt")], 1).unwrap();
+    /// map.insert(&[Arc::new("shirt"), Arc::new("options")], 0).unwrap();
+    /// map.insert(&[Arc::new("shirt"), Arc::new("options"), Arc::new("color")], 0)
+    ///     .unwrap();
+    /// map.insert(&[Arc::new("hat")], 2).unwrap();
+    /// map.insert(&[Arc::new("hat"), Arc::new("accessory")], 3).unwrap();
+    /// map.insert(
+    ///     &[Arc::new("hat"), Arc::new("accessory"), Arc::new("preset")],
+    ///     0,
+    /// )
+    /// .unwrap();
+    /// map.insert(
+    ///     &[
+    ///         Arc::new("hat"),
+    ///         Arc::new("accessory"),
+    ///         Arc::new("preset"),
+    ///         Arc::new("color"),
+    ///     ],
+    ///     0,
+    /// )
+    /// .unwrap();
+    ///
+    /// let groups = map.find_identical_subtrees();
+    ///
+    /// assert_eq!(1, groups.len());
+    /// assert_eq!(2, groups[0].len());
+    /// assert!(groups[0].contains(&vec![Arc::new("shirt"), Arc::new("options")]));
+    /// assert!(groups[0].contains(&vec![
+    ///     Arc::new("hat"),
+    ///     Arc::new("accessory"),
+    ///     Arc::new("preset")
+    /// ]));
+    /// ```
+    pub fn find_identical_subtrees(&self) -> Vec<Vec<Vec<Arc<K>>>> {
+        let mut buckets = HashMap::new();
+
+        // (level, key) of every entry, grouped by the address of its shared node
+        let mut groups: HashMap<usize, Vec<(usize, Arc<K>)>> = HashMap::new();
+        let mut group_of: Vec<HashMap<Arc<K>, usize>> = vec![HashMap::new(); self.pool.len()];
+
+        if let Some(map) = self.pool.first() {
+            for k in map.keys() {
+                self.share_node(0, k, &mut buckets, &mut |level, key, node| {
+                    if !node.children.is_empty() {
+                        let address = Arc::as_ptr(node) as usize;
+
+                        groups.entry(address).or_default().push((level, Arc::clone(key)));
+                        group_of[level].insert(Arc::clone(key), address);
+                    }
+                });
+            }
+        }
+
+        let mut result = Vec::new();
+
+        for members in groups.values() {
+            if members.len() < 2 {
+                continue;
+            }
+
+            let members: Vec<Vec<Arc<K>>> = members
+                .iter()
+                .filter(|(level, key)| {
+                    // skip the subtrees inside larger identical subtrees
+                    match self.pool[*level][key].0.as_ref() {
+                        Some(pk) => group_of[*level - 1]
+                            .get(pk)
+                            .map(|address| groups[address].len() < 2)
+                            .unwrap_or(true),
+                        None => true,
+                    }
+                })
+                .map(|(level, key)| self.key_chain_of(*level, key))
+                .collect();
+
+            if members.len() >= 2 {
+                result.push(members);
+            }
+        }
+
+        result
+    }
+
+    fn share_node<'a, F: FnMut(usize, &Arc<K>, &Arc<SharedNode<'a, K, V>>)>(
+        &'a self,
+        level: usize,
+        key: &Arc<K>,
+        buckets: &mut Buckets<'a, K, V>,
+        visit: &mut F,
+    ) -> (u64, Arc<SharedNode<'a, K, V>>) {
+        let value = &self.pool[level][key].1;
+
+        let mut children = Vec::new();
+        let mut children_digests = Vec::new();
+
+        for c in self.sub[level][key].iter() {
+            let (digest, node) = self.share_node(level + 1, c, buckets, visit);
+
+            let mut hasher = DefaultHasher::new();
+            c.hash(&mut hasher);
+
+            children_digests.push(combine(hasher.finish(), vec![digest]));
+            children.push((Arc::clone(c), node));
+        }
+
+        let mut hasher = DefaultHasher::new();
+        value.hash(&mut hasher);
+
+        let digest = combine(hasher.finish(), children_digests);
+
+        let node = SharedNode {
+            value,
+            children,
+        };
+
+        let bucket = buckets.entry(digest).or_default();
+
+        let node = match bucket.iter().find(|n| n.is_identical(&node)) {
+            Some(n) => Arc::clone(n),
+            None => {
+                let node = Arc::new(node);
+
+                bucket.push(Arc::clone(&node));
+
+                node
+            },
+        };
+
+        visit(level, key, &node);
+
+        (digest, node)
+    }
+}
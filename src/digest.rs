@@ -0,0 +1,123 @@
+use std::{
+    collections::hash_map::DefaultHasher,
+    hash::{Hash, Hasher},
+    sync::Arc,
+};
+
+use crate::{LeveledHashMap, LeveledHashMapError};
+
+const FNV_OFFSET_BASIS: u64 = 0xCBF29CE484222325;
+const FNV_PRIME: u64 = 0x100000001B3;
+
+/// Combine a digest of an entry and the digests of its children with the FNV-1a algorithm. The children digests are sorted first so that the result does not depend on the iteration order.
+pub(crate) fn combine(own: u64, mut children: Vec<u64>) -> u64 {
+    children.sort_unstable();
+
+    let mut hash = FNV_OFFSET_BASIS;
+
+    for d in Some(own).into_iter().chain(children) {
+        for b in d.to_le_bytes() {
+            hash ^= b as u64;
+            hash = hash.wrapping_mul(FNV_PRIME);
+        }
+    }
+
+    hash
+}
+
+impl<K: Eq + Hash, V> LeveledHashMap<K, V> {
+    /// Compute a Merkle-style hash covering an entry and all of its descendants by a key chain which starts at Level 0. If the key chain is empty, the hash covers the whole map. Two subtrees with the same keys and values have the same hash, so replicas can locate divergent branches by comparing the hashes from the top down.
+    ///
+    /// Keys and values are hashed by `DefaultHasher`, whose algorithm is not guaranteed to be the same across Rust releases. Use `subtree_digest` if the hashes need to be compared between different builds.
+    /// ```
+    /// use std::sync::Arc;
+    ///
+    /// use leveled_hash_map::LeveledHashMap;
+    ///
+    /// let mut a: LeveledHashMap<&'static str, u8> = LeveledHashMap::new();
+    ///
+    /// a.insert(&[Arc::new("food")], 1).unwrap();
+    /// a.insert(&[Arc::new("food"), Arc::new("dessert")], 2).unwrap();
+    /// a.insert(&[Arc::new("animal")], 3).unwrap();
+    ///
+    /// let mut b: LeveledHashMap<&'static str, u8> = LeveledHashMap::new();
+    ///
+    /// b.insert(&[Arc::new("food")], 1).unwrap();
+    /// b.insert(&[Arc::new("food"), Arc::new("dessert")], 2).unwrap();
+    /// b.insert(&[Arc::new("animal")], 4).unwrap();
+    ///
+    /// assert_ne!(a.subtree_hash(&[]).unwrap(), b.subtree_hash(&[]).unwrap());
+    /// assert_eq!(
+    ///     a.subtree_hash(&[Arc::new("food")]).unwrap(),
+    ///     b.subtree_hash(&[Arc::new("food")]).unwrap()
+    /// );
+    /// ```
+    #[inline]
+    pub fn subtree_hash(&self, key_chain: &[Arc<K>]) -> Result<u64, LeveledHashMapError<K>>
+    where
+        K: Hash,
+        V: Hash, {
+        self.subtree_digest(key_chain, |k, v| {
+            let mut hasher = DefaultHasher::new();
+
+            k.hash(&mut hasher);
+            v.hash(&mut hasher);
+
+            hasher.finish()
+        })
+    }
+
+    /// Compute a Merkle-style hash covering an entry and all of its descendants by a key chain which starts at Level 0, with a closure computing the digest of each key-value pair. If the key chain is empty, the hash covers the whole map. The digests are combined with the FNV-1a algorithm, so the result is stable as long as the closure is.
+    /// ```
+    /// use std::sync::Arc;
+    ///
+    /// use leveled_hash_map::LeveledHashMap;
+    ///
+    /// let mut map: LeveledHashMap<&'static str, u64> = LeveledHashMap::new();
+    ///
+    /// map.insert(&[Arc::new("food")], 1).unwrap();
+    /// map.insert(&[Arc::new("food"), Arc::new("dessert")], 2).unwrap();
+    ///
+    /// let before = map.subtree_digest(&[Arc::new("food")], |_, v| *v).unwrap();
+    ///
+    /// *map.get_mut(&[Arc::new("food"), Arc::new("dessert")]).unwrap() = 3;
+    ///
+    /// let after = map.subtree_digest(&[Arc::new("food")], |_, v| *v).unwrap();
+    ///
+    /// assert_ne!(before, after);
+    /// ```
+    pub fn subtree_digest<F: FnMut(&K, &V) -> u64>(
+        &self,
+        key_chain: &[Arc<K>],
+        mut digest: F,
+    ) -> Result<u64, LeveledHashMapError<K>> {
+        if key_chain.is_empty() {
+            let children = match self.pool.first() {
+                Some(map) => map.keys().map(|k| self.node_digest(0, k, &mut digest)).collect(),
+                None => Vec::new(),
+            };
+
+            return Ok(combine(0, children));
+        }
+
+        self.get_inner(key_chain, 0)?;
+
+        let level = key_chain.len() - 1;
+
+        Ok(self.node_digest(level, &key_chain[level], &mut digest))
+    }
+
+    fn node_digest<F: FnMut(&K, &V) -> u64>(
+        &self,
+        level: usize,
+        key: &Arc<K>,
+        digest: &mut F,
+    ) -> u64 {
+        let own = digest(key.as_ref(), &self.pool[level][key].1);
+
+        let children =
+            self.sub[level][key].iter().map(|k| self.node_digest(level + 1, k, digest)).collect();
+
+        combine(own, children)
+    }
+}
@@ -0,0 +1,92 @@
+use std::{hash::Hash, sync::Arc};
+
+use crate::LeveledHashMap;
+
+impl<K: Eq + Hash, V> LeveledHashMap<K, V> {
+    /// Enable dirty tracking, for persisting only the entries which have been changed since the last flush. The existing entries are treated as clean. It enables generation tracking as well.
+    #[inline]
+    pub fn track_dirty(&mut self) {
+        self.track_generations();
+
+        if let Some(generations) = self.generations.as_mut() {
+            if generations.flushed.is_none() {
+                generations.flushed = Some(generations.current);
+            }
+        }
+    }
+
+    /// Check whether any entry has been changed or removed since the last flush. It returns `false` if dirty tracking is not enabled.
+    #[inline]
+    pub fn is_dirty(&self) -> bool {
+        match self.generations.as_ref() {
+            Some(generations) => match generations.flushed {
+                Some(flushed) => generations.current > flushed,
+                None => false,
+            },
+            None => false,
+        }
+    }
+
+    /// Visit the entries which have been changed (inserted, or accessed mutably) since the last flush with their key chains, from the top level down, and then mark all entries as clean. It returns the levels and the keys of the entries which have been removed since the last flush, so that they can be removed from the storage as well. Nothing is visited if dirty tracking is not enabled.
+    /// ```
+    /// use std::sync::Arc;
+    ///
+    /// use leveled_hash_map::LeveledHashMap;
+    ///
+    /// let mut map: LeveledHashMap<&'static str, u8> = LeveledHashMap::new();
+    ///
+    /// map.insert(&[Arc::new("food")], 1).unwrap();
+    /// map.insert(&[Arc::new("animal")], 2).unwrap();
+    ///
+    /// map.track_dirty();
+    ///
+    /// map.insert(&[Arc::new("food"), Arc::new("dessert")], 3).unwrap();
+    /// map.remove(&[Arc::new("animal")]).unwrap();
+    ///
+    /// assert!(map.is_dirty());
+    ///
+    /// let mut flushed = Vec::new();
+    ///
+    /// let removed =
+    ///     map.flush_dirty(|key_chain, v| flushed.push((key_chain.to_vec(), *v)));
+    ///
+    /// assert_eq!(vec![(vec![Arc::new("food"), Arc::new("dessert")], 3)], flushed);
+    /// assert_eq!(vec![(0, Arc::new("animal"))], removed);
+    /// assert!(!map.is_dirty());
+    /// ```
+    pub fn flush_dirty<F: FnMut(&[Arc<K>], &V)>(&mut self, mut f: F) -> Vec<(usize, Arc<K>)> {
+        let generations = match self.generations.as_ref() {
+            Some(generations) => generations,
+            None => return Vec::new(),
+        };
+
+        let flushed = match generations.flushed {
+            Some(flushed) => flushed,
+            None => return Vec::new(),
+        };
+
+        for (level, map) in generations.changed.iter().enumerate() {
+            for (key, g) in map {
+                if *g > flushed {
+                    f(&self.key_chain_of(level, key), &self.pool[level][key].1);
+                }
+            }
+        }
+
+        let mut removed = Vec::new();
+
+        for (level, map) in generations.removed.iter().enumerate() {
+            for (key, g) in map {
+                if *g > flushed {
+                    removed.push((level, Arc::clone(key)));
+                }
+            }
+        }
+
+        if let Some(generations) = self.generations.as_mut() {
+            generations.flushed = Some(generations.current);
+        }
+
+        removed
+    }
+}
@@ -0,0 +1,83 @@
+use std::{
+    any::{type_name, Any},
+    hash::Hash,
+    sync::Arc,
+};
+
+use crate::{LeveledHashMap, LeveledHashMapError, ProjectionError};
+
+/// A `LeveledHashMap` whose values can be of different types, for registries where different branches hold different payload types. Values are read back with `get_as` and `get_as_mut`, and `register_projection` can pin the type of a level.
+pub type DynLeveledHashMap<K> = LeveledHashMap<K, Box<dyn Any + Send + Sync>>;
+
+impl<K: Eq + Hash> LeveledHashMap<K, Box<dyn Any + Send + Sync>> {
+    /// Box a value and insert it by a key chain. It works like `insert`.
+    /// ```
+    /// use std::sync::Arc;
+    ///
+    /// use leveled_hash_map::DynLeveledHashMap;
+    ///
+    /// #[derive(Debug, PartialEq)]
+    /// struct Plugin {
+    ///     name: &'static str,
+    /// }
+    ///
+    /// let mut map: DynLeveledHashMap<&'static str> = DynLeveledHashMap::new();
+    ///
+    /// map.insert_dyn(&[Arc::new("plugins")], 2usize).unwrap();
+    /// map.insert_dyn(&[Arc::new("plugins"), Arc::new("fmt")], Plugin {
+    ///     name: "formatter",
+    /// })
+    /// .unwrap();
+    ///
+    /// assert_eq!(&2, map.get_as::<usize>(&[Arc::new("plugins")]).unwrap());
+    /// assert_eq!(
+    ///     &Plugin {
+    ///         name: "formatter"
+    ///     },
+    ///     map.get_as::<Plugin>(&[Arc::new("plugins"), Arc::new("fmt")]).unwrap()
+    /// );
+    /// assert!(map.get_as::<String>(&[Arc::new("plugins")]).is_err());
+    /// ```
+    #[inline]
+    pub fn insert_dyn<T: Any + Send + Sync>(
+        &mut self,
+        key_chain: &[Arc<K>],
+        value: T,
+    ) -> Result<Option<Box<dyn Any + Send + Sync>>, LeveledHashMapError<K>> {
+        self.insert(key_chain, Box::new(value))
+    }
+
+    /// Get a mutable value by a key chain which starts at Level 0, borrowed as a specific type. Like `get_as`, it returns a `Err(ProjectionError)` instance if the entry cannot be found, another type is registered for the level, or the value is not of the type.
+    /// ```
+    /// use std::sync::Arc;
+    ///
+    /// use leveled_hash_map::DynLeveledHashMap;
+    ///
+    /// let mut map: DynLeveledHashMap<&'static str> = DynLeveledHashMap::new();
+    ///
+    /// map.insert_dyn(&[Arc::new("counter")], 1u32).unwrap();
+    ///
+    /// *map.get_as_mut::<u32>(&[Arc::new("counter")]).unwrap() += 1;
+    ///
+    /// assert_eq!(&2, map.get_as::<u32>(&[Arc::new("counter")]).unwrap());
+    /// assert!(map.get_as_mut::<u64>(&[Arc::new("counter")]).is_err());
+    /// ```
+    pub fn get_as_mut<T: Any>(
+        &mut self,
+        key_chain: &[Arc<K>],
+    ) -> Result<&mut T, ProjectionError<K>> {
+        self.get_inner(key_chain, 0).map_err(ProjectionError::Map)?;
+
+        let level = key_chain.len() - 1;
+
+        self.check_projection::<T>(level)?;
+
+        let (_, value) = self.get_professional_mut(key_chain, 0).map_err(ProjectionError::Map)?;
+
+        value.downcast_mut().ok_or_else(|| ProjectionError::ValueMismatch {
+            level,
+            key: Arc::clone(&key_chain[level]),
+            requested: type_name::<T>(),
+        })
+    }
+}
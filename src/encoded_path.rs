@@ -0,0 +1,220 @@
+use std::{collections::HashMap, hash::Hash, sync::Arc};
+
+use crate::{LeveledHashMap, LeveledHashMapError};
+
+/// A compact binary encoding of a key chain, created by `KeyInterner::encode`. It is the varint-encoded level of the last key, followed by the varint-encoded IDs of the keys in a `KeyInterner`, so it can only be decoded by the same interner.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct EncodedPath(Box<[u8]>);
+
+impl EncodedPath {
+    /// Get the encoded bytes.
+    #[inline]
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.0
+    }
+
+    /// Wrap encoded bytes, e.g. which are read from an index. The bytes are validated when being decoded.
+    #[inline]
+    pub fn from_bytes<B: Into<Box<[u8]>>>(bytes: B) -> Self {
+        EncodedPath(bytes.into())
+    }
+
+    /// Get the level of the last key, or `None` if the bytes are malformed.
+    #[inline]
+    pub fn level(&self) -> Option<usize> {
+        read_varint(&self.0).map(|(level, _)| level as usize)
+    }
+}
+
+/// A table assigning an ID to every key, for encoding key chains into `EncodedPath`s. A key has the same ID at any level.
+#[derive(Debug, Clone)]
+pub struct KeyInterner<K: Eq + Hash> {
+    ids:  HashMap<Arc<K>, u64>,
+    keys: Vec<Arc<K>>,
+}
+
+impl<K: Eq + Hash> Default for KeyInterner<K> {
+    #[inline]
+    fn default() -> Self {
+        KeyInterner {
+            ids: HashMap::new(), keys: Vec::new()
+        }
+    }
+}
+
+fn write_varint(bytes: &mut Vec<u8>, mut n: u64) {
+    while n >= 0x80 {
+        bytes.push((n as u8) | 0x80);
+        n >>= 7;
+    }
+
+    bytes.push(n as u8);
+}
+
+fn read_varint(bytes: &[u8]) -> Option<(u64, &[u8])> {
+    let mut n = 0u64;
+
+    for (i, b) in bytes.iter().enumerate().take(10) {
+        n |= ((b & 0x7F) as u64).checked_shl(7 * i as u32)?;
+
+        if b & 0x80 == 0 {
+            return Some((n, &bytes[i + 1..]));
+        }
+    }
+
+    None
+}
+
+impl<K: Eq + Hash> KeyInterner<K> {
+    /// Create an empty interner.
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Get the number of interned keys.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.keys.len()
+    }
+
+    /// Check whether no key is interned.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+
+    /// Get the ID of a key, assigning a new one if the key has not been interned.
+    pub fn intern(&mut self, key: &Arc<K>) -> u64 {
+        if let Some(id) = self.ids.get(key) {
+            return *id;
+        }
+
+        let id = self.keys.len() as u64;
+
+        self.ids.insert(Arc::clone(key), id);
+        self.keys.push(Arc::clone(key));
+
+        id
+    }
+
+    /// Get the ID of a key which has been interned.
+    #[inline]
+    pub fn id(&self, key: &K) -> Option<u64> {
+        self.ids.get(key).copied()
+    }
+
+    /// Get the key of an ID.
+    #[inline]
+    pub fn key(&self, id: u64) -> Option<&Arc<K>> {
+        self.keys.get(id as usize)
+    }
+
+    /// Encode a key chain, interning the keys which have not been interned.
+    /// ```
+    /// use std::sync::Arc;
+    ///
+    /// use leveled_hash_map::{KeyInterner, LeveledHashMap};
+    ///
+    /// let mut map: LeveledHashMap<&'static str, u8> = LeveledHashMap::new();
+    ///
+    /// map.insert(&[Arc::new("food")], 1).unwrap();
+    /// map.insert(&[Arc::new("food"), Arc::new("dessert")], 2).unwrap();
+    ///
+    /// let mut interner = map.key_interner();
+    ///
+    /// let path =
+    ///     interner.encode(&[Arc::new("food"), Arc::new("dessert")]).unwrap();
+    ///
+    /// assert_eq!(3, path.as_bytes().len());
+    /// assert_eq!(Some(1), path.level());
+    /// assert_eq!(
+    ///     Some(vec![Arc::new("food"), Arc::new("dessert")]),
+    ///     interner.decode(&path)
+    /// );
+    /// ```
+    pub fn encode(&mut self, key_chain: &[Arc<K>]) -> Result<EncodedPath, LeveledHashMapError<K>> {
+        if key_chain.is_empty() {
+            return Err(LeveledHashMapError::KeyChainEmpty);
+        }
+
+        let ids: Vec<u64> = key_chain.iter().map(|k| self.intern(k)).collect();
+
+        Ok(encode_ids(&ids))
+    }
+
+    /// Encode a key chain whose keys have all been interned. It returns a `LeveledHashMapError::KeyNotExist` error for the first key which has not been interned.
+    pub fn try_encode(&self, key_chain: &[Arc<K>]) -> Result<EncodedPath, LeveledHashMapError<K>> {
+        if key_chain.is_empty() {
+            return Err(LeveledHashMapError::KeyChainEmpty);
+        }
+
+        let ids = key_chain
+            .iter()
+            .enumerate()
+            .map(|(level, k)| {
+                self.id(k).ok_or_else(|| LeveledHashMapError::KeyNotExist {
+                    level,
+                    key: Arc::clone(k),
+                })
+            })
+            .collect::<Result<Vec<u64>, _>>()?;
+
+        Ok(encode_ids(&ids))
+    }
+
+    /// Decode an `EncodedPath` into a key chain. It returns `None` if the bytes are malformed or contain an unknown ID.
+    pub fn decode(&self, path: &EncodedPath) -> Option<Vec<Arc<K>>> {
+        let (level, mut bytes) = read_varint(&path.0)?;
+
+        let len = (level as usize).checked_add(1)?;
+
+        // every ID takes at least one byte
+        if len > bytes.len() {
+            return None;
+        }
+
+        let mut key_chain = Vec::with_capacity(len);
+
+        for _ in 0..len {
+            let (id, rest) = read_varint(bytes)?;
+
+            key_chain.push(Arc::clone(self.key(id)?));
+
+            bytes = rest;
+        }
+
+        if bytes.is_empty() {
+            Some(key_chain)
+        } else {
+            None
+        }
+    }
+}
+
+fn encode_ids(ids: &[u64]) -> EncodedPath {
+    let mut bytes = Vec::with_capacity(ids.len() + 1);
+
+    write_varint(&mut bytes, ids.len() as u64 - 1);
+
+    for id in ids {
+        write_varint(&mut bytes, *id);
+    }
+
+    EncodedPath(bytes.into_boxed_slice())
+}
+
+impl<K: Eq + Hash, V> LeveledHashMap<K, V> {
+    /// Create a `KeyInterner` with all keys of the map, from the top level down. The interner shares the keys with the map.
+    pub fn key_interner(&self) -> KeyInterner<K> {
+        let mut interner = KeyInterner::new();
+
+        for map in self.pool.iter() {
+            for key in map.keys() {
+                interner.intern(key);
+            }
+        }
+
+        interner
+    }
+}
@@ -0,0 +1,397 @@
+//! A C-compatible interface for `LeveledHashMap<String, Vec<u8>>`. The functions are exported with unmangled names, so they can be called from C after linking this crate into a `staticlib` or a `cdylib`.
+//!
+//! Key chains are passed as arrays of `LhmStr`, which are UTF-8 strings with lengths (not NUL-terminated). Values are arbitrary byte arrays which are copied into the map.
+//!
+//! ```
+//! use leveled_hash_map::ffi::*;
+//!
+//! let food = LhmStr::from("food");
+//! let dessert = LhmStr::from("dessert");
+//!
+//! unsafe {
+//!     let map = lhm_new();
+//!
+//!     assert_eq!(LhmStatus::Ok, lhm_insert(map, &food, 1, b"1".as_ptr(), 1));
+//!     assert_eq!(
+//!         LhmStatus::Ok,
+//!         lhm_insert(map, [food, dessert].as_ptr(), 2, b"22".as_ptr(), 2)
+//!     );
+//!
+//!     let mut value = std::ptr::null();
+//!     let mut value_len = 0;
+//!
+//!     assert_eq!(
+//!         LhmStatus::Ok,
+//!         lhm_get(
+//!             map,
+//!             [food, dessert].as_ptr(),
+//!             2,
+//!             &mut value,
+//!             &mut value_len
+//!         )
+//!     );
+//!     assert_eq!(b"22", std::slice::from_raw_parts(value, value_len));
+//!
+//!     assert_eq!(LhmStatus::Ok, lhm_remove(map, &food, 1));
+//!     assert_eq!(
+//!         LhmStatus::KeyNotExist,
+//!         lhm_get(map, &food, 1, &mut value, &mut value_len)
+//!     );
+//!
+//!     lhm_free(map);
+//! }
+//! ```
+
+use std::{
+    os::raw::{c_int, c_void},
+    ptr, slice, str,
+    sync::Arc,
+};
+
+use crate::{LeveledHashMap, LeveledHashMapError};
+
+/// The map type used by the C interface.
+pub type LhmMap = LeveledHashMap<String, Vec<u8>>;
+
+/// A borrowed UTF-8 string with its length in bytes.
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+pub struct LhmStr {
+    /// The pointer to the first byte. It can be null if `len` is `0`.
+    pub ptr: *const u8,
+    /// The length in bytes.
+    pub len: usize,
+}
+
+impl From<&str> for LhmStr {
+    #[inline]
+    fn from(s: &str) -> Self {
+        LhmStr {
+            ptr: s.as_ptr(), len: s.len()
+        }
+    }
+}
+
+/// The status codes returned by the C interface. Every variant of `LeveledHashMapError` has a corresponding status code.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LhmStatus {
+    /// The operation succeeded.
+    Ok                      = 0,
+    /// A required pointer is null.
+    NullPointer             = 1,
+    /// A key is not valid UTF-8.
+    InvalidUtf8             = 2,
+    /// See `LeveledHashMapError::KeyTooMany`.
+    KeyTooMany              = 3,
+    /// See `LeveledHashMapError::KeyNotExist`.
+    KeyNotExist             = 4,
+    /// See `LeveledHashMapError::KeyChainEmpty`.
+    KeyChainEmpty           = 5,
+    /// See `LeveledHashMapError::KeyChainIncorrect`.
+    KeyChainIncorrect       = 6,
+    /// See `LeveledHashMapError::KeyDuplicated`.
+    KeyDuplicated           = 7,
+    /// See `LeveledHashMapError::KeyHasChildren`.
+    KeyHasChildren          = 8,
+    /// See `LeveledHashMapError::KeyReserved`.
+    KeyReserved             = 9,
+    /// See `LeveledHashMapError::ChildrenOfMissingParent`.
+    ChildrenOfMissingParent = 10,
+    /// See `LeveledHashMapError::ValueNotAllowed`.
+    ValueNotAllowed         = 11,
+    /// See `LeveledHashMapError::LevelFull`.
+    LevelFull               = 12,
+    /// See `LeveledHashMapError::TooManyChildren`.
+    TooManyChildren         = 13,
+    /// See `LeveledHashMapError::LevelOutOfRange`.
+    LevelOutOfRange         = 14,
+    /// See `LeveledHashMapError::TombstonesDisabled`.
+    TombstonesDisabled      = 15,
+}
+
+impl<K> From<LeveledHashMapError<K>> for LhmStatus {
+    #[inline]
+    fn from(err: LeveledHashMapError<K>) -> Self {
+        match err {
+            LeveledHashMapError::KeyTooMany => LhmStatus::KeyTooMany,
+            LeveledHashMapError::KeyNotExist {
+                ..
+            } => LhmStatus::KeyNotExist,
+            LeveledHashMapError::KeyChainEmpty => LhmStatus::KeyChainEmpty,
+            LeveledHashMapError::KeyChainIncorrect {
+                ..
+            } => LhmStatus::KeyChainIncorrect,
+            LeveledHashMapError::KeyDuplicated {
+                ..
+            } => LhmStatus::KeyDuplicated,
+            LeveledHashMapError::KeyHasChildren {
+                ..
+            } => LhmStatus::KeyHasChildren,
+            LeveledHashMapError::KeyReserved {
+                ..
+            } => LhmStatus::KeyReserved,
+            LeveledHashMapError::ChildrenOfMissingParent {
+                ..
+            } => LhmStatus::ChildrenOfMissingParent,
+            LeveledHashMapError::ValueNotAllowed {
+                ..
+            } => LhmStatus::ValueNotAllowed,
+            LeveledHashMapError::LevelFull {
+                ..
+            } => LhmStatus::LevelFull,
+            LeveledHashMapError::TooManyChildren {
+                ..
+            } => LhmStatus::TooManyChildren,
+            LeveledHashMapError::LevelOutOfRange {
+                ..
+            } => LhmStatus::LevelOutOfRange,
+            LeveledHashMapError::TombstonesDisabled => LhmStatus::TombstonesDisabled,
+        }
+    }
+}
+
+/// The callback of `lhm_for_each`. It receives the user data, the key chain of an entry and the value of the entry. Returning a non-zero value stops the iteration.
+pub type LhmVisitor = extern "C" fn(
+    user_data: *mut c_void,
+    key_chain: *const LhmStr,
+    key_chain_len: usize,
+    value: *const u8,
+    value_len: usize,
+) -> c_int;
+
+#[inline]
+unsafe fn bytes<'a>(p: *const u8, len: usize) -> Result<&'a [u8], LhmStatus> {
+    if len == 0 {
+        Ok(&[])
+    } else if p.is_null() {
+        Err(LhmStatus::NullPointer)
+    } else {
+        Ok(slice::from_raw_parts(p, len))
+    }
+}
+
+unsafe fn read_key_chain(
+    key_chain: *const LhmStr,
+    key_chain_len: usize,
+) -> Result<Vec<Arc<String>>, LhmStatus> {
+    let key_chain = if key_chain_len == 0 {
+        &[]
+    } else if key_chain.is_null() {
+        return Err(LhmStatus::NullPointer);
+    } else {
+        slice::from_raw_parts(key_chain, key_chain_len)
+    };
+
+    key_chain
+        .iter()
+        .map(|s| {
+            let s = str::from_utf8(bytes(s.ptr, s.len)?).map_err(|_| LhmStatus::InvalidUtf8)?;
+
+            Ok(Arc::new(s.to_string()))
+        })
+        .collect()
+}
+
+/// Create a new map. It needs to be freed by `lhm_free`.
+#[no_mangle]
+pub extern "C" fn lhm_new() -> *mut LhmMap {
+    Box::into_raw(Box::new(LeveledHashMap::new()))
+}
+
+/// Free a map created by `lhm_new`. Passing a null pointer does nothing.
+///
+/// # Safety
+///
+/// `map` must be null or a pointer returned by `lhm_new` which has not been freed.
+#[no_mangle]
+pub unsafe extern "C" fn lhm_free(map: *mut LhmMap) {
+    if !map.is_null() {
+        drop(Box::from_raw(map));
+    }
+}
+
+/// Insert a copy of a value by a key chain which starts at Level 0.
+///
+/// # Safety
+///
+/// `map` must be a valid map, `key_chain` must point to `key_chain_len` valid `LhmStr`s, and `value` must point to `value_len` readable bytes.
+#[no_mangle]
+pub unsafe extern "C" fn lhm_insert(
+    map: *mut LhmMap,
+    key_chain: *const LhmStr,
+    key_chain_len: usize,
+    value: *const u8,
+    value_len: usize,
+) -> LhmStatus {
+    let map = match map.as_mut() {
+        Some(map) => map,
+        None => return LhmStatus::NullPointer,
+    };
+
+    let key_chain = match read_key_chain(key_chain, key_chain_len) {
+        Ok(key_chain) => key_chain,
+        Err(status) => return status,
+    };
+
+    let value = match bytes(value, value_len) {
+        Ok(value) => value.to_vec(),
+        Err(status) => return status,
+    };
+
+    match map.insert(&key_chain, value) {
+        Ok(_) => LhmStatus::Ok,
+        Err(err) => err.into(),
+    }
+}
+
+/// Get a value by a key chain which starts at Level 0. The pointer written to `value` is borrowed from the map, and it is valid until the map is changed or freed.
+///
+/// # Safety
+///
+/// `map` must be a valid map, `key_chain` must point to `key_chain_len` valid `LhmStr`s, and `value` and `value_len` must be writable.
+#[no_mangle]
+pub unsafe extern "C" fn lhm_get(
+    map: *const LhmMap,
+    key_chain: *const LhmStr,
+    key_chain_len: usize,
+    value: *mut *const u8,
+    value_len: *mut usize,
+) -> LhmStatus {
+    let map = match map.as_ref() {
+        Some(map) => map,
+        None => return LhmStatus::NullPointer,
+    };
+
+    if value.is_null() || value_len.is_null() {
+        return LhmStatus::NullPointer;
+    }
+
+    let key_chain = match read_key_chain(key_chain, key_chain_len) {
+        Ok(key_chain) => key_chain,
+        Err(status) => return status,
+    };
+
+    match map.get_professional(&key_chain, 0) {
+        Ok((_, v)) => {
+            ptr::write(value, v.as_ptr());
+            ptr::write(value_len, v.len());
+
+            LhmStatus::Ok
+        },
+        Err(err) => err.into(),
+    }
+}
+
+/// Remove an entry along with its descendants by a key chain which starts at Level 0.
+///
+/// # Safety
+///
+/// `map` must be a valid map, and `key_chain` must point to `key_chain_len` valid `LhmStr`s.
+#[no_mangle]
+pub unsafe extern "C" fn lhm_remove(
+    map: *mut LhmMap,
+    key_chain: *const LhmStr,
+    key_chain_len: usize,
+) -> LhmStatus {
+    let map = match map.as_mut() {
+        Some(map) => map,
+        None => return LhmStatus::NullPointer,
+    };
+
+    let key_chain = match read_key_chain(key_chain, key_chain_len) {
+        Ok(key_chain) => key_chain,
+        Err(status) => return status,
+    };
+
+    match map.remove_professional(&key_chain, 0) {
+        Ok(_) => LhmStatus::Ok,
+        Err(err) => err.into(),
+    }
+}
+
+/// Visit every entry with its key chain, parents before their children. The pointers passed to the callback are only valid during the call.
+///
+/// ```
+/// use std::os::raw::{c_int, c_void};
+///
+/// use leveled_hash_map::ffi::*;
+///
+/// extern "C" fn count(
+///     user_data: *mut c_void,
+///     _key_chain: *const LhmStr,
+///     key_chain_len: usize,
+///     _value: *const u8,
+///     _value_len: usize,
+/// ) -> c_int {
+///     let depths = unsafe { &mut *(user_data as *mut Vec<usize>) };
+///
+///     depths.push(key_chain_len);
+///
+///     0
+/// }
+///
+/// let food = LhmStr::from("food");
+/// let dessert = LhmStr::from("dessert");
+///
+/// let mut depths: Vec<usize> = Vec::new();
+///
+/// unsafe {
+///     let map = lhm_new();
+///
+///     lhm_insert(map, &food, 1, std::ptr::null(), 0);
+///     lhm_insert(map, [food, dessert].as_ptr(), 2, std::ptr::null(), 0);
+///
+///     assert_eq!(
+///         LhmStatus::Ok,
+///         lhm_for_each(
+///             map,
+///             Some(count),
+///             &mut depths as *mut Vec<usize> as *mut c_void
+///         )
+///     );
+///
+///     lhm_free(map);
+/// }
+///
+/// assert_eq!(vec![1, 2], depths);
+/// ```
+///
+/// # Safety
+///
+/// `map` must be a valid map which is not changed during the iteration.
+#[no_mangle]
+pub unsafe extern "C" fn lhm_for_each(
+    map: *const LhmMap,
+    visitor: Option<LhmVisitor>,
+    user_data: *mut c_void,
+) -> LhmStatus {
+    let map = match map.as_ref() {
+        Some(map) => map,
+        None => return LhmStatus::NullPointer,
+    };
+
+    let visitor = match visitor {
+        Some(visitor) => visitor,
+        None => return LhmStatus::NullPointer,
+    };
+
+    let mut path: Vec<LhmStr> = Vec::new();
+    let mut stack: Vec<(usize, &Arc<String>)> =
+        map.pool.first().map(|m| m.keys().map(|k| (0, k)).collect()).unwrap_or_default();
+
+    while let Some((level, key)) = stack.pop() {
+        path.truncate(level);
+        path.push(LhmStr::from(key.as_str()));
+
+        let value = &map.pool[level][key].1;
+
+        if visitor(user_data, path.as_ptr(), path.len(), value.as_ptr(), value.len()) != 0 {
+            break;
+        }
+
+        stack.extend(map.sub[level][key].iter().map(|c| (level + 1, c)));
+    }
+
+    LhmStatus::Ok
+}
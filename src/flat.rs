@@ -0,0 +1,211 @@
+use std::{
+    collections::{HashMap, HashSet},
+    convert::TryFrom,
+    hash::Hash,
+    sync::Arc,
+};
+
+use crate::{LeveledHashMap, LeveledHashMapError};
+
+/// A plain snapshot of the entries of a `LeveledHashMap` without `Arc`s, for passing the hierarchy to other languages or processes. `levels[n]` holds the `(key, parent_key, value)` tuples at Level n in arbitrary order. The parent key of an entry at Level 0 is `None`, and the parent key of an entry at any other level is the key of an entry at the previous level.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct FlatSnapshot<K, V> {
+    /// The entries at each level.
+    pub levels: Vec<Vec<(K, Option<K>, V)>>,
+}
+
+impl<K: Eq + Hash, V> LeveledHashMap<K, V> {
+    /// Create a `FlatSnapshot` of the map by cloning the keys and the values.
+    /// ```
+    /// use std::sync::Arc;
+    ///
+    /// use leveled_hash_map::LeveledHashMap;
+    ///
+    /// let mut map: LeveledHashMap<&'static str, u8> = LeveledHashMap::new();
+    ///
+    /// map.insert(&[Arc::new("food")], 1).unwrap();
+    /// map.insert(&[Arc::new("food"), Arc::new("dessert")], 2).unwrap();
+    ///
+    /// let snapshot = map.to_flat_snapshot();
+    ///
+    /// assert_eq!(
+    ///     vec![vec![("food", None, 1)], vec![("dessert", Some("food"), 2)]],
+    ///     snapshot.levels
+    /// );
+    /// ```
+    pub fn to_flat_snapshot(&self) -> FlatSnapshot<K, V>
+    where
+        K: Clone,
+        V: Clone, {
+        FlatSnapshot {
+            levels: self
+                .pool
+                .iter()
+                .map(|map| {
+                    map.iter()
+                        .map(|(k, (pk, v))| {
+                            (
+                                k.as_ref().clone(),
+                                pk.as_ref().map(|pk| pk.as_ref().clone()),
+                                v.clone(),
+                            )
+                        })
+                        .collect()
+                })
+                .collect(),
+        }
+    }
+
+    /// Convert the map into a `FlatSnapshot`. The values are moved, and the keys are cloned because they are shared inside the map.
+    pub fn into_flat_snapshot(self) -> FlatSnapshot<K, V>
+    where
+        K: Clone, {
+        let LeveledHashMap {
+            pool,
+            sub,
+            ..
+        } = self;
+
+        drop(sub);
+
+        FlatSnapshot {
+            levels: pool
+                .into_iter()
+                .map(|map| {
+                    map.into_iter()
+                        .map(|(k, (pk, v))| (unwrap_key(k), pk.map(unwrap_key), v))
+                        .collect()
+                })
+                .collect(),
+        }
+    }
+
+    /// Create a `LeveledHashMap` from a `FlatSnapshot`. The levels of the entries are kept, including empty levels. It returns a `Err(LeveledHashMapError)` instance if a key is duplicated at the same level or the parent key of an entry is incorrect.
+    /// ```
+    /// use std::sync::Arc;
+    ///
+    /// use leveled_hash_map::{FlatSnapshot, LeveledHashMap, LeveledHashMapError};
+    ///
+    /// let snapshot = FlatSnapshot {
+    ///     levels: vec![vec![("food", None, 1)], vec![(
+    ///         "dessert",
+    ///         Some("food"),
+    ///         2,
+    ///     )]],
+    /// };
+    ///
+    /// let map = LeveledHashMap::from_flat_snapshot(snapshot).unwrap();
+    ///
+    /// assert_eq!(Some(&2), map.get(&[Arc::new("food"), Arc::new("dessert")]));
+    ///
+    /// let snapshot = FlatSnapshot {
+    ///     levels: vec![vec![("food", None, 1)], vec![(
+    ///         "dessert",
+    ///         Some("drink"),
+    ///         2,
+    ///     )]],
+    /// };
+    ///
+    /// match LeveledHashMap::from_flat_snapshot(snapshot) {
+    ///     Err(LeveledHashMapError::KeyNotExist {
+    ///         level,
+    ///         key,
+    ///     }) => {
+    ///         assert_eq!(0, level);
+    ///         assert_eq!(Arc::new("drink"), key);
+    ///     },
+    ///     _ => unreachable!(),
+    /// }
+    /// ```
+    pub fn from_flat_snapshot(
+        snapshot: FlatSnapshot<K, V>,
+    ) -> Result<Self, LeveledHashMapError<K>> {
+        let mut map = LeveledHashMap::new();
+
+        for (level, entries) in snapshot.levels.into_iter().enumerate() {
+            let mut pool = HashMap::with_capacity(entries.len());
+            let mut sub = HashMap::with_capacity(entries.len());
+
+            for (k, pk, v) in entries {
+                let k = Arc::new(k);
+
+                if pool.contains_key(&k) {
+                    return Err(LeveledHashMapError::KeyDuplicated {
+                        level,
+                        key: k,
+                    });
+                }
+
+                let pk = match pk {
+                    Some(pk) => {
+                        if level == 0 {
+                            return Err(LeveledHashMapError::KeyChainIncorrect {
+                                level,
+                                key: k,
+                                last_key: Some(Arc::new(pk)),
+                            });
+                        }
+
+                        let parent =
+                            map.sub[level - 1].get_key_value(&pk).map(|(pk, _)| Arc::clone(pk));
+
+                        match parent {
+                            Some(pk) => {
+                                map.sub[level - 1].get_mut(&pk).unwrap().insert(Arc::clone(&k));
+
+                                Some(pk)
+                            },
+                            None => {
+                                return Err(LeveledHashMapError::KeyNotExist {
+                                    level: level - 1,
+                                    key:   Arc::new(pk),
+                                })
+                            },
+                        }
+                    },
+                    None => {
+                        if level > 0 {
+                            return Err(LeveledHashMapError::KeyChainIncorrect {
+                                level,
+                                key: k,
+                                last_key: None,
+                            });
+                        }
+
+                        None
+                    },
+                };
+
+                sub.insert(Arc::clone(&k), HashSet::new());
+                pool.insert(k, (pk, v));
+            }
+
+            map.pool.push(pool);
+            map.sub.push(sub);
+        }
+
+        Ok(map)
+    }
+}
+
+#[inline]
+pub(crate) fn unwrap_key<K: Clone>(key: Arc<K>) -> K {
+    Arc::try_unwrap(key).unwrap_or_else(|key| key.as_ref().clone())
+}
+
+impl<K: Eq + Hash + Clone, V> From<LeveledHashMap<K, V>> for FlatSnapshot<K, V> {
+    #[inline]
+    fn from(map: LeveledHashMap<K, V>) -> Self {
+        map.into_flat_snapshot()
+    }
+}
+
+impl<K: Eq + Hash, V> TryFrom<FlatSnapshot<K, V>> for LeveledHashMap<K, V> {
+    type Error = LeveledHashMapError<K>;
+
+    #[inline]
+    fn try_from(snapshot: FlatSnapshot<K, V>) -> Result<Self, Self::Error> {
+        LeveledHashMap::from_flat_snapshot(snapshot)
+    }
+}
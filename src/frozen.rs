@@ -0,0 +1,267 @@
+use std::{
+    borrow::Borrow,
+    collections::{HashMap, HashSet},
+    hash::Hash,
+    ops::Range,
+    sync::Arc,
+};
+
+use crate::{borrowed::BorrowedKey, FrozenEntries, LeveledHashMap};
+
+/// An entry stored in a level of a `FrozenLeveledHashMap`.
+#[derive(Debug)]
+pub(crate) struct FrozenEntry<K, V> {
+    pub(crate) key:      Arc<K>,
+    /// The index of the parent entry at the previous level.
+    pub(crate) parent:   Option<usize>,
+    /// The indices of the child entries at the next level.
+    pub(crate) children: Range<usize>,
+    pub(crate) value:    V,
+}
+
+/// The entries at a level of a `FrozenLeveledHashMap`, stored contiguously and grouped by their parents.
+#[derive(Debug)]
+struct FrozenLevel<K, V> {
+    index:   HashMap<Arc<K>, usize>,
+    entries: Vec<FrozenEntry<K, V>>,
+}
+
+/// A read-only form of `LeveledHashMap` created by `LeveledHashMap::freeze`, for hierarchies which are built once and read afterwards. The child sets are dropped, and the entries of every level are packed into a contiguous array in which the children of an entry are adjacent, so looking up and walking the hierarchy touch less memory.
+///
+/// It has no interior mutability, so it is `Sync` whenever `K` and `V` are, and can be shared with an `Arc` without locking.
+#[derive(Debug)]
+pub struct FrozenLeveledHashMap<K: Eq + Hash, V> {
+    levels: Vec<FrozenLevel<K, V>>,
+}
+
+impl<K: Eq + Hash, V> LeveledHashMap<K, V> {
+    /// Convert the map into a `FrozenLeveledHashMap`. The stored keys and the values are moved, and the per-level settings, generation tracking and access tracking are dropped.
+    /// ```
+    /// use std::sync::Arc;
+    ///
+    /// use leveled_hash_map::LeveledHashMap;
+    ///
+    /// let mut map: LeveledHashMap<&'static str, u8> = LeveledHashMap::new();
+    ///
+    /// map.insert(&[Arc::new("food")], 1).unwrap();
+    /// map.insert(&[Arc::new("food"), Arc::new("dessert")], 2).unwrap();
+    /// map.insert(&[Arc::new("food"), Arc::new("meat")], 3).unwrap();
+    ///
+    /// let frozen = Arc::new(map.freeze());
+    ///
+    /// let reader = Arc::clone(&frozen);
+    ///
+    /// std::thread::spawn(move || {
+    ///     assert_eq!(
+    ///         Some(&2),
+    ///         reader.get(&[Arc::new("food"), Arc::new("dessert")])
+    ///     );
+    /// })
+    /// .join()
+    /// .unwrap();
+    ///
+    /// assert_eq!(3, frozen.len());
+    /// assert_eq!(2, frozen.children(&[Arc::new("food")]).unwrap().len());
+    /// ```
+    pub fn freeze(self) -> FrozenLeveledHashMap<K, V> {
+        let mut levels: Vec<FrozenLevel<K, V>> = Vec::with_capacity(self.pool.len());
+
+        for (level, map) in self.pool.into_iter().enumerate() {
+            let mut entries: Vec<FrozenEntry<K, V>> = map
+                .into_iter()
+                .map(|(key, (parent_key, value))| {
+                    let parent = parent_key.map(|pk| levels[level - 1].index[&pk]);
+
+                    FrozenEntry {
+                        key,
+                        parent,
+                        children: 0..0,
+                        value,
+                    }
+                })
+                .collect();
+
+            // group the children of every parent together
+            entries.sort_by_key(|entry| entry.parent);
+
+            let mut index = HashMap::with_capacity(entries.len());
+
+            for (i, entry) in entries.iter().enumerate() {
+                index.insert(Arc::clone(&entry.key), i);
+
+                if let Some(parent) = entry.parent {
+                    let children = &mut levels[level - 1].entries[parent].children;
+
+                    if children.start == children.end {
+                        *children = i..i + 1;
+                    } else {
+                        children.end = i + 1;
+                    }
+                }
+            }
+
+            levels.push(FrozenLevel {
+                index,
+                entries,
+            });
+        }
+
+        FrozenLeveledHashMap {
+            levels,
+        }
+    }
+}
+
+impl<K: Eq + Hash, V> FrozenLeveledHashMap<K, V> {
+    /// Find the index of an entry by a key chain which starts at Level 0.
+    fn find<Q: ?Sized + Hash + Eq, T: BorrowedKey<Q>>(&self, key_chain: &[T]) -> Option<usize>
+    where
+        K: Borrow<Q>, {
+        let mut parent = None;
+
+        for (level, key) in key_chain.iter().enumerate() {
+            let i = *self.levels.get(level)?.index.get(key as &dyn BorrowedKey<Q>)?;
+
+            if self.levels[level].entries[i].parent != parent {
+                return None;
+            }
+
+            parent = Some(i);
+        }
+
+        parent
+    }
+
+    /// Get a value by a key chain which starts at Level 0.
+    #[inline]
+    pub fn get(&self, key_chain: &[Arc<K>]) -> Option<&V> {
+        let i = self.find::<K, _>(key_chain)?;
+
+        Some(&self.levels[key_chain.len() - 1].entries[i].value)
+    }
+
+    /// Get a value by a key chain of borrowed keys which starts at Level 0, without constructing `Arc`s. For example, a `FrozenLeveledHashMap<String, V>` can be queried with `&[&str]`.
+    /// ```
+    /// use std::sync::Arc;
+    ///
+    /// use leveled_hash_map::LeveledHashMap;
+    ///
+    /// let mut map: LeveledHashMap<String, u8> = LeveledHashMap::new();
+    ///
+    /// map.insert(&[Arc::new("food".to_string())], 1).unwrap();
+    ///
+    /// let frozen = map.freeze();
+    ///
+    /// assert_eq!(Some(&1), frozen.get_ref::<str>(&["food"]));
+    /// assert_eq!(None, frozen.get_ref::<str>(&["drink"]));
+    /// ```
+    #[inline]
+    pub fn get_ref<Q: ?Sized + Hash + Eq>(&self, key_chain: &[&Q]) -> Option<&V>
+    where
+        K: Borrow<Q>, {
+        let i = self.find::<Q, _>(key_chain)?;
+
+        Some(&self.levels[key_chain.len() - 1].entries[i].value)
+    }
+
+    /// Check whether an entry exists by a key chain which starts at Level 0.
+    #[inline]
+    pub fn contains_key_chain(&self, key_chain: &[Arc<K>]) -> bool {
+        self.find::<K, _>(key_chain).is_some()
+    }
+
+    /// Get an iterator over the children of an entry by a key chain which starts at Level 0. It yields `(key, value)` pairs, and knows its exact length.
+    #[inline]
+    pub fn children(&self, key_chain: &[Arc<K>]) -> Option<FrozenEntries<'_, K, V>> {
+        let i = self.find::<K, _>(key_chain)?;
+
+        let level = key_chain.len();
+
+        let children = self.levels[level - 1].entries[i].children.clone();
+
+        Some(FrozenEntries {
+            inner: match self.levels.get(level) {
+                Some(next) => next.entries[children].iter(),
+                None => [].iter(),
+            },
+        })
+    }
+
+    /// Get an iterator over the entries at a specific level. It yields `(key, value)` pairs with the children of the same parent next to each other, and knows its exact length.
+    #[inline]
+    pub fn iter_level(&self, level: usize) -> Option<FrozenEntries<'_, K, V>> {
+        self.levels.get(level).map(|l| FrozenEntries {
+            inner: l.entries.iter()
+        })
+    }
+
+    /// Get the number of entries.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.levels.iter().map(|l| l.entries.len()).sum()
+    }
+
+    /// Check whether there is no entry.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.levels.iter().all(|l| l.entries.is_empty())
+    }
+
+    /// Get the deepest level which holds entries, or `None` if there is no level.
+    #[inline]
+    pub fn max_level(&self) -> Option<usize> {
+        self.levels.len().checked_sub(1)
+    }
+
+    /// Convert the frozen map back into a `LeveledHashMap`, moving the keys and the values.
+    /// ```
+    /// use std::sync::Arc;
+    ///
+    /// use leveled_hash_map::LeveledHashMap;
+    ///
+    /// let mut map: LeveledHashMap<&'static str, u8> = LeveledHashMap::new();
+    ///
+    /// map.insert(&[Arc::new("food")], 1).unwrap();
+    /// map.insert(&[Arc::new("food"), Arc::new("dessert")], 2).unwrap();
+    ///
+    /// let mut map = map.freeze().thaw();
+    ///
+    /// map.insert(&[Arc::new("food"), Arc::new("meat")], 3).unwrap();
+    ///
+    /// assert_eq!(Some(&2), map.get(&[Arc::new("food"), Arc::new("dessert")]));
+    /// ```
+    pub fn thaw(self) -> LeveledHashMap<K, V> {
+        let mut map = LeveledHashMap::new();
+
+        let mut previous_keys: Vec<Arc<K>> = Vec::new();
+
+        for level in self.levels {
+            let keys: Vec<Arc<K>> = level.entries.iter().map(|e| Arc::clone(&e.key)).collect();
+
+            let mut pool = HashMap::with_capacity(level.entries.len());
+            let mut sub = HashMap::with_capacity(level.entries.len());
+
+            for entry in level.entries {
+                let children: HashSet<Arc<K>> = HashSet::with_capacity(entry.children.len());
+
+                let parent_key = entry.parent.map(|p| Arc::clone(&previous_keys[p]));
+
+                if let (Some(parent_key), Some(previous)) =
+                    (parent_key.as_ref(), map.sub.last_mut())
+                {
+                    previous.get_mut(parent_key).unwrap().insert(Arc::clone(&entry.key));
+                }
+
+                sub.insert(Arc::clone(&entry.key), children);
+                pool.insert(entry.key, (parent_key, entry.value));
+            }
+
+            map.pool.push(pool);
+            map.sub.push(sub);
+
+            previous_keys = keys;
+        }
+
+        map
+    }
+}
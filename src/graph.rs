@@ -0,0 +1,229 @@
+use std::{
+    collections::HashMap,
+    error::Error,
+    fmt::{self, Debug, Display, Formatter},
+    hash::Hash,
+    sync::Arc,
+};
+
+use petgraph::{
+    graph::{DiGraph, NodeIndex},
+    Direction,
+};
+
+use crate::{LeveledHashMap, LeveledHashMapError};
+
+/// Possible errors come from converting a `petgraph` graph into a `LeveledHashMap`.
+pub enum GraphError<K> {
+    /// A node has more than one incoming edge, so the graph is not a forest.
+    MultipleParents { node: NodeIndex },
+    /// Some nodes cannot be reached from any node without incoming edges, because they are in a cycle.
+    Cycle { nodes: Vec<NodeIndex> },
+    /// A node cannot be inserted into the map.
+    Map { node: NodeIndex, error: LeveledHashMapError<K> },
+}
+
+impl<K> Debug for GraphError<K> {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter) -> Result<(), fmt::Error> {
+        match self {
+            GraphError::MultipleParents {
+                node,
+            } => {
+                let mut s = f.debug_struct("MultipleParents");
+                s.field("Node", node);
+                s.finish()
+            },
+            GraphError::Cycle {
+                nodes,
+            } => {
+                let mut s = f.debug_struct("Cycle");
+                s.field("Nodes", nodes);
+                s.finish()
+            },
+            GraphError::Map {
+                node,
+                error,
+            } => {
+                let mut s = f.debug_struct("Map");
+                s.field("Node", node);
+                s.field("Error", error);
+                s.finish()
+            },
+        }
+    }
+}
+
+impl<K> Display for GraphError<K> {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter) -> Result<(), fmt::Error> {
+        match self {
+            GraphError::MultipleParents {
+                node,
+            } => f.write_fmt(format_args!("Node {} has more than one parent.", node.index())),
+            GraphError::Cycle {
+                nodes,
+            } => f.write_fmt(format_args!("{} nodes are in cycles.", nodes.len())),
+            GraphError::Map {
+                node,
+                error,
+            } => f.write_fmt(format_args!("Node {}: {}", node.index(), error)),
+        }
+    }
+}
+
+impl<K> Error for GraphError<K> {}
+
+impl<K: Eq + Hash, V> LeveledHashMap<K, V> {
+    /// Convert the map into a `petgraph` directed graph without cloning the values. Every entry becomes a node carrying its key chain (which starts at Level 0) and a reference to its value, and every parent has an edge to each of its children.
+    /// ```
+    /// use std::sync::Arc;
+    ///
+    /// use leveled_hash_map::LeveledHashMap;
+    ///
+    /// let mut map: LeveledHashMap<&'static str, u8> = LeveledHashMap::new();
+    ///
+    /// map.insert(&[Arc::new("food")], 1).unwrap();
+    /// map.insert(&[Arc::new("food"), Arc::new("dessert")], 2).unwrap();
+    /// map.insert(&[Arc::new("food"), Arc::new("meat")], 3).unwrap();
+    ///
+    /// let graph = map.to_graph();
+    ///
+    /// assert_eq!(3, graph.node_count());
+    /// assert_eq!(2, graph.edge_count());
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "petgraph")))]
+    pub fn to_graph(&self) -> DiGraph<(Vec<Arc<K>>, &V), ()> {
+        let len = self.pool.iter().map(|m| m.len()).sum();
+
+        let mut graph = DiGraph::with_capacity(len, len);
+        let mut indices: Vec<HashMap<&Arc<K>, NodeIndex>> = Vec::with_capacity(self.pool.len());
+
+        for (key_chain, value) in self.iter_breadth_first() {
+            let level = key_chain.len() - 1;
+
+            let (key, (pk, _)) = self.pool[level].get_key_value(&key_chain[level]).unwrap();
+
+            let index = graph.add_node((key_chain, value));
+
+            if let Some(pk) = pk.as_ref() {
+                graph.add_edge(indices[level - 1][pk], index, ());
+            }
+
+            if indices.len() == level {
+                indices.push(HashMap::new());
+            }
+
+            indices[level].insert(key, index);
+        }
+
+        graph
+    }
+
+    /// Convert a `petgraph` directed graph which is a forest into a map. Every node carries a key and a value, and edges point from parents to children. Nodes without incoming edges are placed at Level 0, and keys need to be unique at each level. Edge weights are ignored.
+    /// ```
+    /// use std::sync::Arc;
+    ///
+    /// use leveled_hash_map::LeveledHashMap;
+    /// use petgraph::graph::DiGraph;
+    ///
+    /// let mut graph = DiGraph::new();
+    ///
+    /// let food = graph.add_node(("food", 1));
+    /// let dessert = graph.add_node(("dessert", 2));
+    ///
+    /// graph.add_edge(food, dessert, ());
+    ///
+    /// let map = LeveledHashMap::try_from_graph(graph).unwrap();
+    ///
+    /// assert_eq!(Some(&2), map.get(&[Arc::new("food"), Arc::new("dessert")]));
+    ///
+    /// let mut graph = DiGraph::new();
+    ///
+    /// let a = graph.add_node(("a", 1));
+    /// let b = graph.add_node(("b", 2));
+    ///
+    /// graph.add_edge(a, b, ());
+    /// graph.add_edge(b, a, ());
+    ///
+    /// assert!(LeveledHashMap::try_from_graph(graph).is_err());
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "petgraph")))]
+    pub fn try_from_graph<E>(graph: DiGraph<(K, V), E>) -> Result<Self, GraphError<K>> {
+        for node in graph.node_indices() {
+            if graph.neighbors_directed(node, Direction::Incoming).nth(1).is_some() {
+                return Err(GraphError::MultipleParents {
+                    node,
+                });
+            }
+        }
+
+        // the nodes in the order of a breadth-first traversal from the roots, with the parents of them
+        let mut order: Vec<(NodeIndex, Option<usize>)> = graph
+            .node_indices()
+            .filter(|n| graph.neighbors_directed(*n, Direction::Incoming).next().is_none())
+            .map(|n| (n, None))
+            .collect();
+
+        let mut i = 0;
+
+        while i < order.len() {
+            let node = order[i].0;
+
+            order.extend(graph.neighbors_directed(node, Direction::Outgoing).map(|c| (c, Some(i))));
+
+            i += 1;
+        }
+
+        if order.len() < graph.node_count() {
+            let mut visited = vec![false; graph.node_count()];
+
+            for (node, _) in order.iter() {
+                visited[node.index()] = true;
+            }
+
+            return Err(GraphError::Cycle {
+                nodes: graph.node_indices().filter(|n| !visited[n.index()]).collect(),
+            });
+        }
+
+        let (nodes, _) = graph.into_nodes_edges();
+
+        let mut weights: Vec<Option<(K, V)>> = nodes.into_iter().map(|n| Some(n.weight)).collect();
+        let mut key_chains: Vec<Vec<Arc<K>>> = Vec::with_capacity(order.len());
+
+        let mut map = LeveledHashMap::new();
+
+        for (node, parent) in order {
+            let (key, value) = weights[node.index()].take().unwrap();
+
+            let mut key_chain = match parent {
+                Some(parent) => key_chains[parent].clone(),
+                None => Vec::new(),
+            };
+
+            let level = key_chain.len();
+
+            if let Some((k, _)) = map.pool.get(level).and_then(|m| m.get_key_value(&key)) {
+                return Err(GraphError::Map {
+                    node,
+                    error: LeveledHashMapError::KeyDuplicated {
+                        level,
+                        key: Arc::clone(k),
+                    },
+                });
+            }
+
+            key_chain.push(Arc::new(key));
+
+            map.insert(&key_chain, value).map_err(|error| GraphError::Map {
+                node,
+                error,
+            })?;
+
+            key_chains.push(key_chain);
+        }
+
+        Ok(map)
+    }
+}
@@ -0,0 +1,831 @@
+use std::{
+    collections::{hash_map, hash_set, HashMap, HashSet, VecDeque},
+    hash::Hash,
+    iter::FusedIterator,
+    mem, slice,
+    sync::Arc,
+    vec,
+};
+
+use crate::frozen::FrozenEntry;
+
+/// An iterator over the entries at a specific level of a `LeveledHashMap`, created by `LeveledHashMap::iter_level`. It yields `(key, parent_key, value)` tuples in arbitrary order.
+#[derive(Debug)]
+pub struct LevelIter<'a, K, V> {
+    pub(crate) inner: hash_map::Iter<'a, Arc<K>, (Option<Arc<K>>, V)>,
+}
+
+impl<'a, K, V> Clone for LevelIter<'a, K, V> {
+    #[inline]
+    fn clone(&self) -> Self {
+        LevelIter {
+            inner: self.inner.clone()
+        }
+    }
+}
+
+impl<'a, K, V> Iterator for LevelIter<'a, K, V> {
+    type Item = (Arc<K>, Option<Arc<K>>, &'a V);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next().map(|(k, (pk, v))| (Arc::clone(k), pk.as_ref().map(Arc::clone), v))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<'a, K, V> ExactSizeIterator for LevelIter<'a, K, V> {
+    #[inline]
+    fn len(&self) -> usize {
+        self.inner.len()
+    }
+}
+
+impl<'a, K, V> FusedIterator for LevelIter<'a, K, V> {}
+
+/// A mutable iterator over the entries at a specific level of a `LeveledHashMap`, created by `LeveledHashMap::iter_level_mut`. It yields `(key, parent_key, value)` tuples in arbitrary order.
+#[derive(Debug)]
+pub struct LevelIterMut<'a, K, V> {
+    pub(crate) inner: hash_map::IterMut<'a, Arc<K>, (Option<Arc<K>>, V)>,
+}
+
+impl<'a, K, V> Iterator for LevelIterMut<'a, K, V> {
+    type Item = (Arc<K>, Option<Arc<K>>, &'a mut V);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next().map(|(k, (pk, v))| (Arc::clone(k), pk.as_ref().map(Arc::clone), v))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<'a, K, V> ExactSizeIterator for LevelIterMut<'a, K, V> {
+    #[inline]
+    fn len(&self) -> usize {
+        self.inner.len()
+    }
+}
+
+impl<'a, K, V> FusedIterator for LevelIterMut<'a, K, V> {}
+
+/// A pre-order depth-first iterator over the key chains of an entry and its descendants, created by `LeveledHashMap::keys_deep`. Values are never touched.
+#[derive(Debug)]
+pub struct KeysDeep<'a, K> {
+    pub(crate) sub:   &'a [HashMap<Arc<K>, HashSet<Arc<K>>>],
+    pub(crate) path:  Vec<Arc<K>>,
+    pub(crate) stack: Vec<(usize, &'a Arc<K>)>,
+}
+
+impl<'a, K: Eq + Hash> Iterator for KeysDeep<'a, K> {
+    type Item = Vec<Arc<K>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (level, key) = self.stack.pop()?;
+
+        self.path.truncate(level);
+        self.path.push(Arc::clone(key));
+
+        self.stack.extend(self.sub[level][key].iter().map(|c| (level + 1, c)));
+
+        Some(self.path.clone())
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.stack.len(), None)
+    }
+}
+
+impl<'a, K: Eq + Hash> FusedIterator for KeysDeep<'a, K> {}
+
+/// A pre-order depth-first iterator over all entries of a `LeveledHashMap`, created by `LeveledHashMap::iter`. It yields `(key_chain, value)` tuples, where every key chain starts at Level 0.
+#[derive(Debug)]
+pub struct Iter<'a, K, V> {
+    pub(crate) pool:      &'a [HashMap<Arc<K>, (Option<Arc<K>>, V)>],
+    pub(crate) sub:       &'a [HashMap<Arc<K>, HashSet<Arc<K>>>],
+    pub(crate) path:      Vec<Arc<K>>,
+    pub(crate) stack:     Vec<(usize, &'a Arc<K>)>,
+    pub(crate) remaining: usize,
+}
+
+impl<'a, K, V> Clone for Iter<'a, K, V> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Iter {
+            pool:      self.pool,
+            sub:       self.sub,
+            path:      self.path.clone(),
+            stack:     self.stack.clone(),
+            remaining: self.remaining,
+        }
+    }
+}
+
+impl<'a, K: Eq + Hash, V> Iterator for Iter<'a, K, V> {
+    type Item = (Vec<Arc<K>>, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (level, key) = self.stack.pop()?;
+
+        self.path.truncate(level);
+        self.path.push(Arc::clone(key));
+
+        self.stack.extend(self.sub[level][key].iter().map(|c| (level + 1, c)));
+
+        self.remaining -= 1;
+
+        Some((self.path.clone(), &self.pool[level][key].1))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<'a, K: Eq + Hash, V> ExactSizeIterator for Iter<'a, K, V> {
+    #[inline]
+    fn len(&self) -> usize {
+        self.remaining
+    }
+}
+
+impl<'a, K: Eq + Hash, V> FusedIterator for Iter<'a, K, V> {}
+
+/// A mutable iterator over all entries of a `LeveledHashMap`, created by `LeveledHashMap::iter_mut`. It yields `(key_chain, value)` tuples level by level, so parents are always yielded before their children.
+#[derive(Debug)]
+pub struct IterMut<'a, K, V> {
+    pub(crate) levels:    slice::IterMut<'a, HashMap<Arc<K>, (Option<Arc<K>>, V)>>,
+    pub(crate) current:   Option<hash_map::IterMut<'a, Arc<K>, (Option<Arc<K>>, V)>>,
+    /// The key chains of the entries at the previous level.
+    pub(crate) parents:   HashMap<Arc<K>, Vec<Arc<K>>>,
+    /// The key chains of the entries at the current level.
+    pub(crate) chains:    HashMap<Arc<K>, Vec<Arc<K>>>,
+    pub(crate) remaining: usize,
+}
+
+impl<'a, K: Eq + Hash, V> Iterator for IterMut<'a, K, V> {
+    type Item = (Vec<Arc<K>>, &'a mut V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some((k, (pk, v))) = self.current.as_mut().and_then(|c| c.next()) {
+                let mut key_chain = match pk.as_ref() {
+                    Some(pk) => self.parents.get(pk).cloned().unwrap_or_default(),
+                    None => Vec::new(),
+                };
+
+                key_chain.push(Arc::clone(k));
+
+                if self.levels.len() > 0 {
+                    self.chains.insert(Arc::clone(k), key_chain.clone());
+                }
+
+                self.remaining -= 1;
+
+                return Some((key_chain, v));
+            }
+
+            self.current = Some(self.levels.next()?.iter_mut());
+            self.parents = mem::take(&mut self.chains);
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<'a, K: Eq + Hash, V> ExactSizeIterator for IterMut<'a, K, V> {
+    #[inline]
+    fn len(&self) -> usize {
+        self.remaining
+    }
+}
+
+impl<'a, K: Eq + Hash, V> FusedIterator for IterMut<'a, K, V> {}
+
+/// An owning iterator over all entries of a `LeveledHashMap`, created by its `IntoIterator` implementation. It yields `(key_chain, value)` tuples level by level, so parents are always yielded before their children.
+#[derive(Debug)]
+pub struct IntoIter<K, V> {
+    pub(crate) levels:    vec::IntoIter<HashMap<Arc<K>, (Option<Arc<K>>, V)>>,
+    pub(crate) current:   Option<hash_map::IntoIter<Arc<K>, (Option<Arc<K>>, V)>>,
+    /// The key chains of the entries at the previous level.
+    pub(crate) parents:   HashMap<Arc<K>, Vec<Arc<K>>>,
+    /// The key chains of the entries at the current level.
+    pub(crate) chains:    HashMap<Arc<K>, Vec<Arc<K>>>,
+    pub(crate) remaining: usize,
+}
+
+impl<K: Eq + Hash, V> Iterator for IntoIter<K, V> {
+    type Item = (Vec<Arc<K>>, V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some((k, (pk, v))) = self.current.as_mut().and_then(|c| c.next()) {
+                let mut key_chain = match pk.as_ref() {
+                    Some(pk) => self.parents.get(pk).cloned().unwrap_or_default(),
+                    None => Vec::new(),
+                };
+
+                key_chain.push(Arc::clone(&k));
+
+                if self.levels.len() > 0 {
+                    self.chains.insert(k, key_chain.clone());
+                }
+
+                self.remaining -= 1;
+
+                return Some((key_chain, v));
+            }
+
+            self.current = Some(self.levels.next()?.into_iter());
+            self.parents = mem::take(&mut self.chains);
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<K: Eq + Hash, V> ExactSizeIterator for IntoIter<K, V> {
+    #[inline]
+    fn len(&self) -> usize {
+        self.remaining
+    }
+}
+
+impl<K: Eq + Hash, V> FusedIterator for IntoIter<K, V> {}
+
+/// An iterator over the values of all entries of a `LeveledHashMap`, created by `LeveledHashMap::values`. Values are yielded level by level.
+#[derive(Debug)]
+pub struct Values<'a, K, V> {
+    pub(crate) levels:    slice::Iter<'a, HashMap<Arc<K>, (Option<Arc<K>>, V)>>,
+    pub(crate) current:   Option<hash_map::Values<'a, Arc<K>, (Option<Arc<K>>, V)>>,
+    pub(crate) remaining: usize,
+}
+
+impl<'a, K, V> Iterator for Values<'a, K, V> {
+    type Item = &'a V;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some((_, v)) = self.current.as_mut().and_then(|c| c.next()) {
+                self.remaining -= 1;
+
+                return Some(v);
+            }
+
+            self.current = Some(self.levels.next()?.values());
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<'a, K, V> ExactSizeIterator for Values<'a, K, V> {
+    #[inline]
+    fn len(&self) -> usize {
+        self.remaining
+    }
+}
+
+impl<'a, K, V> FusedIterator for Values<'a, K, V> {}
+
+/// A mutable iterator over the values of all entries of a `LeveledHashMap`, created by `LeveledHashMap::values_mut`. Values are yielded level by level.
+#[derive(Debug)]
+pub struct ValuesMut<'a, K, V> {
+    pub(crate) levels:    slice::IterMut<'a, HashMap<Arc<K>, (Option<Arc<K>>, V)>>,
+    pub(crate) current:   Option<hash_map::ValuesMut<'a, Arc<K>, (Option<Arc<K>>, V)>>,
+    pub(crate) remaining: usize,
+}
+
+impl<'a, K, V> Iterator for ValuesMut<'a, K, V> {
+    type Item = &'a mut V;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some((_, v)) = self.current.as_mut().and_then(|c| c.next()) {
+                self.remaining -= 1;
+
+                return Some(v);
+            }
+
+            self.current = Some(self.levels.next()?.values_mut());
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<'a, K, V> ExactSizeIterator for ValuesMut<'a, K, V> {
+    #[inline]
+    fn len(&self) -> usize {
+        self.remaining
+    }
+}
+
+impl<'a, K, V> FusedIterator for ValuesMut<'a, K, V> {}
+
+/// An owning iterator over the values of all entries of a `LeveledHashMap`, created by `LeveledHashMap::into_values`. Values are yielded level by level.
+#[derive(Debug)]
+pub struct IntoValues<K, V> {
+    pub(crate) levels:    vec::IntoIter<HashMap<Arc<K>, (Option<Arc<K>>, V)>>,
+    pub(crate) current:   Option<hash_map::IntoIter<Arc<K>, (Option<Arc<K>>, V)>>,
+    pub(crate) remaining: usize,
+}
+
+impl<K, V> Iterator for IntoValues<K, V> {
+    type Item = V;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some((_, (_, v))) = self.current.as_mut().and_then(|c| c.next()) {
+                self.remaining -= 1;
+
+                return Some(v);
+            }
+
+            self.current = Some(self.levels.next()?.into_iter());
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<K, V> ExactSizeIterator for IntoValues<K, V> {
+    #[inline]
+    fn len(&self) -> usize {
+        self.remaining
+    }
+}
+
+impl<K, V> FusedIterator for IntoValues<K, V> {}
+
+/// A mutable pre-order depth-first iterator over an entry and its descendants, created by `LeveledHashMap::iter_subtree_mut`. It yields `(key_chain, value)` tuples.
+#[derive(Debug)]
+pub struct SubtreeIterMut<'a, K, V> {
+    pub(crate) inner: vec::IntoIter<(Vec<Arc<K>>, &'a mut V)>,
+}
+
+impl<'a, K, V> Iterator for SubtreeIterMut<'a, K, V> {
+    type Item = (Vec<Arc<K>>, &'a mut V);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<'a, K, V> ExactSizeIterator for SubtreeIterMut<'a, K, V> {
+    #[inline]
+    fn len(&self) -> usize {
+        self.inner.len()
+    }
+}
+
+impl<'a, K, V> FusedIterator for SubtreeIterMut<'a, K, V> {}
+
+/// A breadth-first iterator over entries of a `LeveledHashMap`, created by `LeveledHashMap::iter_breadth_first` or `LeveledHashMap::iter_breadth_first_from`. It yields `(key_chain, value)` tuples level by level, and every key chain starts at Level 0.
+#[derive(Debug)]
+pub struct BreadthFirstIter<'a, K, V> {
+    pub(crate) pool:      &'a [HashMap<Arc<K>, (Option<Arc<K>>, V)>],
+    pub(crate) sub:       &'a [HashMap<Arc<K>, HashSet<Arc<K>>>],
+    /// The key chains of the parents and the keys of the entries waiting to be yielded.
+    pub(crate) queue:     VecDeque<(Vec<Arc<K>>, &'a Arc<K>)>,
+    pub(crate) remaining: usize,
+}
+
+impl<'a, K, V> Clone for BreadthFirstIter<'a, K, V> {
+    #[inline]
+    fn clone(&self) -> Self {
+        BreadthFirstIter {
+            pool:      self.pool,
+            sub:       self.sub,
+            queue:     self.queue.clone(),
+            remaining: self.remaining,
+        }
+    }
+}
+
+impl<'a, K: Eq + Hash, V> Iterator for BreadthFirstIter<'a, K, V> {
+    type Item = (Vec<Arc<K>>, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (mut key_chain, key) = self.queue.pop_front()?;
+
+        let level = key_chain.len();
+
+        key_chain.push(Arc::clone(key));
+
+        self.queue.extend(self.sub[level][key].iter().map(|c| (key_chain.clone(), c)));
+
+        self.remaining -= 1;
+
+        Some((key_chain, &self.pool[level][key].1))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<'a, K: Eq + Hash, V> ExactSizeIterator for BreadthFirstIter<'a, K, V> {
+    #[inline]
+    fn len(&self) -> usize {
+        self.remaining
+    }
+}
+
+impl<'a, K: Eq + Hash, V> FusedIterator for BreadthFirstIter<'a, K, V> {}
+
+/// A pre-order depth-first iterator over the entries without children, created by `LeveledHashMap::leaves` or `LeveledHashMap::leaves_of`. It yields `(key_chain, value)` tuples, and every key chain starts at Level 0.
+#[derive(Debug, Clone)]
+pub struct Leaves<'a, K, V> {
+    pub(crate) inner: Iter<'a, K, V>,
+}
+
+impl<'a, K: Eq + Hash, V> Iterator for Leaves<'a, K, V> {
+    type Item = (Vec<Arc<K>>, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let sub = self.inner.sub;
+
+        self.inner.by_ref().find(|(key_chain, _)| {
+            let level = key_chain.len() - 1;
+
+            sub[level][&key_chain[level]].is_empty()
+        })
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(self.inner.len()))
+    }
+}
+
+impl<'a, K: Eq + Hash, V> FusedIterator for Leaves<'a, K, V> {}
+
+/// An iterator over the ancestors of an entry, created by `LeveledHashMap::ancestors`. It yields `(level, key, value)` tuples from the parent of the entry up to Level 0, following the stored parent keys.
+#[derive(Debug)]
+pub struct Ancestors<'a, K, V> {
+    pub(crate) pool: &'a [HashMap<Arc<K>, (Option<Arc<K>>, V)>],
+    pub(crate) next: Option<(usize, &'a Arc<K>)>,
+}
+
+impl<'a, K, V> Clone for Ancestors<'a, K, V> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Ancestors {
+            pool: self.pool, next: self.next
+        }
+    }
+}
+
+impl<'a, K: Eq + Hash, V> Iterator for Ancestors<'a, K, V> {
+    type Item = (usize, Arc<K>, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (level, key) = self.next.take()?;
+
+        let (pk, v) = &self.pool[level][key];
+
+        self.next = pk.as_ref().map(|pk| (level - 1, pk));
+
+        Some((level, Arc::clone(key), v))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.len();
+
+        (len, Some(len))
+    }
+}
+
+impl<'a, K: Eq + Hash, V> ExactSizeIterator for Ancestors<'a, K, V> {
+    #[inline]
+    fn len(&self) -> usize {
+        self.next.map(|(level, _)| level + 1).unwrap_or(0)
+    }
+}
+
+impl<'a, K: Eq + Hash, V> FusedIterator for Ancestors<'a, K, V> {}
+
+/// A pre-order depth-first iterator over the descendants of an entry, created by `LeveledHashMap::descendants`. It yields `(depth, key_chain, value)` tuples, where `depth` is 1 for the children of the entry, and every key chain starts at Level 0.
+#[derive(Debug, Clone)]
+pub struct Descendants<'a, K, V> {
+    pub(crate) inner: Iter<'a, K, V>,
+    /// The level of the entry whose descendants are iterated.
+    pub(crate) level: usize,
+}
+
+impl<'a, K: Eq + Hash, V> Iterator for Descendants<'a, K, V> {
+    type Item = (usize, Vec<Arc<K>>, &'a V);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next().map(|(key_chain, v)| (key_chain.len() - 1 - self.level, key_chain, v))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<'a, K: Eq + Hash, V> ExactSizeIterator for Descendants<'a, K, V> {
+    #[inline]
+    fn len(&self) -> usize {
+        self.inner.len()
+    }
+}
+
+impl<'a, K: Eq + Hash, V> FusedIterator for Descendants<'a, K, V> {}
+
+/// The keys which the siblings of an entry are picked from.
+#[derive(Debug, Clone)]
+pub(crate) enum SiblingKeys<'a, K> {
+    Roots(hash_map::Keys<'a, Arc<K>, HashSet<Arc<K>>>),
+    Children(hash_set::Iter<'a, Arc<K>>),
+}
+
+/// An iterator over the siblings of an entry, created by `LeveledHashMap::siblings`. It yields `(key, value)` pairs in arbitrary order.
+#[derive(Debug, Clone)]
+pub struct Siblings<'a, K, V> {
+    pub(crate) map:  &'a HashMap<Arc<K>, (Option<Arc<K>>, V)>,
+    pub(crate) keys: SiblingKeys<'a, K>,
+    /// The key of the entry itself, which is skipped.
+    pub(crate) key:  &'a Arc<K>,
+}
+
+impl<'a, K: Eq + Hash, V> Iterator for Siblings<'a, K, V> {
+    type Item = (Arc<K>, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let k = match &mut self.keys {
+                SiblingKeys::Roots(keys) => keys.next()?,
+                SiblingKeys::Children(keys) => keys.next()?,
+            };
+
+            if k != self.key {
+                return Some((Arc::clone(k), &self.map[k].1));
+            }
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = match &self.keys {
+            SiblingKeys::Roots(keys) => keys.len(),
+            SiblingKeys::Children(keys) => keys.len(),
+        };
+
+        (len.saturating_sub(1), Some(len))
+    }
+}
+
+impl<'a, K: Eq + Hash, V> FusedIterator for Siblings<'a, K, V> {}
+
+/// An iterator over the entries at a specific level which have the same parent, yielded by `ChildrenByParent`. It yields `(key, value)` pairs in arbitrary order.
+#[derive(Debug, Clone)]
+pub struct Children<'a, K, V> {
+    pub(crate) map:  &'a HashMap<Arc<K>, (Option<Arc<K>>, V)>,
+    pub(crate) keys: SiblingKeys<'a, K>,
+}
+
+impl<'a, K: Eq + Hash, V> Iterator for Children<'a, K, V> {
+    type Item = (&'a Arc<K>, &'a V);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let k = match &mut self.keys {
+            SiblingKeys::Roots(keys) => keys.next()?,
+            SiblingKeys::Children(keys) => keys.next()?,
+        };
+
+        Some((k, &self.map[k].1))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.len();
+
+        (len, Some(len))
+    }
+}
+
+impl<'a, K: Eq + Hash, V> ExactSizeIterator for Children<'a, K, V> {
+    #[inline]
+    fn len(&self) -> usize {
+        match &self.keys {
+            SiblingKeys::Roots(keys) => keys.len(),
+            SiblingKeys::Children(keys) => keys.len(),
+        }
+    }
+}
+
+impl<'a, K: Eq + Hash, V> FusedIterator for Children<'a, K, V> {}
+
+/// An iterator over the entries at a specific level grouped by their parents, created by `LeveledHashMap::children_by_parent`. It yields `(parent_key, children)` pairs, where `parent_key` is `None` for the only group at Level 0.
+#[derive(Debug, Clone)]
+pub struct ChildrenByParent<'a, K, V> {
+    pub(crate) map:     &'a HashMap<Arc<K>, (Option<Arc<K>>, V)>,
+    pub(crate) roots:   Option<&'a HashMap<Arc<K>, HashSet<Arc<K>>>>,
+    pub(crate) parents: Option<hash_map::Iter<'a, Arc<K>, HashSet<Arc<K>>>>,
+}
+
+impl<'a, K: Eq + Hash, V> Iterator for ChildrenByParent<'a, K, V> {
+    type Item = (Option<&'a Arc<K>>, Children<'a, K, V>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(roots) = self.roots.take() {
+            if !roots.is_empty() {
+                return Some((None, Children {
+                    map:  self.map,
+                    keys: SiblingKeys::Roots(roots.keys()),
+                }));
+            }
+        }
+
+        let parents = self.parents.as_mut()?;
+
+        parents.find(|(_, children)| !children.is_empty()).map(|(pk, children)| {
+            (Some(pk), Children {
+                map: self.map, keys: SiblingKeys::Children(children.iter())
+            })
+        })
+    }
+}
+
+impl<'a, K: Eq + Hash, V> FusedIterator for ChildrenByParent<'a, K, V> {}
+
+/// The traversal state of one top-level subtree in an `Interleaved` iterator.
+#[derive(Debug, Clone)]
+pub(crate) struct SubtreeCursor<'a, K> {
+    pub(crate) path:   Vec<Arc<K>>,
+    pub(crate) stack:  Vec<(usize, &'a Arc<K>)>,
+    /// How many entries the subtree yields in each round.
+    pub(crate) weight: usize,
+    /// How many entries the subtree has yielded in the current round.
+    pub(crate) used:   usize,
+}
+
+/// An iterator which interleaves the entries of different top-level subtrees, created by `LeveledHashMap::iter_interleaved` or `LeveledHashMap::iter_interleaved_weighted`. Each subtree is walked depth-first, and the subtrees take turns to yield entries. It yields `(key_chain, value)` tuples, and every key chain starts at Level 0.
+#[derive(Debug, Clone)]
+pub struct Interleaved<'a, K, V> {
+    pub(crate) pool:      &'a [HashMap<Arc<K>, (Option<Arc<K>>, V)>],
+    pub(crate) sub:       &'a [HashMap<Arc<K>, HashSet<Arc<K>>>],
+    pub(crate) cursors:   VecDeque<SubtreeCursor<'a, K>>,
+    pub(crate) remaining: usize,
+}
+
+impl<'a, K: Eq + Hash, V> Iterator for Interleaved<'a, K, V> {
+    type Item = (Vec<Arc<K>>, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut cursor = self.cursors.pop_front()?;
+
+        let (level, key) = cursor.stack.pop().unwrap();
+
+        cursor.path.truncate(level);
+        cursor.path.push(Arc::clone(key));
+
+        cursor.stack.extend(self.sub[level][key].iter().map(|c| (level + 1, c)));
+
+        let key_chain = cursor.path.clone();
+
+        if !cursor.stack.is_empty() {
+            cursor.used += 1;
+
+            if cursor.used < cursor.weight {
+                self.cursors.push_front(cursor);
+            } else {
+                cursor.used = 0;
+
+                self.cursors.push_back(cursor);
+            }
+        }
+
+        self.remaining -= 1;
+
+        Some((key_chain, &self.pool[level][key].1))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<'a, K: Eq + Hash, V> ExactSizeIterator for Interleaved<'a, K, V> {
+    #[inline]
+    fn len(&self) -> usize {
+        self.remaining
+    }
+}
+
+impl<'a, K: Eq + Hash, V> FusedIterator for Interleaved<'a, K, V> {}
+
+/// An iterator over the links from the entries at a specific level of a `LeveledHashMap` to their parents, created by `LeveledHashMap::parent_links`. It yields `(key, parent_key)` tuples in arbitrary order, where `parent_key` is `None` at Level 0.
+#[derive(Debug)]
+pub struct ParentLinks<'a, K, V> {
+    pub(crate) inner: hash_map::Iter<'a, Arc<K>, (Option<Arc<K>>, V)>,
+}
+
+impl<'a, K, V> Clone for ParentLinks<'a, K, V> {
+    #[inline]
+    fn clone(&self) -> Self {
+        ParentLinks {
+            inner: self.inner.clone()
+        }
+    }
+}
+
+impl<'a, K, V> Iterator for ParentLinks<'a, K, V> {
+    type Item = (&'a Arc<K>, Option<&'a Arc<K>>);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next().map(|(k, (pk, _))| (k, pk.as_ref()))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<'a, K, V> ExactSizeIterator for ParentLinks<'a, K, V> {
+    #[inline]
+    fn len(&self) -> usize {
+        self.inner.len()
+    }
+}
+
+impl<'a, K, V> FusedIterator for ParentLinks<'a, K, V> {}
+
+/// An iterator over some entries of a `FrozenLeveledHashMap`, created by `FrozenLeveledHashMap::children` and `FrozenLeveledHashMap::iter_level`. It yields `(key, value)` pairs.
+#[derive(Debug)]
+pub struct FrozenEntries<'a, K, V> {
+    pub(crate) inner: slice::Iter<'a, FrozenEntry<K, V>>,
+}
+
+impl<'a, K, V> Clone for FrozenEntries<'a, K, V> {
+    #[inline]
+    fn clone(&self) -> Self {
+        FrozenEntries {
+            inner: self.inner.clone()
+        }
+    }
+}
+
+impl<'a, K, V> Iterator for FrozenEntries<'a, K, V> {
+    type Item = (&'a Arc<K>, &'a V);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next().map(|entry| (&entry.key, &entry.value))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<'a, K, V> ExactSizeIterator for FrozenEntries<'a, K, V> {
+    #[inline]
+    fn len(&self) -> usize {
+        self.inner.len()
+    }
+}
+
+impl<'a, K, V> FusedIterator for FrozenEntries<'a, K, V> {}
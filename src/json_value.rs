@@ -0,0 +1,165 @@
+use std::{collections::HashMap, fmt::Display, hash::Hash, str::FromStr, sync::Arc};
+
+use serde::{
+    de::{DeserializeOwned, Error as _},
+    Serialize,
+};
+use serde_json::{Map, Value};
+
+use crate::{LeveledHashMap, Node};
+
+impl<K: Eq + Hash, V> LeveledHashMap<K, V> {
+    /// Convert the map into a `serde_json::Value` where nested JSON objects become levels. Every entry is an object holding its own value in the field named `value_field` and its children in the other fields. A key which is the same as `value_field` cannot be converted.
+    /// ```
+    /// use std::sync::Arc;
+    ///
+    /// use leveled_hash_map::LeveledHashMap;
+    /// use serde_json::json;
+    ///
+    /// let mut map: LeveledHashMap<&'static str, u8> = LeveledHashMap::new();
+    ///
+    /// map.insert(&[Arc::new("food")], 1).unwrap();
+    /// map.insert(&[Arc::new("food"), Arc::new("dessert")], 2).unwrap();
+    ///
+    /// assert_eq!(
+    ///     json!({"food": {"_value": 1, "dessert": {"_value": 2}}}),
+    ///     map.to_json_value("_value").unwrap()
+    /// );
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "json")))]
+    pub fn to_json_value(&self, value_field: &str) -> Result<Value, serde_json::Error>
+    where
+        K: Display,
+        V: Serialize, {
+        match self.pool.first() {
+            Some(map) => self.to_json_object(0, map.keys(), value_field),
+            None => Ok(Value::Object(Map::new())),
+        }
+    }
+
+    /// Convert some entries at a specific level, along with their descendants, into a JSON object.
+    fn to_json_object<'a, I: Iterator<Item = &'a Arc<K>>>(
+        &'a self,
+        level: usize,
+        keys: I,
+        value_field: &str,
+    ) -> Result<Value, serde_json::Error>
+    where
+        K: Display,
+        V: Serialize, {
+        let mut object = Map::new();
+
+        for key in keys {
+            let name = key.to_string();
+
+            if name == value_field {
+                return Err(serde_json::Error::custom(format_args!(
+                    "The key {:?} at level {} is the same as the value field.",
+                    name, level
+                )));
+            }
+
+            let mut node =
+                match self.to_json_object(level + 1, self.sub[level][key].iter(), value_field)? {
+                    Value::Object(node) => node,
+                    _ => unreachable!(),
+                };
+
+            node.insert(String::from(value_field), serde_json::to_value(&self.pool[level][key].1)?);
+
+            object.insert(name, Value::Object(node));
+        }
+
+        Ok(Value::Object(object))
+    }
+
+    /// Create a map from a `serde_json::Value` where nested JSON objects become levels, in the representation of `to_json_value`. Every entry needs to be an object with a field named `value_field`.
+    /// ```
+    /// use std::sync::Arc;
+    ///
+    /// use leveled_hash_map::LeveledHashMap;
+    /// use serde_json::json;
+    ///
+    /// let map: LeveledHashMap<String, u8> = LeveledHashMap::from_json_value(
+    ///     json!({"food": {"_value": 1, "dessert": {"_value": 2}}}),
+    ///     "_value",
+    /// )
+    /// .unwrap();
+    ///
+    /// assert_eq!(
+    ///     Some(&2),
+    ///     map.get(&[
+    ///         Arc::new("food".to_string()),
+    ///         Arc::new("dessert".to_string())
+    ///     ])
+    /// );
+    ///
+    /// assert!(LeveledHashMap::<String, u8>::from_json_value(
+    ///     json!({"food": {"dessert": {"_value": 2}}}),
+    ///     "_value",
+    /// )
+    /// .is_err());
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "json")))]
+    pub fn from_json_value(value: Value, value_field: &str) -> Result<Self, serde_json::Error>
+    where
+        K: FromStr,
+        K::Err: Display,
+        V: DeserializeOwned, {
+        let tree = json_object_to_tree(value, value_field, 0)?;
+
+        LeveledHashMap::from_tree(tree).map_err(serde_json::Error::custom)
+    }
+}
+
+/// Convert a JSON object of entries at a specific level into nodes.
+fn json_object_to_tree<K: Eq + Hash + FromStr, V: DeserializeOwned>(
+    value: Value,
+    value_field: &str,
+    level: usize,
+) -> Result<HashMap<K, Node<K, V>>, serde_json::Error>
+where
+    K::Err: Display, {
+    let object = match value {
+        Value::Object(object) => object,
+        _ => {
+            return Err(serde_json::Error::custom(format_args!(
+                "The entries at level {} are not in an object.",
+                level
+            )))
+        },
+    };
+
+    let mut tree = HashMap::with_capacity(object.len());
+
+    for (name, node) in object {
+        let mut node = match node {
+            Value::Object(node) => node,
+            _ => {
+                return Err(serde_json::Error::custom(format_args!(
+                    "The entry {:?} at level {} is not an object.",
+                    name, level
+                )))
+            },
+        };
+
+        let value = match node.remove(value_field) {
+            Some(value) => serde_json::from_value(value)?,
+            None => {
+                return Err(serde_json::Error::custom(format_args!(
+                    "The entry {:?} at level {} has no {:?} field.",
+                    name, level, value_field
+                )))
+            },
+        };
+
+        let key = name.parse::<K>().map_err(serde_json::Error::custom)?;
+
+        tree.insert(key, Node {
+            value,
+            children: json_object_to_tree(Value::Object(node), value_field, level + 1)?,
+        });
+    }
+
+    Ok(tree)
+}
@@ -0,0 +1,205 @@
+use std::{
+    error::Error,
+    fmt::{self, Debug, Display, Formatter},
+    hash::Hash,
+    io::{self, BufRead, Write},
+    sync::Arc,
+};
+
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+use crate::{LeveledHashMap, LeveledHashMapError};
+
+#[derive(Serialize)]
+struct RecordRef<'a, K, V> {
+    path:  Vec<&'a K>,
+    value: &'a V,
+}
+
+#[derive(Deserialize)]
+struct Record<K, V> {
+    path:  Vec<K>,
+    value: V,
+}
+
+/// Possible errors come from the JSON Lines import/export of `LeveledHashMap`.
+pub enum JsonLinesError<K> {
+    /// An I/O error.
+    Io(io::Error),
+    /// A line cannot be serialized or deserialized.
+    Json { line: usize, error: serde_json::Error },
+    /// A record cannot be inserted into the map.
+    Map { line: usize, error: LeveledHashMapError<K> },
+}
+
+impl<K> Debug for JsonLinesError<K> {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter) -> Result<(), fmt::Error> {
+        match self {
+            JsonLinesError::Io(err) => Debug::fmt(err, f),
+            JsonLinesError::Json {
+                line,
+                error,
+            } => {
+                let mut s = f.debug_struct("Json");
+                s.field("Line", line);
+                s.field("Error", error);
+                s.finish()
+            },
+            JsonLinesError::Map {
+                line,
+                error,
+            } => {
+                let mut s = f.debug_struct("Map");
+                s.field("Line", line);
+                s.field("Error", error);
+                s.finish()
+            },
+        }
+    }
+}
+
+impl<K> Display for JsonLinesError<K> {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter) -> Result<(), fmt::Error> {
+        match self {
+            JsonLinesError::Io(err) => Display::fmt(err, f),
+            JsonLinesError::Json {
+                line,
+                error,
+            } => f.write_fmt(format_args!("Line {}: {}", line, error)),
+            JsonLinesError::Map {
+                line,
+                error,
+            } => f.write_fmt(format_args!("Line {}: {}", line, error)),
+        }
+    }
+}
+
+impl<K> Error for JsonLinesError<K> {}
+
+impl<K> From<io::Error> for JsonLinesError<K> {
+    #[inline]
+    fn from(err: io::Error) -> Self {
+        JsonLinesError::Io(err)
+    }
+}
+
+impl<K: Eq + Hash, V> LeveledHashMap<K, V> {
+    /// Export the map to a writer in the JSON Lines format, one `{"path":[...],"value":...}` record per line. Parents are always written before their children, and the map is streamed without being copied.
+    /// ```
+    /// use std::sync::Arc;
+    ///
+    /// use leveled_hash_map::LeveledHashMap;
+    ///
+    /// let mut map: LeveledHashMap<String, u8> = LeveledHashMap::new();
+    ///
+    /// map.insert(&[Arc::new("food".to_string())], 1).unwrap();
+    /// map.insert(
+    ///     &[Arc::new("food".to_string()), Arc::new("dessert".to_string())],
+    ///     2,
+    /// )
+    /// .unwrap();
+    ///
+    /// let mut jsonl = Vec::new();
+    ///
+    /// map.to_jsonl(&mut jsonl).unwrap();
+    ///
+    /// let jsonl = String::from_utf8(jsonl).unwrap();
+    /// let mut lines = jsonl.lines();
+    ///
+    /// assert_eq!(Some(r#"{"path":["food"],"value":1}"#), lines.next());
+    /// assert_eq!(Some(r#"{"path":["food","dessert"],"value":2}"#), lines.next());
+    /// assert_eq!(None, lines.next());
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "json")))]
+    pub fn to_jsonl<W: Write>(&self, mut writer: W) -> Result<(), JsonLinesError<K>>
+    where
+        K: Serialize,
+        V: Serialize, {
+        let mut path: Vec<&K> = Vec::new();
+        let mut stack: Vec<(usize, &Arc<K>)> =
+            self.pool.first().map(|m| m.keys().map(|k| (0, k)).collect()).unwrap_or_default();
+
+        let mut line = 0;
+
+        while let Some((level, key)) = stack.pop() {
+            line += 1;
+
+            path.truncate(level);
+            path.push(key.as_ref());
+
+            let record = RecordRef {
+                path: path.clone(), value: &self.pool[level][key].1
+            };
+
+            serde_json::to_writer(&mut writer, &record).map_err(|error| JsonLinesError::Json {
+                line,
+                error,
+            })?;
+
+            writer.write_all(b"\n")?;
+
+            stack.extend(self.sub[level][key].iter().map(|c| (level + 1, c)));
+        }
+
+        writer.flush()?;
+
+        Ok(())
+    }
+
+    /// Import a map from a reader in the JSON Lines format produced by `to_jsonl`. Records are inserted one by one, so parents need to come before their children. Empty lines are skipped.
+    /// ```
+    /// use std::sync::Arc;
+    ///
+    /// use leveled_hash_map::LeveledHashMap;
+    ///
+    /// let jsonl = [
+    ///     r#"{"path":["food"],"value":1}"#,
+    ///     r#"{"path":["food","dessert"],"value":2}"#,
+    /// ];
+    /// let jsonl = jsonl.join("\n");
+    ///
+    /// let map: LeveledHashMap<String, u8> =
+    ///     LeveledHashMap::from_jsonl(jsonl.as_bytes()).unwrap();
+    ///
+    /// assert_eq!(
+    ///     Some(&2),
+    ///     map.get(&[
+    ///         Arc::new("food".to_string()),
+    ///         Arc::new("dessert".to_string())
+    ///     ])
+    /// );
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "json")))]
+    pub fn from_jsonl<R: BufRead>(reader: R) -> Result<Self, JsonLinesError<K>>
+    where
+        K: DeserializeOwned,
+        V: DeserializeOwned, {
+        let mut map = LeveledHashMap::new();
+
+        for (i, s) in reader.lines().enumerate() {
+            let s = s?;
+            let line = i + 1;
+
+            if s.trim().is_empty() {
+                continue;
+            }
+
+            let record: Record<K, V> =
+                serde_json::from_str(&s).map_err(|error| JsonLinesError::Json {
+                    line,
+                    error,
+                })?;
+
+            let key_chain: Vec<Arc<K>> = record.path.into_iter().map(Arc::new).collect();
+
+            map.insert(&key_chain, record.value).map_err(|error| JsonLinesError::Map {
+                line,
+                error,
+            })?;
+        }
+
+        Ok(map)
+    }
+}
@@ -0,0 +1,468 @@
+use std::{hash::Hash, ops::Deref, str::FromStr, sync::Arc};
+
+use crate::LeveledHashMap;
+
+/// Types which can be used as a key chain argument of `LeveledHashMap::get_by` and its siblings, such as `&[Arc<K>]`, `&[Arc<K>; N]`, `&Vec<Arc<K>>` and tuples of keys for fixed small depths. The keys in a tuple can be `K`s or `Arc<K>`s.
+/// ```
+/// use std::sync::Arc;
+///
+/// use leveled_hash_map::LeveledHashMap;
+///
+/// let mut map: LeveledHashMap<&'static str, u8> = LeveledHashMap::new();
+///
+/// map.insert(&[Arc::new("food")], 1).unwrap();
+/// map.insert(&[Arc::new("food"), Arc::new("dessert")], 2).unwrap();
+///
+/// assert_eq!(Some(&1), map.get_by(("food",)));
+/// assert_eq!(Some(&2), map.get_by(("food", "dessert")));
+/// assert_eq!(Some(&2), map.get_by((Arc::new("food"), "dessert")));
+/// assert_eq!(Some(&2), map.get_advanced_by(("dessert",), 1));
+/// ```
+pub trait IntoKeyChain<K> {
+    /// The type which can be viewed as a key chain.
+    type KeyChain: AsRef<[Arc<K>]>;
+
+    /// Convert this argument into a key chain.
+    fn into_key_chain(self) -> Self::KeyChain;
+}
+
+impl<'a, K> IntoKeyChain<K> for &'a [Arc<K>] {
+    type KeyChain = &'a [Arc<K>];
+
+    #[inline]
+    fn into_key_chain(self) -> Self::KeyChain {
+        self
+    }
+}
+
+impl<'a, K, const N: usize> IntoKeyChain<K> for &'a [Arc<K>; N] {
+    type KeyChain = &'a [Arc<K>];
+
+    #[inline]
+    fn into_key_chain(self) -> Self::KeyChain {
+        self
+    }
+}
+
+impl<'a, K> IntoKeyChain<K> for &'a Vec<Arc<K>> {
+    type KeyChain = &'a [Arc<K>];
+
+    #[inline]
+    fn into_key_chain(self) -> Self::KeyChain {
+        self
+    }
+}
+
+impl<K, A: Into<Arc<K>>> IntoKeyChain<K> for (A,) {
+    type KeyChain = [Arc<K>; 1];
+
+    #[inline]
+    fn into_key_chain(self) -> Self::KeyChain {
+        [self.0.into()]
+    }
+}
+
+impl<K, A: Into<Arc<K>>, B: Into<Arc<K>>> IntoKeyChain<K> for (A, B) {
+    type KeyChain = [Arc<K>; 2];
+
+    #[inline]
+    fn into_key_chain(self) -> Self::KeyChain {
+        [self.0.into(), self.1.into()]
+    }
+}
+
+impl<K, A: Into<Arc<K>>, B: Into<Arc<K>>, C: Into<Arc<K>>> IntoKeyChain<K> for (A, B, C) {
+    type KeyChain = [Arc<K>; 3];
+
+    #[inline]
+    fn into_key_chain(self) -> Self::KeyChain {
+        [self.0.into(), self.1.into(), self.2.into()]
+    }
+}
+
+impl<K: Eq + Hash, V> LeveledHashMap<K, V> {
+    /// Get a value by a key chain of any `IntoKeyChain` type, such as a tuple of keys. The key chain starts at Level 0.
+    #[inline]
+    pub fn get_by<C: IntoKeyChain<K>>(&self, key_chain: C) -> Option<&V> {
+        self.get(key_chain.into_key_chain().as_ref())
+    }
+
+    /// Get a value by a key chain of any `IntoKeyChain` type, such as a tuple of keys. The key chain starts at Level 0.
+    #[inline]
+    pub fn get_by_mut<C: IntoKeyChain<K>>(&mut self, key_chain: C) -> Option<&mut V> {
+        self.get_mut(key_chain.into_key_chain().as_ref())
+    }
+
+    /// Get a value by a key chain of any `IntoKeyChain` type, such as a tuple of keys, and a level which the key chain starts with.
+    #[inline]
+    pub fn get_advanced_by<C: IntoKeyChain<K>>(
+        &self,
+        key_chain: C,
+        start_level: usize,
+    ) -> Option<&V> {
+        self.get_advanced(key_chain.into_key_chain().as_ref(), start_level)
+    }
+
+    /// Get a value by a key chain of any `IntoKeyChain` type, such as a tuple of keys, and a level which the key chain starts with.
+    #[inline]
+    pub fn get_advanced_by_mut<C: IntoKeyChain<K>>(
+        &mut self,
+        key_chain: C,
+        start_level: usize,
+    ) -> Option<&mut V> {
+        self.get_advanced_mut(key_chain.into_key_chain().as_ref(), start_level)
+    }
+}
+
+/// An owned key chain. It can be built from `&[Arc<K>]`, `Vec<Arc<K>>`, `Vec<K>` or a delimited string, and dereferences to `[Arc<K>]`, so it can be passed to every method which takes a key chain.
+/// ```
+/// use std::sync::Arc;
+///
+/// use leveled_hash_map::{KeyChain, LeveledHashMap};
+///
+/// let mut map: LeveledHashMap<String, u8> = LeveledHashMap::new();
+///
+/// let mut key_chain: KeyChain<String> = "food".parse().unwrap();
+///
+/// map.insert(&key_chain, 1).unwrap();
+///
+/// key_chain.push("dessert".to_string());
+///
+/// map.insert(&key_chain, 2).unwrap();
+///
+/// assert_eq!(Some(&2), map.get(&key_chain));
+/// assert_eq!(Some(&1), map.get(key_chain.parent().unwrap()));
+/// assert_eq!(Some(Arc::new("dessert".to_string())), key_chain.pop());
+/// ```
+#[derive(Debug, PartialEq, Eq, Hash)]
+pub struct KeyChain<K> {
+    keys: Vec<Arc<K>>,
+}
+
+impl<K> KeyChain<K> {
+    /// Create an empty key chain.
+    #[inline]
+    pub fn new() -> KeyChain<K> {
+        KeyChain {
+            keys: Vec::new()
+        }
+    }
+
+    /// Parse a string whose keys are delimited by a separator. An empty string results in an empty key chain.
+    /// ```
+    /// use std::sync::Arc;
+    ///
+    /// use leveled_hash_map::KeyChain;
+    ///
+    /// let key_chain: KeyChain<u32> = KeyChain::parse("1.2.3", '.').unwrap();
+    ///
+    /// assert_eq!(&[Arc::new(1), Arc::new(2), Arc::new(3)], key_chain.as_slice());
+    ///
+    /// assert!(KeyChain::<u32>::parse("1.x", '.').is_err());
+    /// ```
+    pub fn parse(s: &str, separator: char) -> Result<KeyChain<K>, K::Err>
+    where
+        K: FromStr, {
+        if s.is_empty() {
+            return Ok(KeyChain::new());
+        }
+
+        s.split(separator).map(|key| key.parse().map(Arc::new)).collect::<Result<_, _>>().map(
+            |keys| KeyChain {
+                keys,
+            },
+        )
+    }
+
+    /// Append a key to the end of the key chain.
+    #[inline]
+    pub fn push<T: Into<Arc<K>>>(&mut self, key: T) {
+        self.keys.push(key.into());
+    }
+
+    /// Remove the last key of the key chain and return it.
+    #[inline]
+    pub fn pop(&mut self) -> Option<Arc<K>> {
+        self.keys.pop()
+    }
+
+    /// Get the key chain of the parent, or `None` if the key chain is empty.
+    #[inline]
+    pub fn parent(&self) -> Option<&[Arc<K>]> {
+        self.keys.split_last().map(|(_, parent)| parent)
+    }
+
+    /// Get the keys as a slice.
+    #[inline]
+    pub fn as_slice(&self) -> &[Arc<K>] {
+        &self.keys
+    }
+
+    /// Convert the key chain into a `Vec` of keys.
+    #[inline]
+    pub fn into_vec(self) -> Vec<Arc<K>> {
+        self.keys
+    }
+}
+
+impl<K> Clone for KeyChain<K> {
+    #[inline]
+    fn clone(&self) -> Self {
+        KeyChain {
+            keys: self.keys.clone()
+        }
+    }
+}
+
+impl<K> Default for KeyChain<K> {
+    #[inline]
+    fn default() -> Self {
+        KeyChain::new()
+    }
+}
+
+impl<K> Deref for KeyChain<K> {
+    type Target = [Arc<K>];
+
+    #[inline]
+    fn deref(&self) -> &[Arc<K>] {
+        &self.keys
+    }
+}
+
+impl<K> AsRef<[Arc<K>]> for KeyChain<K> {
+    #[inline]
+    fn as_ref(&self) -> &[Arc<K>] {
+        &self.keys
+    }
+}
+
+impl<K> From<&[Arc<K>]> for KeyChain<K> {
+    #[inline]
+    fn from(keys: &[Arc<K>]) -> Self {
+        KeyChain {
+            keys: keys.to_vec()
+        }
+    }
+}
+
+impl<K> From<Vec<Arc<K>>> for KeyChain<K> {
+    #[inline]
+    fn from(keys: Vec<Arc<K>>) -> Self {
+        KeyChain {
+            keys,
+        }
+    }
+}
+
+impl<K> From<Vec<K>> for KeyChain<K> {
+    #[inline]
+    fn from(keys: Vec<K>) -> Self {
+        KeyChain {
+            keys: keys.into_iter().map(Arc::new).collect()
+        }
+    }
+}
+
+impl<K> From<KeyChain<K>> for Vec<Arc<K>> {
+    #[inline]
+    fn from(key_chain: KeyChain<K>) -> Self {
+        key_chain.keys
+    }
+}
+
+impl<K: FromStr> FromStr for KeyChain<K> {
+    type Err = K::Err;
+
+    /// Parse a string whose keys are delimited by `'/'`.
+    #[inline]
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        KeyChain::parse(s, '/')
+    }
+}
+
+impl<K> IntoKeyChain<K> for KeyChain<K> {
+    type KeyChain = KeyChain<K>;
+
+    #[inline]
+    fn into_key_chain(self) -> Self::KeyChain {
+        self
+    }
+}
+
+impl<'a, K> IntoKeyChain<K> for &'a KeyChain<K> {
+    type KeyChain = &'a [Arc<K>];
+
+    #[inline]
+    fn into_key_chain(self) -> Self::KeyChain {
+        &self.keys
+    }
+}
+
+/// A key chain builder which always holds at least one key, so a key chain built with it can never cause `LeveledHashMapError::KeyChainEmpty`. It dereferences to `[Arc<K>]`, so it can be passed to every method which takes a key chain.
+/// ```
+/// use std::sync::Arc;
+///
+/// use leveled_hash_map::{ChainBuf, LeveledHashMap};
+///
+/// let mut map: LeveledHashMap<&'static str, u8> = LeveledHashMap::new();
+///
+/// let food = ChainBuf::root("food");
+/// let dessert = food.clone().child("dessert");
+///
+/// map.insert(&food, 1).unwrap();
+/// map.insert(&dessert, 2).unwrap();
+///
+/// assert_eq!(Some(&2), map.get(&dessert));
+/// assert_eq!(&Arc::new("dessert"), dessert.last());
+/// assert_eq!(1, dessert.level());
+/// ```
+#[derive(Debug, PartialEq, Eq, Hash)]
+pub struct ChainBuf<K> {
+    keys: Vec<Arc<K>>,
+}
+
+impl<K> ChainBuf<K> {
+    /// Create a key chain with a key at Level 0.
+    #[inline]
+    pub fn root<T: Into<Arc<K>>>(key: T) -> ChainBuf<K> {
+        ChainBuf {
+            keys: vec![key.into()]
+        }
+    }
+
+    /// Append a child key to the key chain.
+    #[inline]
+    pub fn child<T: Into<Arc<K>>>(mut self, key: T) -> ChainBuf<K> {
+        self.keys.push(key.into());
+
+        self
+    }
+
+    /// Append a child key to the key chain in place.
+    #[inline]
+    pub fn push_child<T: Into<Arc<K>>>(&mut self, key: T) {
+        self.keys.push(key.into());
+    }
+
+    /// Remove the last key of the key chain and return it, unless it is the only key.
+    /// ```
+    /// use std::sync::Arc;
+    ///
+    /// use leveled_hash_map::ChainBuf;
+    ///
+    /// let mut key_chain = ChainBuf::root("food").child("dessert");
+    ///
+    /// assert_eq!(Some(Arc::new("dessert")), key_chain.pop());
+    /// assert_eq!(None, key_chain.pop());
+    /// assert_eq!(1, key_chain.len());
+    /// ```
+    #[inline]
+    pub fn pop(&mut self) -> Option<Arc<K>> {
+        if self.keys.len() > 1 {
+            self.keys.pop()
+        } else {
+            None
+        }
+    }
+
+    /// Get the last key.
+    #[inline]
+    pub fn last(&self) -> &Arc<K> {
+        &self.keys[self.keys.len() - 1]
+    }
+
+    /// Get the level of the last key.
+    #[inline]
+    pub fn level(&self) -> usize {
+        self.keys.len() - 1
+    }
+
+    /// Get the key chain of the parent, or `None` if the last key is at Level 0.
+    #[inline]
+    pub fn parent(&self) -> Option<&[Arc<K>]> {
+        if self.keys.len() > 1 {
+            Some(&self.keys[..self.keys.len() - 1])
+        } else {
+            None
+        }
+    }
+
+    /// Get the keys as a slice.
+    #[inline]
+    pub fn as_slice(&self) -> &[Arc<K>] {
+        &self.keys
+    }
+
+    /// Convert the key chain into a `Vec` of keys.
+    #[inline]
+    pub fn into_vec(self) -> Vec<Arc<K>> {
+        self.keys
+    }
+}
+
+impl<K> Clone for ChainBuf<K> {
+    #[inline]
+    fn clone(&self) -> Self {
+        ChainBuf {
+            keys: self.keys.clone()
+        }
+    }
+}
+
+impl<K> Deref for ChainBuf<K> {
+    type Target = [Arc<K>];
+
+    #[inline]
+    fn deref(&self) -> &[Arc<K>] {
+        &self.keys
+    }
+}
+
+impl<K> AsRef<[Arc<K>]> for ChainBuf<K> {
+    #[inline]
+    fn as_ref(&self) -> &[Arc<K>] {
+        &self.keys
+    }
+}
+
+impl<K> TryFrom<Vec<Arc<K>>> for ChainBuf<K> {
+    type Error = Vec<Arc<K>>;
+
+    /// Convert a `Vec` of keys into a key chain, giving the `Vec` back if it is empty.
+    #[inline]
+    fn try_from(keys: Vec<Arc<K>>) -> Result<Self, Self::Error> {
+        if keys.is_empty() {
+            Err(keys)
+        } else {
+            Ok(ChainBuf {
+                keys,
+            })
+        }
+    }
+}
+
+impl<K> From<ChainBuf<K>> for KeyChain<K> {
+    #[inline]
+    fn from(key_chain: ChainBuf<K>) -> Self {
+        KeyChain::from(key_chain.keys)
+    }
+}
+
+impl<K> IntoKeyChain<K> for ChainBuf<K> {
+    type KeyChain = ChainBuf<K>;
+
+    #[inline]
+    fn into_key_chain(self) -> Self::KeyChain {
+        self
+    }
+}
+
+impl<'a, K> IntoKeyChain<K> for &'a ChainBuf<K> {
+    type KeyChain = &'a [Arc<K>];
+
+    #[inline]
+    fn into_key_chain(self) -> Self::KeyChain {
+        &self.keys
+    }
+}
@@ -1,21 +1,207 @@
+#![cfg_attr(docsrs, feature(doc_cfg))]
 #![allow(clippy::type_complexity)]
 
+mod adjacency;
+mod aggregate;
+mod align;
+#[cfg(feature = "rkyv")]
+mod archive;
+mod borrowed;
+mod budgeted;
+mod cas;
+#[cfg(feature = "unicase")]
+mod caseless;
+mod chunks;
+#[cfg(feature = "codegen")]
+#[cfg_attr(docsrs, doc(cfg(feature = "codegen")))]
+pub mod codegen;
+#[cfg(feature = "compress")]
+mod compress;
+mod context;
+mod dedup;
+mod digest;
+mod dirty;
+mod dynamic;
+mod encoded_path;
+#[cfg(feature = "ffi")]
+#[cfg_attr(docsrs, doc(cfg(feature = "ffi")))]
+pub mod ffi;
+mod flat;
+mod frozen;
+#[cfg(feature = "petgraph")]
+mod graph;
+mod iter;
+#[cfg(feature = "json")]
+mod json_value;
+#[cfg(feature = "json")]
+mod jsonl;
+mod key_chain;
+mod limits;
+mod meta;
+mod overwrite_policy;
+#[cfg(feature = "rayon")]
+mod parallel;
+mod parse;
+mod projection;
+#[cfg(feature = "python")]
+#[cfg_attr(docsrs, doc(cfg(feature = "python")))]
+pub mod python;
+mod read_through;
+mod relevel;
+mod repair;
+mod reserved;
+mod results;
+#[cfg(feature = "rand")]
+mod sample;
+#[cfg(feature = "serde")]
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+pub mod serde_flat;
+mod set;
+#[cfg(feature = "log")]
+mod slow_op;
+#[cfg(feature = "stats")]
+mod stats;
+mod string;
+mod suggest;
+#[cfg(feature = "test_util")]
+#[cfg_attr(docsrs, doc(cfg(feature = "test_util")))]
+pub mod test_util;
+mod tombstone;
+#[cfg(feature = "toml")]
+mod toml_table;
+mod traverser;
+mod tree;
+mod tree_like;
+mod try_insert;
+#[cfg(feature = "yaml")]
+mod yaml;
+
 use std::{
-    collections::{HashMap, HashSet},
+    any::TypeId,
+    collections::{HashMap, HashSet, VecDeque},
     error::Error,
     fmt::{self, Debug, Display, Formatter},
     hash::Hash,
+    mem,
+    ops::{Index, IndexMut},
     sync::Arc,
 };
 
+pub use adjacency::*;
+pub use aggregate::*;
+pub use align::*;
+#[cfg(feature = "rkyv")]
+pub use archive::*;
+pub use budgeted::*;
+#[cfg(feature = "unicase")]
+pub use caseless::*;
+pub use chunks::*;
+#[cfg(feature = "compress")]
+pub use compress::*;
+pub use context::*;
+pub use dedup::*;
+pub use dynamic::*;
+pub use encoded_path::*;
+pub use flat::*;
+pub use frozen::*;
+#[cfg(feature = "petgraph")]
+pub use graph::*;
+pub use iter::*;
+#[cfg(feature = "json")]
+pub use jsonl::*;
+pub use key_chain::*;
+pub use limits::*;
+pub use meta::*;
+pub use overwrite_policy::*;
+#[cfg(feature = "rayon")]
+pub use parallel::*;
+pub use parse::*;
+pub use projection::*;
+pub use read_through::*;
+pub use repair::*;
+pub use results::*;
+pub use set::*;
+#[cfg(feature = "stats")]
+pub use stats::*;
+pub use string::*;
+pub use suggest::*;
+pub use tombstone::*;
+pub use traverser::*;
+pub use tree::*;
+pub use tree_like::*;
+pub use try_insert::*;
+
 /// A structure to separate values into different levels with keys. Every key-value entry which is not at the top level has a parent key at the superior level. Keys at the same level are unique, no matter what parent keys they have.
 #[derive(Debug)]
 pub struct LeveledHashMap<K: Eq + Hash, V> {
-    pool: Vec<HashMap<Arc<K>, (Option<Arc<K>>, V)>>,
-    sub:  Vec<HashMap<Arc<K>, HashSet<Arc<K>>>>,
+    pool:             Vec<HashMap<Arc<K>, (Option<Arc<K>>, V)>>,
+    sub:              Vec<HashMap<Arc<K>, HashSet<Arc<K>>>>,
+    generations:      Option<Generations<K>>,
+    overwrite_policy: OverwritePolicy,
+    reserved:         Vec<HashSet<Arc<K>>>,
+    projections:      Vec<Option<(TypeId, &'static str)>>,
+    allowed_types:    Vec<Vec<projection::AllowedType<V>>>,
+    tombstones:       Option<tombstone::Tombstones<K, V>>,
+    limits:           Vec<LevelLimits>,
+    #[cfg(feature = "log")]
+    slow_op:          Option<slow_op::SlowOpThreshold<K>>,
+    #[cfg(feature = "stats")]
+    stats:            Option<std::sync::Mutex<stats::AccessStats<K>>>,
+}
+
+/// Per-entry generation stamps which are recorded when generation tracking is enabled.
+#[derive(Debug)]
+struct Generations<K: Eq + Hash> {
+    current: u64,
+    changed: Vec<HashMap<Arc<K>, u64>>,
+    removed: Vec<HashMap<Arc<K>, u64>>,
+    /// The generation of the last flush, if dirty tracking is enabled.
+    flushed: Option<u64>,
+}
+
+impl<K: Eq + Hash> Generations<K> {
+    #[inline]
+    fn ensure_level(&mut self, level: usize) {
+        while self.changed.len() <= level {
+            self.changed.push(HashMap::new());
+            self.removed.push(HashMap::new());
+        }
+    }
+
+    fn mark_changed(&mut self, level: usize, key: &Arc<K>) {
+        self.ensure_level(level);
+
+        self.current += 1;
+
+        self.removed[level].remove(key);
+        self.changed[level].insert(Arc::clone(key), self.current);
+    }
+
+    fn mark_removed(&mut self, level: usize, key: &Arc<K>) {
+        self.ensure_level(level);
+
+        self.current += 1;
+
+        self.changed[level].remove(key);
+        self.removed[level].insert(Arc::clone(key), self.current);
+    }
+}
+
+/// Entries changed after a specific generation, produced by `LeveledHashMap::export_since`.
+#[derive(Debug)]
+pub struct IncrementalExport<'a, K, V> {
+    /// The generation of the map at the time of exporting. Use it as the argument of the next `export_since` call.
+    pub generation: u64,
+    /// Entries which were removed (along with their descendants), as `(level, key)` pairs ordered by level.
+    pub removed:    Vec<(usize, Arc<K>)>,
+    /// Entries which were inserted or mutably accessed, as full key chains ordered by level (parents first).
+    pub changed:    Vec<(Vec<Arc<K>>, &'a V)>,
 }
 
 /// Possible errors come from `LeveledHashMap`.
+///
+/// With the `serde` feature enabled, errors are serializable, so they can be returned over APIs as they are.
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum LeveledHashMapError<K> {
     /// The length of a key chain is over the max level of a `LeveledHashMap`.
     /// ```
@@ -154,6 +340,266 @@ pub enum LeveledHashMapError<K> {
     /// }
     /// ```
     KeyChainIncorrect { level: usize, key: Arc<K>, last_key: Option<Arc<K>> },
+    /// The key would be duplicated at a level, which breaks the uniqueness of keys at the same level.
+    /// ```
+    /// use std::sync::Arc;
+    ///
+    /// use leveled_hash_map::{LeveledHashMap, LeveledHashMapError};
+    ///
+    /// let mut map = LeveledHashMap::new();
+    ///
+    /// map.insert(&[Arc::new("food")], 100).unwrap();
+    ///
+    /// map.insert(&[Arc::new("food"), Arc::new("dessert")], 100).unwrap();
+    ///
+    /// map.insert(&[Arc::new("dessert")], 200).unwrap();
+    ///
+    /// // try to move "dessert" at Level 0 under "food", here "food/dessert" exists
+    ///
+    /// match map.relevel_key(&Arc::new("dessert"), 0, &[Arc::new("food")]) {
+    ///     Ok(_) => unreachable!(),
+    ///     Err(err) => match err {
+    ///         LeveledHashMapError::KeyDuplicated {
+    ///             level,
+    ///             key,
+    ///         } => {
+    ///             assert_eq!(1, level);
+    ///             assert_eq!(Arc::new("dessert"), key);
+    ///         },
+    ///         _ => unreachable!(),
+    ///     },
+    /// }
+    /// ```
+    KeyDuplicated { level: usize, key: Arc<K> },
+    /// The entry has children, so its value is not allowed to be overwritten under `OverwritePolicy::Error`.
+    /// ```
+    /// use std::sync::Arc;
+    ///
+    /// use leveled_hash_map::{
+    ///     LeveledHashMap, LeveledHashMapError, OverwritePolicy,
+    /// };
+    ///
+    /// let mut map = LeveledHashMap::new();
+    ///
+    /// map.set_overwrite_policy(OverwritePolicy::Error);
+    ///
+    /// map.insert(&[Arc::new("food")], 100).unwrap();
+    ///
+    /// map.insert(&[Arc::new("food"), Arc::new("dessert")], 100).unwrap();
+    ///
+    /// // try to overwrite "food"
+    ///
+    /// match map.insert(&[Arc::new("food")], 200) {
+    ///     Ok(_) => unreachable!(),
+    ///     Err(err) => match err {
+    ///         LeveledHashMapError::KeyHasChildren {
+    ///             level,
+    ///             key,
+    ///         } => {
+    ///             assert_eq!(0, level);
+    ///             assert_eq!(Arc::new("food"), key);
+    ///         },
+    ///         _ => unreachable!(),
+    ///     },
+    /// }
+    /// ```
+    KeyHasChildren { level: usize, key: Arc<K> },
+    /// The key is reserved at the level, so it is not allowed to be inserted by the ordinary insertion methods.
+    /// ```
+    /// use std::sync::Arc;
+    ///
+    /// use leveled_hash_map::{LeveledHashMap, LeveledHashMapError};
+    ///
+    /// let mut map = LeveledHashMap::new();
+    ///
+    /// map.reserve_key(1, "__meta");
+    ///
+    /// map.insert(&[Arc::new("food")], 100).unwrap();
+    ///
+    /// // try to insert "food/__meta"
+    ///
+    /// match map.insert(&[Arc::new("food"), Arc::new("__meta")], 200) {
+    ///     Ok(_) => unreachable!(),
+    ///     Err(err) => match err {
+    ///         LeveledHashMapError::KeyReserved {
+    ///             level,
+    ///             key,
+    ///         } => {
+    ///             assert_eq!(1, level);
+    ///             assert_eq!(Arc::new("__meta"), key);
+    ///         },
+    ///         _ => unreachable!(),
+    ///     },
+    /// }
+    /// ```
+    KeyReserved { level: usize, key: Arc<K> },
+    /// The parent key chain for inserting children does not exist.
+    /// ```
+    /// use std::{collections::HashMap, sync::Arc};
+    ///
+    /// use leveled_hash_map::{LeveledHashMap, LeveledHashMapError};
+    ///
+    /// let mut map = LeveledHashMap::new();
+    ///
+    /// map.insert(&[Arc::new("food")], 100).unwrap();
+    ///
+    /// let mut batch = HashMap::new();
+    ///
+    /// batch.insert("dog", 200);
+    /// batch.insert("cat", 201);
+    ///
+    /// // try to insert children of "animal"
+    ///
+    /// match map.insert_many(&[Arc::new("animal")], batch, 0) {
+    ///     Ok(_) => unreachable!(),
+    ///     Err(err) => match err {
+    ///         LeveledHashMapError::ChildrenOfMissingParent {
+    ///             parent_chain,
+    ///             children,
+    ///         } => {
+    ///             assert_eq!(vec![Arc::new("animal")], parent_chain);
+    ///             assert_eq!(2, children);
+    ///         },
+    ///         _ => unreachable!(),
+    ///     },
+    /// }
+    /// ```
+    ChildrenOfMissingParent { parent_chain: Vec<Arc<K>>, children: usize },
+    /// The type of the value is not allowed at the level.
+    /// ```
+    /// use std::{any::Any, sync::Arc};
+    ///
+    /// use leveled_hash_map::{LeveledHashMap, LeveledHashMapError};
+    ///
+    /// let mut map: LeveledHashMap<&'static str, Box<dyn Any>> =
+    ///     LeveledHashMap::new();
+    ///
+    /// map.allow_type_at_level::<String>(0);
+    ///
+    /// match map.insert(&[Arc::new("food")], Box::new(1u8)) {
+    ///     Ok(_) => unreachable!(),
+    ///     Err(err) => match err {
+    ///         LeveledHashMapError::ValueNotAllowed {
+    ///             level,
+    ///             key,
+    ///         } => {
+    ///             assert_eq!(0, level);
+    ///             assert_eq!(Arc::new("food"), key);
+    ///         },
+    ///         _ => unreachable!(),
+    ///     },
+    /// }
+    /// ```
+    ValueNotAllowed { level: usize, key: Arc<K> },
+    /// The level already holds as many entries as its limit allows.
+    /// ```
+    /// use std::sync::Arc;
+    ///
+    /// use leveled_hash_map::{LevelLimits, LeveledHashMap, LeveledHashMapError};
+    ///
+    /// let mut map: LeveledHashMap<&'static str, u8> = LeveledHashMap::new();
+    ///
+    /// map.set_level_limits(0, LevelLimits {
+    ///     max_entries: Some(1),
+    ///     ..LevelLimits::default()
+    /// });
+    ///
+    /// map.insert(&[Arc::new("food")], 1).unwrap();
+    ///
+    /// match map.insert(&[Arc::new("animal")], 2) {
+    ///     Ok(_) => unreachable!(),
+    ///     Err(err) => match err {
+    ///         LeveledHashMapError::LevelFull {
+    ///             level,
+    ///             limit,
+    ///         } => {
+    ///             assert_eq!(0, level);
+    ///             assert_eq!(1, limit);
+    ///         },
+    ///         _ => unreachable!(),
+    ///     },
+    /// }
+    /// ```
+    LevelFull { level: usize, limit: usize },
+    /// The parent already has as many children as the limit of its level allows.
+    /// ```
+    /// use std::sync::Arc;
+    ///
+    /// use leveled_hash_map::{LevelLimits, LeveledHashMap, LeveledHashMapError};
+    ///
+    /// let mut map: LeveledHashMap<&'static str, u8> = LeveledHashMap::new();
+    ///
+    /// map.set_level_limits(0, LevelLimits {
+    ///     max_children: Some(1),
+    ///     ..LevelLimits::default()
+    /// });
+    ///
+    /// map.insert(&[Arc::new("food")], 1).unwrap();
+    /// map.insert(&[Arc::new("food"), Arc::new("dessert")], 2).unwrap();
+    ///
+    /// match map.insert(&[Arc::new("food"), Arc::new("meat")], 3) {
+    ///     Ok(_) => unreachable!(),
+    ///     Err(err) => match err {
+    ///         LeveledHashMapError::TooManyChildren {
+    ///             level,
+    ///             key,
+    ///             limit,
+    ///         } => {
+    ///             assert_eq!(0, level);
+    ///             assert_eq!(Arc::new("food"), key);
+    ///             assert_eq!(1, limit);
+    ///         },
+    ///         _ => unreachable!(),
+    ///     },
+    /// }
+    /// ```
+    TooManyChildren { level: usize, key: Arc<K>, limit: usize },
+    /// The level which the key chain starts with is deeper than the max level of the map. `max` is `None` if the map has no levels.
+    /// ```
+    /// use std::sync::Arc;
+    ///
+    /// use leveled_hash_map::{LeveledHashMap, LeveledHashMapError};
+    ///
+    /// let mut map: LeveledHashMap<&'static str, u8> = LeveledHashMap::new();
+    ///
+    /// map.insert(&[Arc::new("food")], 1).unwrap();
+    ///
+    /// match map.get_professional(&[Arc::new("food")], 3) {
+    ///     Ok(_) => unreachable!(),
+    ///     Err(err) => match err {
+    ///         LeveledHashMapError::LevelOutOfRange {
+    ///             level,
+    ///             max,
+    ///         } => {
+    ///             assert_eq!(3, level);
+    ///             assert_eq!(Some(0), max);
+    ///         },
+    ///         _ => unreachable!(),
+    ///     },
+    /// }
+    /// ```
+    LevelOutOfRange { level: usize, max: Option<usize> },
+    /// Tombstones are not enabled, so an entry cannot be soft-removed.
+    /// ```
+    /// use std::sync::Arc;
+    ///
+    /// use leveled_hash_map::{LeveledHashMap, LeveledHashMapError};
+    ///
+    /// let mut map: LeveledHashMap<&'static str, u8> = LeveledHashMap::new();
+    ///
+    /// map.insert(&[Arc::new("food")], 1).unwrap();
+    ///
+    /// match map.soft_remove(&[Arc::new("food")]) {
+    ///     Ok(_) => unreachable!(),
+    ///     Err(err) => match err {
+    ///         LeveledHashMapError::TombstonesDisabled => (),
+    ///         _ => unreachable!(),
+    ///     },
+    /// }
+    ///
+    /// assert_eq!(Some(&1), map.get(&[Arc::new("food")]));
+    /// ```
+    TombstonesDisabled,
 }
 
 impl<K> Debug for LeveledHashMapError<K> {
@@ -176,6 +622,72 @@ impl<K> Debug for LeveledHashMapError<K> {
                 s.field("Level", level);
                 s.finish()
             },
+            LeveledHashMapError::KeyDuplicated {
+                level, ..
+            } => {
+                let mut s = f.debug_struct("KeyDuplicated");
+                s.field("Level", level);
+                s.finish()
+            },
+            LeveledHashMapError::KeyHasChildren {
+                level, ..
+            } => {
+                let mut s = f.debug_struct("KeyHasChildren");
+                s.field("Level", level);
+                s.finish()
+            },
+            LeveledHashMapError::KeyReserved {
+                level, ..
+            } => {
+                let mut s = f.debug_struct("KeyReserved");
+                s.field("Level", level);
+                s.finish()
+            },
+            LeveledHashMapError::ChildrenOfMissingParent {
+                parent_chain,
+                children,
+            } => {
+                let mut s = f.debug_struct("ChildrenOfMissingParent");
+                s.field("ParentChainLength", &parent_chain.len());
+                s.field("Children", children);
+                s.finish()
+            },
+            LeveledHashMapError::ValueNotAllowed {
+                level, ..
+            } => {
+                let mut s = f.debug_struct("ValueNotAllowed");
+                s.field("Level", level);
+                s.finish()
+            },
+            LeveledHashMapError::LevelFull {
+                level,
+                limit,
+            } => {
+                let mut s = f.debug_struct("LevelFull");
+                s.field("Level", level);
+                s.field("Limit", limit);
+                s.finish()
+            },
+            LeveledHashMapError::TooManyChildren {
+                level,
+                limit,
+                ..
+            } => {
+                let mut s = f.debug_struct("TooManyChildren");
+                s.field("Level", level);
+                s.field("Limit", limit);
+                s.finish()
+            },
+            LeveledHashMapError::LevelOutOfRange {
+                level,
+                max,
+            } => {
+                let mut s = f.debug_struct("LevelOutOfRange");
+                s.field("Level", level);
+                s.field("Max", max);
+                s.finish()
+            },
+            LeveledHashMapError::TombstonesDisabled => f.write_str("TombstonesDisabled"),
         }
     }
 }
@@ -198,33 +710,211 @@ impl<K> Display for LeveledHashMapError<K> {
             LeveledHashMapError::KeyChainIncorrect {
                 level, ..
             } => f.write_fmt(format_args!("The key chain is incorrect at level {}.", level)),
+            LeveledHashMapError::KeyDuplicated {
+                level, ..
+            } => f.write_fmt(format_args!("The key would be duplicated at level {}.", level)),
+            LeveledHashMapError::KeyHasChildren {
+                level, ..
+            } => f.write_fmt(format_args!(
+                "The entry at level {} has children and its value is not allowed to be \
+                 overwritten.",
+                level
+            )),
+            LeveledHashMapError::KeyReserved {
+                level, ..
+            } => f.write_fmt(format_args!("The key is reserved at level {}.", level)),
+            LeveledHashMapError::ChildrenOfMissingParent {
+                parent_chain,
+                children,
+            } => f.write_fmt(format_args!(
+                "The parent key chain (length {}) of {} children to be inserted does not exist.",
+                parent_chain.len(),
+                children
+            )),
+            LeveledHashMapError::ValueNotAllowed {
+                level, ..
+            } => f.write_fmt(format_args!(
+                "The type of the value is not allowed at level {}.",
+                level
+            )),
+            LeveledHashMapError::LevelFull {
+                level,
+                limit,
+            } => f.write_fmt(format_args!(
+                "Level {} already holds {} entries, which is its limit.",
+                level, limit
+            )),
+            LeveledHashMapError::TooManyChildren {
+                level,
+                limit,
+                ..
+            } => f.write_fmt(format_args!(
+                "The parent at level {} already has {} children, which is the limit.",
+                level, limit
+            )),
+            LeveledHashMapError::LevelOutOfRange {
+                level,
+                max,
+            } => match max {
+                Some(max) => f.write_fmt(format_args!(
+                    "Level {} is deeper than the max level {} of a `LeveledHashMap`.",
+                    level, max
+                )),
+                None => f.write_fmt(format_args!(
+                    "Level {} does not exist because a `LeveledHashMap` has no levels.",
+                    level
+                )),
+            },
+            LeveledHashMapError::TombstonesDisabled => {
+                f.write_str("Tombstones are not enabled for the `LeveledHashMap`.")
+            },
         }
     }
 }
 
 impl<K> Error for LeveledHashMapError<K> {}
 
-impl<K: Eq + Hash, V> LeveledHashMap<K, V> {
-    /// Create a new `LeveledHashMap` instance. The key needs to be implemented `Eq` and `Hash` traits.
-    /// ```
-    /// use leveled_hash_map::LeveledHashMap;
-    ///
-    /// let _map: LeveledHashMap<&'static str, String> = LeveledHashMap::new();
-    /// ```
-    #[inline]
-    pub fn new() -> LeveledHashMap<K, V> {
-        LeveledHashMap {
-            pool: Vec::new(), sub: Vec::new()
-        }
-    }
-
-    /// Get a value by a key chain. The key chain starts at Level 0.
+impl<K> LeveledHashMapError<K> {
+    /// Get a stable code of the kind of the error, such as `"key_not_exist"`, for mapping errors to localized messages without matching the English `Display` strings. The codes are the snake-case names of the variants, and the fields of the variants can be used as the parameters of the messages.
     /// ```
     /// use std::sync::Arc;
     ///
-    /// use leveled_hash_map::LeveledHashMap;
+    /// use leveled_hash_map::{LeveledHashMap, LeveledHashMapError};
     ///
-    /// let map: LeveledHashMap<&'static str, String> = LeveledHashMap::new();
+    /// fn localize<K>(err: &LeveledHashMapError<K>) -> String {
+    ///     match err.error_code() {
+    ///         "key_chain_empty" => String::from("鍵鏈是空的。"),
+    ///         "key_not_exist" | "key_chain_incorrect" => {
+    ///             String::from("找不到項目。")
+    ///         },
+    ///         code => format!("錯誤:{}", code),
+    ///     }
+    /// }
+    ///
+    /// let map: LeveledHashMap<&'static str, u8> = LeveledHashMap::new();
+    ///
+    /// let err = map.get_professional(&[], 0).unwrap_err();
+    ///
+    /// assert_eq!("key_chain_empty", err.error_code());
+    /// assert_eq!("鍵鏈是空的。", localize(&err));
+    /// ```
+    #[inline]
+    pub fn error_code(&self) -> &'static str {
+        match self {
+            LeveledHashMapError::KeyTooMany => "key_too_many",
+            LeveledHashMapError::KeyNotExist {
+                ..
+            } => "key_not_exist",
+            LeveledHashMapError::KeyChainEmpty => "key_chain_empty",
+            LeveledHashMapError::KeyChainIncorrect {
+                ..
+            } => "key_chain_incorrect",
+            LeveledHashMapError::KeyDuplicated {
+                ..
+            } => "key_duplicated",
+            LeveledHashMapError::KeyHasChildren {
+                ..
+            } => "key_has_children",
+            LeveledHashMapError::KeyReserved {
+                ..
+            } => "key_reserved",
+            LeveledHashMapError::ChildrenOfMissingParent {
+                ..
+            } => "children_of_missing_parent",
+            LeveledHashMapError::ValueNotAllowed {
+                ..
+            } => "value_not_allowed",
+            LeveledHashMapError::LevelFull {
+                ..
+            } => "level_full",
+            LeveledHashMapError::TooManyChildren {
+                ..
+            } => "too_many_children",
+            LeveledHashMapError::LevelOutOfRange {
+                ..
+            } => "level_out_of_range",
+            LeveledHashMapError::TombstonesDisabled => "tombstones_disabled",
+        }
+    }
+}
+
+impl<K: Eq + Hash, V> LeveledHashMap<K, V> {
+    /// Create a new `LeveledHashMap` instance. The key needs to be implemented `Eq` and `Hash` traits.
+    /// ```
+    /// use leveled_hash_map::LeveledHashMap;
+    ///
+    /// let _map: LeveledHashMap<&'static str, String> = LeveledHashMap::new();
+    /// ```
+    #[inline]
+    pub fn new() -> LeveledHashMap<K, V> {
+        LeveledHashMap {
+            pool:                            Vec::new(),
+            sub:                             Vec::new(),
+            generations:                     None,
+            overwrite_policy:                OverwritePolicy::KeepSubtree,
+            reserved:                        Vec::new(),
+            projections:                     Vec::new(),
+            allowed_types:                   Vec::new(),
+            tombstones:                      None,
+            limits:                          Vec::new(),
+            #[cfg(feature = "log")]
+            slow_op:                         None,
+            #[cfg(feature = "stats")]
+            stats:                           None,
+        }
+    }
+
+    /// Create a new `LeveledHashMap` instance from a static list of key chains which start at Level 0 and their values. Key chains are inserted from the shortest to the longest, so parents can be listed after their children. Along with `std::sync::OnceLock`, it can be used to define a global map which is built on the first access.
+    /// ```
+    /// use std::sync::{Arc, OnceLock};
+    ///
+    /// use leveled_hash_map::LeveledHashMap;
+    ///
+    /// fn commands() -> &'static LeveledHashMap<&'static str, &'static str> {
+    ///     static COMMANDS: OnceLock<LeveledHashMap<&'static str, &'static str>> =
+    ///         OnceLock::new();
+    ///
+    ///     COMMANDS.get_or_init(|| {
+    ///         LeveledHashMap::from_static_paths(&[
+    ///             (&["remote", "add"], "Add a remote."),
+    ///             (&["remote"], "Manage remotes."),
+    ///             (&["status"], "Show the status."),
+    ///         ])
+    ///         .unwrap()
+    ///     })
+    /// }
+    ///
+    /// assert_eq!(
+    ///     Some(&"Add a remote."),
+    ///     commands().get(&[Arc::new("remote"), Arc::new("add")])
+    /// );
+    /// ```
+    pub fn from_static_paths(paths: &[(&[K], V)]) -> Result<Self, LeveledHashMapError<K>>
+    where
+        K: Clone,
+        V: Clone, {
+        let mut order: Vec<&(&[K], V)> = paths.iter().collect();
+
+        order.sort_by_key(|(key_chain, _)| key_chain.len());
+
+        let mut map = LeveledHashMap::new();
+
+        for (key_chain, value) in order {
+            let key_chain: Vec<Arc<K>> = key_chain.iter().cloned().map(Arc::new).collect();
+
+            map.insert(&key_chain, value.clone())?;
+        }
+
+        Ok(map)
+    }
+
+    /// Get a value by a key chain. The key chain starts at Level 0.
+    /// ```
+    /// use std::sync::Arc;
+    ///
+    /// use leveled_hash_map::LeveledHashMap;
+    ///
+    /// let map: LeveledHashMap<&'static str, String> = LeveledHashMap::new();
     ///
     /// let _result = map.get(&[Arc::new("first_key")]);
     /// ```
@@ -278,7 +968,7 @@ impl<K: Eq + Hash, V> LeveledHashMap<K, V> {
         self.get_professional_mut(key_chain, start_level).ok().map(|v| v.1)
     }
 
-    /// Get a value and its parent key by a key chain and a level which the key chain starts with. It returns a `Err(LeveledHashMapError)` instance to describe the reason of the getting failure.
+    /// Get a value and its parent key by a key chain and a level which the key chain starts with. It returns a `Err(LeveledHashMapError)` instance to describe the reason of the getting failure. `get_with_parent` returns the same information with named fields.
     /// ```
     /// use std::sync::Arc;
     ///
@@ -303,15 +993,29 @@ impl<K: Eq + Hash, V> LeveledHashMap<K, V> {
     /// assert_eq!(Some(Arc::new("food")), result_2.0);
     /// assert_eq!("甜點", result_2.1);
     /// ```
+    #[inline]
     pub fn get_professional(
         &self,
         key_chain: &[Arc<K>],
         start_level: usize,
     ) -> Result<(Option<Arc<K>>, &V), LeveledHashMapError<K>> {
+        self.get_with_parent(key_chain, start_level).map(|entry| (entry.parent_key, entry.value))
+    }
+
+    fn get_inner(
+        &self,
+        key_chain: &[Arc<K>],
+        start_level: usize,
+    ) -> Result<(Option<&Arc<K>>, &V), LeveledHashMapError<K>> {
         let key_chain_len = key_chain.len();
 
         if key_chain_len == 0 {
             return Err(LeveledHashMapError::KeyChainEmpty);
+        } else if start_level > 0 && start_level >= self.pool.len() {
+            return Err(LeveledHashMapError::LevelOutOfRange {
+                level: start_level,
+                max:   self.max_level(),
+            });
         } else if key_chain_len + start_level > self.pool.len() {
             return Err(LeveledHashMapError::KeyTooMany);
         }
@@ -360,8 +1064,7 @@ impl<K: Eq + Hash, V> LeveledHashMap<K, V> {
                         last_key: pk.as_ref().map(Arc::clone),
                     });
                 }
-                let pk = pk.as_ref().map(Arc::clone);
-                Ok((pk, v))
+                Ok((pk.as_ref(), v))
             },
             None => Err(LeveledHashMapError::KeyNotExist {
                 level: ii, key: Arc::clone(ck)
@@ -369,7 +1072,7 @@ impl<K: Eq + Hash, V> LeveledHashMap<K, V> {
         }
     }
 
-    /// Get a value and its parent key by a key chain and a level which the key chain starts with. It returns a `Err(LeveledHashMapError)` instance to describe the reason of the getting failure.
+    /// Get a value and its parent key by a key chain and a level which the key chain starts with. It returns a `Err(LeveledHashMapError)` instance to describe the reason of the getting failure. `get_with_parent_mut` returns the same information with named fields.
     /// ```
     /// use std::sync::Arc;
     ///
@@ -389,70 +1092,164 @@ impl<K: Eq + Hash, V> LeveledHashMap<K, V> {
     /// assert_eq!(None, result.0);
     /// assert_eq!("食物/食品", result.1);
     /// ```
+    #[inline]
     pub fn get_professional_mut(
         &mut self,
         key_chain: &[Arc<K>],
         start_level: usize,
     ) -> Result<(Option<Arc<K>>, &mut V), LeveledHashMapError<K>> {
-        let key_chain_len = key_chain.len();
+        self.get_with_parent_mut(key_chain, start_level)
+            .map(|entry| (entry.parent_key, entry.value))
+    }
 
-        if key_chain_len == 0 {
-            return Err(LeveledHashMapError::KeyChainEmpty);
-        } else if key_chain_len + start_level > self.pool.len() {
-            return Err(LeveledHashMapError::KeyTooMany);
+    /// Get a value by a key chain and a level which the key chain starts with. It returns a `Err(LeveledHashMapError)` instance to describe the reason of the getting failure, without cloning the parent key like `get_professional` does.
+    /// ```
+    /// use std::sync::Arc;
+    ///
+    /// use leveled_hash_map::{LeveledHashMap, LeveledHashMapError};
+    ///
+    /// let mut map: LeveledHashMap<&'static str, u8> = LeveledHashMap::new();
+    ///
+    /// map.insert(&[Arc::new("food")], 1).unwrap();
+    /// map.insert(&[Arc::new("food"), Arc::new("dessert")], 2).unwrap();
+    ///
+    /// assert_eq!(&2, map.try_get(&[Arc::new("dessert")], 1).unwrap());
+    ///
+    /// match map.try_get(&[Arc::new("food"), Arc::new("meat")], 0) {
+    ///     Err(LeveledHashMapError::KeyNotExist {
+    ///         level,
+    ///         key,
+    ///     }) => {
+    ///         assert_eq!(1, level);
+    ///         assert_eq!(Arc::new("meat"), key);
+    ///     },
+    ///     _ => unreachable!(),
+    /// }
+    /// ```
+    #[inline]
+    pub fn try_get(
+        &self,
+        key_chain: &[Arc<K>],
+        start_level: usize,
+    ) -> Result<&V, LeveledHashMapError<K>> {
+        let (_, v) = self.get_inner(key_chain, start_level)?;
+
+        #[cfg(feature = "stats")]
+        {
+            let key_chain_len_dec = key_chain.len() - 1;
+
+            self.record_access(
+                key_chain_len_dec + start_level,
+                &key_chain[key_chain_len_dec],
+                false,
+            );
         }
 
-        let key_chain_len_dec = key_chain_len - 1;
+        Ok(v)
+    }
 
-        let mut i = 0;
+    /// Get a mutable value by a key chain and a level which the key chain starts with. It returns a `Err(LeveledHashMapError)` instance to describe the reason of the getting failure, without cloning the parent key like `get_professional_mut` does.
+    /// ```
+    /// use std::sync::Arc;
+    ///
+    /// use leveled_hash_map::LeveledHashMap;
+    ///
+    /// let mut map: LeveledHashMap<&'static str, u8> = LeveledHashMap::new();
+    ///
+    /// map.insert(&[Arc::new("food")], 1).unwrap();
+    ///
+    /// *map.try_get_mut(&[Arc::new("food")], 0).unwrap() += 1;
+    ///
+    /// assert_eq!(Some(&2), map.get(&[Arc::new("food")]));
+    /// assert!(map.try_get_mut(&[Arc::new("meat")], 0).is_err());
+    /// ```
+    pub fn try_get_mut(
+        &mut self,
+        key_chain: &[Arc<K>],
+        start_level: usize,
+    ) -> Result<&mut V, LeveledHashMapError<K>> {
+        self.get_inner(key_chain, start_level)?;
 
-        let mut last_key = None;
+        let key_chain_len_dec = key_chain.len() - 1;
 
-        while i < key_chain_len_dec {
-            let ii = i + start_level;
-            let ck = &key_chain[i];
-            match self.pool[ii].get(ck) {
-                Some((pk, _)) => {
-                    if ii > start_level && last_key.ne(&pk.as_ref()) {
-                        return Err(LeveledHashMapError::KeyChainIncorrect {
-                            level:    ii,
-                            key:      Arc::clone(ck),
-                            last_key: pk.as_ref().map(Arc::clone),
-                        });
-                    }
-                    last_key = Some(ck);
-                },
-                None => {
-                    return Err(LeveledHashMapError::KeyNotExist {
-                        level: ii,
-                        key:   Arc::clone(ck),
-                    })
-                },
-            }
+        let level = key_chain_len_dec + start_level;
+        let key = &key_chain[key_chain_len_dec];
 
-            i += 1;
+        if let Some(generations) = self.generations.as_mut() {
+            generations.mark_changed(level, key);
         }
 
-        let ck = &key_chain[key_chain_len_dec];
+        #[cfg(feature = "stats")]
+        self.record_access(level, key, true);
 
-        let ii = key_chain_len_dec + start_level;
+        Ok(&mut self.pool[level].get_mut(key).unwrap().1)
+    }
 
-        match self.pool[ii].get_mut(ck) {
-            Some((pk, v)) => {
-                if ii > start_level && last_key.ne(&pk.as_ref()) {
-                    return Err(LeveledHashMapError::KeyChainIncorrect {
-                        level:    ii,
-                        key:      Arc::clone(ck),
-                        last_key: pk.as_ref().map(Arc::clone),
-                    });
-                }
-                let pk = pk.as_ref().map(Arc::clone);
-                Ok((pk, v))
-            },
-            None => Err(LeveledHashMapError::KeyNotExist {
-                level: ii, key: Arc::clone(ck)
-            }),
+    /// Clone the descendants of an entry by a key chain which starts at Level 0 into a standalone map, leaving this map untouched. The children of the entry become the entries at Level 0 of the new map, and the keys are shared with this map.
+    /// ```
+    /// use std::sync::Arc;
+    ///
+    /// use leveled_hash_map::LeveledHashMap;
+    ///
+    /// let mut map: LeveledHashMap<&'static str, u8> = LeveledHashMap::new();
+    ///
+    /// map.insert(&[Arc::new("food")], 1).unwrap();
+    /// map.insert(&[Arc::new("food"), Arc::new("dessert")], 2).unwrap();
+    /// map.insert(&[Arc::new("food"), Arc::new("dessert"), Arc::new("cake")], 3)
+    ///     .unwrap();
+    ///
+    /// let subtree = map.clone_subtree(&[Arc::new("food")]).unwrap();
+    ///
+    /// assert_eq!(Some(&2), subtree.get(&[Arc::new("dessert")]));
+    /// assert_eq!(Some(&3), subtree.get(&[Arc::new("dessert"), Arc::new("cake")]));
+    /// assert_eq!(Some(&2), map.get(&[Arc::new("food"), Arc::new("dessert")]));
+    /// ```
+    pub fn clone_subtree(
+        &self,
+        key_chain: &[Arc<K>],
+    ) -> Result<LeveledHashMap<K, V>, LeveledHashMapError<K>>
+    where
+        V: Clone, {
+        if key_chain.is_empty() {
+            return Err(LeveledHashMapError::KeyChainEmpty);
+        }
+
+        self.get_inner(key_chain, 0)?;
+
+        let level = key_chain.len() - 1;
+
+        let mut map = LeveledHashMap::new();
+
+        let mut current: Vec<&Arc<K>> = self.sub[level][&key_chain[level]].iter().collect();
+        let mut l = level + 1;
+
+        while !current.is_empty() {
+            let mut pool = HashMap::with_capacity(current.len());
+            let mut sub = HashMap::with_capacity(current.len());
+            let mut next = Vec::new();
+
+            for key in current {
+                let (pk, v) = &self.pool[l][key];
+
+                let pk = if l == level + 1 { None } else { pk.as_ref().map(Arc::clone) };
+
+                pool.insert(Arc::clone(key), (pk, v.clone()));
+
+                let children = &self.sub[l][key];
+
+                sub.insert(Arc::clone(key), children.clone());
+
+                next.extend(children.iter());
+            }
+
+            map.pool.push(pool);
+            map.sub.push(sub);
+
+            current = next;
+            l += 1;
         }
+
+        Ok(map)
     }
 
     /// Remove a value by a key chain. The key chain starts at Level 0.
@@ -532,7 +1329,7 @@ impl<K: Eq + Hash, V> LeveledHashMap<K, V> {
         self.remove_professional(key_chain, start_level).ok().map(|v| (v.1, v.2))
     }
 
-    /// Remove a value by a key chain and a level which the key chain starts with. It returns a `Err(LeveledHashMapError)` instance to describe the reason of the getting failure.
+    /// Remove a value by a key chain and a level which the key chain starts with. It returns a `Err(LeveledHashMapError)` instance to describe the reason of the getting failure. `remove_with_subtree` returns the same information with named fields.
     /// ```
     /// use std::sync::Arc;
     ///
@@ -564,6 +1361,7 @@ impl<K: Eq + Hash, V> LeveledHashMap<K, V> {
     ///     result.2[0].get(&Arc::new("meat")).unwrap()
     /// );
     /// ```
+    #[inline]
     pub fn remove_professional(
         &mut self,
         key_chain: &[Arc<K>],
@@ -572,33 +1370,42 @@ impl<K: Eq + Hash, V> LeveledHashMap<K, V> {
         (Option<Arc<K>>, V, Vec<HashMap<Arc<K>, (Option<Arc<K>>, V)>>),
         LeveledHashMapError<K>,
     > {
-        let last_key = self.get_professional(key_chain, start_level)?.0;
-
-        let key_chain_len = key_chain.len();
+        self.remove_with_subtree(key_chain, start_level)
+            .map(|removed| (removed.parent_key, removed.value, removed.descendants))
+    }
 
-        let key_chain_len_dec = key_chain_len - 1;
+    /// Remove an existing entry at a specific level along with its descendants, without validating the key chain.
+    fn remove_node(
+        &mut self,
+        level: usize,
+        key: &Arc<K>,
+    ) -> (Option<Arc<K>>, V, Vec<HashMap<Arc<K>, (Option<Arc<K>>, V)>>) {
+        let (pk, v) = self.pool[level].remove(key).unwrap();
 
-        let level = key_chain_len_dec + start_level;
+        if let Some(generations) = self.generations.as_mut() {
+            generations.mark_removed(level, key);
+        }
 
-        let (pk, v) = self.pool[level].remove(&key_chain[key_chain_len_dec]).unwrap();
+        #[cfg(feature = "stats")]
+        self.forget_access(level, key);
 
-        if level > 0 {
-            if let Some(v) = self.sub[level - 1].get_mut(&last_key.unwrap()) {
-                v.remove(&key_chain[key_chain_len_dec]);
+        if let Some(pk) = pk.as_ref() {
+            if let Some(v) = self.sub[level - 1].get_mut(pk) {
+                v.remove(key);
             }
         }
 
-        let sub = self.sub[level].remove(&key_chain[key_chain_len_dec]).unwrap();
+        let sub = self.sub[level].remove(key).unwrap_or_default();
 
         if sub.is_empty() {
-            return Ok((pk, v, Vec::new()));
+            return (pk, v, Vec::new());
         }
 
         let mut sub_values: Vec<HashMap<Arc<K>, (Option<Arc<K>>, V)>> = Vec::new();
         let mut my_sub_values = HashMap::new();
 
         for s in sub {
-            let (a, b, mut c) = self.remove_professional(&[Arc::clone(&s)], level + 1).unwrap();
+            let (a, b, mut c) = self.remove_node(level + 1, &s);
 
             let len = c.len();
 
@@ -621,7 +1428,7 @@ impl<K: Eq + Hash, V> LeveledHashMap<K, V> {
 
         sub_values.insert(0, my_sub_values);
 
-        Ok((pk, v, sub_values))
+        (pk, v, sub_values)
     }
 
     /// Insert a value by a key chain. It returns a `Err(LeveledHashMapError)` instance to describe the reason of the getting failure.
@@ -653,55 +1460,462 @@ impl<K: Eq + Hash, V> LeveledHashMap<K, V> {
         key_chain: &[Arc<K>],
         value: V,
     ) -> Result<Option<V>, LeveledHashMapError<K>> {
-        let key_chain_len = key_chain.len();
-
-        if key_chain_len == 0 {
-            return Err(LeveledHashMapError::KeyChainEmpty);
-        }
-
-        let key_chain_len_dec = key_chain_len - 1;
-
-        if key_chain_len_dec > self.pool.len() {
-            return Err(LeveledHashMapError::KeyTooMany);
+        if let Some(key) = key_chain.last() {
+            self.check_reserved(key_chain.len() - 1, key)?;
         }
 
-        match self.get_professional(key_chain, 0) {
-            Ok(_) => {
-                if key_chain_len_dec > 0 {
-                    Ok(self.pool[key_chain_len_dec]
-                        .insert(
-                            Arc::clone(&key_chain[key_chain_len_dec]),
-                            (Some(Arc::clone(&key_chain[key_chain_len_dec - 1])), value),
-                        )
-                        .map(|v| v.1))
-                } else {
-                    Ok(self.pool[0].insert(Arc::clone(&key_chain[0]), (None, value)).map(|v| v.1))
-                }
-            },
-            Err(err) => match err {
-                LeveledHashMapError::KeyChainEmpty => Err(LeveledHashMapError::KeyChainEmpty),
-                LeveledHashMapError::KeyTooMany => {
-                    let mut map = HashMap::new();
+        self.insert_reserved(key_chain, value)
+    }
 
-                    if self.pool.is_empty() {
-                        map.insert(Arc::clone(&key_chain[0]), (None, value));
+    /// Apply a closure to the value by a key chain which starts at Level 0 in place, and get the result of the closure. It returns a `Err(LeveledHashMapError)` instance to describe why the entry cannot be found.
+    /// ```
+    /// use std::sync::Arc;
+    ///
+    /// use leveled_hash_map::{LeveledHashMap, LeveledHashMapError};
+    ///
+    /// let mut map: LeveledHashMap<&'static str, u32> = LeveledHashMap::new();
+    ///
+    /// map.insert(&[Arc::new("food")], 1).unwrap();
+    ///
+    /// map.update(&[Arc::new("food")], |v| *v += 1).unwrap();
+    ///
+    /// assert_eq!(Some(&2), map.get(&[Arc::new("food")]));
+    ///
+    /// match map.update(&[Arc::new("animal")], |v| *v += 1) {
+    ///     Err(LeveledHashMapError::KeyNotExist {
+    ///         level, ..
+    ///     }) => assert_eq!(0, level),
+    ///     _ => unreachable!(),
+    /// }
+    /// ```
+    #[inline]
+    pub fn update<T, F: FnOnce(&mut V) -> T>(
+        &mut self,
+        key_chain: &[Arc<K>],
+        f: F,
+    ) -> Result<T, LeveledHashMapError<K>> {
+        self.get_professional_mut(key_chain, 0).map(|(_, v)| f(v))
+    }
 
-                        self.pool.push(map);
+    /// Check whether an entry exists by a key chain which starts at Level 0. It neither clones keys nor builds errors, so it is cheaper than `get` for validating many key chains.
+    /// ```
+    /// use std::sync::Arc;
+    ///
+    /// use leveled_hash_map::LeveledHashMap;
+    ///
+    /// let mut map: LeveledHashMap<&'static str, u8> = LeveledHashMap::new();
+    ///
+    /// map.insert(&[Arc::new("food")], 1).unwrap();
+    /// map.insert(&[Arc::new("food"), Arc::new("dessert")], 2).unwrap();
+    ///
+    /// assert!(map.contains_key_chain(&[Arc::new("food"), Arc::new("dessert")]));
+    /// assert!(!map.contains_key_chain(&[Arc::new("dessert")]));
+    /// assert!(!map.contains_key_chain(&[]));
+    /// ```
+    #[inline]
+    pub fn contains_key_chain(&self, key_chain: &[Arc<K>]) -> bool {
+        !key_chain.is_empty() && self.existing_prefix_len(key_chain) == key_chain.len()
+    }
 
-                        let mut map = HashMap::new();
+    /// Check whether a key exists at a specific level, no matter what parent key it has.
+    /// ```
+    /// use std::sync::Arc;
+    ///
+    /// use leveled_hash_map::LeveledHashMap;
+    ///
+    /// let mut map: LeveledHashMap<&'static str, u8> = LeveledHashMap::new();
+    ///
+    /// map.insert(&[Arc::new("food")], 1).unwrap();
+    /// map.insert(&[Arc::new("food"), Arc::new("dessert")], 2).unwrap();
+    ///
+    /// assert!(map.contains_key_at_level(1, &"dessert"));
+    /// assert!(!map.contains_key_at_level(0, &"dessert"));
+    /// ```
+    #[inline]
+    pub fn contains_key_at_level(&self, level: usize, key: &K) -> bool {
+        self.pool.get(level).map_or(false, |map| map.contains_key(key))
+    }
 
-                        map.insert(Arc::clone(&key_chain[0]), HashSet::new());
+    /// Get the key chain stored in the map and the value by a key chain which starts at Level 0. The returned key chain consists of the `Arc`s owned by the map instead of the given ones, so they can be cloned cheaply and reused.
+    /// ```
+    /// use std::sync::Arc;
+    ///
+    /// use leveled_hash_map::LeveledHashMap;
+    ///
+    /// let mut map: LeveledHashMap<String, u8> = LeveledHashMap::new();
+    ///
+    /// let food = Arc::new("food".to_string());
+    ///
+    /// map.insert(&[Arc::clone(&food)], 1).unwrap();
+    ///
+    /// let (key_chain, value) =
+    ///     map.get_key_value_chain(&[Arc::new("food".to_string())]).unwrap();
+    ///
+    /// assert!(Arc::ptr_eq(&food, &key_chain[0]));
+    /// assert_eq!(&1, value);
+    /// ```
+    pub fn get_key_value_chain(&self, key_chain: &[Arc<K>]) -> Option<(Vec<Arc<K>>, &V)> {
+        let (_, value) = self.get_professional(key_chain, 0).ok()?;
 
-                        self.sub.push(map);
+        let key_chain = key_chain
+            .iter()
+            .enumerate()
+            .map(|(level, key)| Arc::clone(self.pool[level].get_key_value(key).unwrap().0))
+            .collect();
 
-                        Ok(None)
-                    } else {
-                        map.insert(
-                            Arc::clone(&key_chain[key_chain_len_dec]),
-                            (Some(Arc::clone(&key_chain[key_chain_len_dec - 1])), value),
-                        );
+        Some((key_chain, value))
+    }
 
-                        self.pool.push(map);
+    /// Get values by several key chains which start at Level 0 at once. Consecutive key chains sharing the same leading keys are validated only once for the shared part, so looking up siblings in a row is cheaper than calling `get` for each of them.
+    /// ```
+    /// use std::sync::Arc;
+    ///
+    /// use leveled_hash_map::LeveledHashMap;
+    ///
+    /// let mut map: LeveledHashMap<&'static str, u8> = LeveledHashMap::new();
+    ///
+    /// map.insert(&[Arc::new("food")], 1).unwrap();
+    /// map.insert(&[Arc::new("food"), Arc::new("dessert")], 2).unwrap();
+    /// map.insert(&[Arc::new("food"), Arc::new("meat")], 3).unwrap();
+    ///
+    /// let food = Arc::new("food");
+    ///
+    /// assert_eq!(
+    ///     vec![Some(&2), None, Some(&3), Some(&1)],
+    ///     map.get_many(&[
+    ///         &[Arc::clone(&food), Arc::new("dessert")],
+    ///         &[Arc::clone(&food), Arc::new("fruit")],
+    ///         &[Arc::clone(&food), Arc::new("meat")],
+    ///         &[Arc::clone(&food)],
+    ///     ])
+    /// );
+    /// ```
+    pub fn get_many(&self, key_chains: &[&[Arc<K>]]) -> Vec<Option<&V>> {
+        let mut result = Vec::with_capacity(key_chains.len());
+
+        // the previous key chain and how many keys at the start of it exist with the right parents
+        let mut previous: &[Arc<K>] = &[];
+        let mut valid = 0;
+
+        for key_chain in key_chains.iter().copied() {
+            valid = previous
+                .iter()
+                .zip(key_chain.iter())
+                .take(valid)
+                .take_while(|(a, b)| a == b)
+                .count();
+
+            let mut parent = if valid > 0 { Some(&key_chain[valid - 1]) } else { None };
+
+            for (level, key) in key_chain.iter().enumerate().skip(valid) {
+                match self.pool.get(level).and_then(|map| map.get(key)) {
+                    Some((pk, _)) if pk.as_ref() == parent => {
+                        parent = Some(key);
+                        valid = level + 1;
+                    },
+                    _ => break,
+                }
+            }
+
+            let value = if valid > 0 && valid == key_chain.len() {
+                let level = valid - 1;
+
+                #[cfg(feature = "stats")]
+                self.record_access(level, &key_chain[level], false);
+
+                self.pool[level].get(&key_chain[level]).map(|(_, v)| v)
+            } else {
+                None
+            };
+
+            result.push(value);
+
+            previous = key_chain;
+        }
+
+        result
+    }
+
+    /// Get mutable references to the values by several key chains which start at Level 0 at once. It returns `None` if any of the entries does not exist, or if any two key chains address the same entry. An entry which is the only one wanted at its level is looked up directly, but the entries at a level where two or more are wanted are found by walking the level until all of them are reached, which takes time proportional to the number of entries at that level in the worst case.
+    /// ```
+    /// use std::sync::Arc;
+    ///
+    /// use leveled_hash_map::LeveledHashMap;
+    ///
+    /// let mut map: LeveledHashMap<&'static str, u32> = LeveledHashMap::new();
+    ///
+    /// map.insert(&[Arc::new("warehouse")], 10).unwrap();
+    /// map.insert(&[Arc::new("warehouse"), Arc::new("shelf")], 0).unwrap();
+    ///
+    /// let [warehouse, shelf] = map
+    ///     .get_many_mut([&[Arc::new("warehouse")], &[
+    ///         Arc::new("warehouse"),
+    ///         Arc::new("shelf"),
+    ///     ]])
+    ///     .unwrap();
+    ///
+    /// *warehouse -= 3;
+    /// *shelf += 3;
+    ///
+    /// assert_eq!(Some(&7), map.get(&[Arc::new("warehouse")]));
+    /// assert_eq!(Some(&3), map.get(&[Arc::new("warehouse"), Arc::new("shelf")]));
+    ///
+    /// assert!(map
+    ///     .get_many_mut([&[Arc::new("warehouse")], &[Arc::new("warehouse")]])
+    ///     .is_none());
+    /// ```
+    pub fn get_many_mut<const N: usize>(
+        &mut self,
+        key_chains: [&[Arc<K>]; N],
+    ) -> Option<[&mut V; N]> {
+        for (i, key_chain) in key_chains.iter().enumerate() {
+            self.get_inner(key_chain, 0).ok()?;
+
+            let level = key_chain.len() - 1;
+
+            if key_chains[..i].iter().any(|c| c.len() - 1 == level && c[level] == key_chain[level])
+            {
+                return None;
+            }
+        }
+
+        for key_chain in key_chains.iter() {
+            let level = key_chain.len() - 1;
+
+            if let Some(generations) = self.generations.as_mut() {
+                generations.mark_changed(level, &key_chain[level]);
+            }
+
+            #[cfg(feature = "stats")]
+            self.record_access(level, &key_chain[level], true);
+        }
+
+        let mut values: Vec<Option<&mut V>> = (0..N).map(|_| None).collect();
+
+        let mut remaining = N;
+
+        for (level, map) in self.pool.iter_mut().enumerate() {
+            if remaining == 0 {
+                break;
+            }
+
+            let wanted = key_chains.iter().filter(|c| c.len() - 1 == level).count();
+
+            match wanted {
+                0 => (),
+                1 => {
+                    let i = key_chains.iter().position(|c| c.len() - 1 == level).unwrap();
+
+                    values[i] = map.get_mut(&key_chains[i][level]).map(|(_, v)| v);
+                },
+                _ => {
+                    let mut left = wanted;
+
+                    for (key, (_, v)) in map.iter_mut() {
+                        if let Some(i) =
+                            key_chains.iter().position(|c| c.len() - 1 == level && c[level] == *key)
+                        {
+                            values[i] = Some(v);
+
+                            left -= 1;
+
+                            if left == 0 {
+                                break;
+                            }
+                        }
+                    }
+                },
+            }
+
+            remaining -= wanted;
+        }
+
+        values.into_iter().collect::<Option<Vec<&mut V>>>()?.try_into().ok()
+    }
+
+    /// Get a mutable reference to the value by a key chain which starts at Level 0, inserting the value computed by a closure first if the last key does not exist. All the other keys in the key chain need to exist.
+    /// ```
+    /// use std::sync::Arc;
+    ///
+    /// use leveled_hash_map::LeveledHashMap;
+    ///
+    /// let mut map: LeveledHashMap<&'static str, Vec<u8>> = LeveledHashMap::new();
+    ///
+    /// map.get_or_insert_with(&[Arc::new("food")], Vec::new).unwrap().push(1);
+    /// map.get_or_insert_with(&[Arc::new("food")], Vec::new).unwrap().push(2);
+    ///
+    /// assert_eq!(Some(&vec![1, 2]), map.get(&[Arc::new("food")]));
+    ///
+    /// assert!(map
+    ///     .get_or_insert_with(&[Arc::new("animal"), Arc::new("dog")], Vec::new)
+    ///     .is_err());
+    /// ```
+    pub fn get_or_insert_with<F: FnOnce() -> V>(
+        &mut self,
+        key_chain: &[Arc<K>],
+        f: F,
+    ) -> Result<&mut V, LeveledHashMapError<K>> {
+        let missing = match self.get_inner(key_chain, 0) {
+            Ok(_) => false,
+            Err(LeveledHashMapError::KeyNotExist {
+                level, ..
+            }) if level == key_chain.len() - 1 => true,
+            Err(LeveledHashMapError::KeyTooMany) if key_chain.len() == self.pool.len() + 1 => true,
+            Err(err) => return Err(err),
+        };
+
+        if missing {
+            self.insert(key_chain, f())?;
+        }
+
+        self.get_professional_mut(key_chain, 0).map(|(_, v)| v)
+    }
+
+    /// Insert a value by a key chain of owned keys which starts at Level 0. The keys are wrapped in `Arc`s internally, and the `Arc`s already stored in the map are reused for the keys which exist. It returns a `Err(LeveledHashMapError)` instance to describe the reason of the inserting failure.
+    /// ```
+    /// use std::sync::Arc;
+    ///
+    /// use leveled_hash_map::LeveledHashMap;
+    ///
+    /// let mut map: LeveledHashMap<String, u8> = LeveledHashMap::new();
+    ///
+    /// map.insert_owned(vec!["food".to_string()], 1).unwrap();
+    /// map.insert_owned(vec!["food".to_string(), "dessert".to_string()], 2)
+    ///     .unwrap();
+    ///
+    /// assert_eq!(Some(&2), map.get_ref::<str>(&["food", "dessert"]));
+    /// assert_eq!(
+    ///     Some(2),
+    ///     map.insert_owned(["food", "dessert"].map(String::from), 3).unwrap()
+    /// );
+    /// ```
+    pub fn insert_owned<I: IntoIterator<Item = K>>(
+        &mut self,
+        key_chain: I,
+        value: V,
+    ) -> Result<Option<V>, LeveledHashMapError<K>> {
+        let key_chain: Vec<Arc<K>> = key_chain
+            .into_iter()
+            .enumerate()
+            .map(|(level, key)| match self.pool.get(level).and_then(|m| m.get_key_value(&key)) {
+                Some((k, _)) => Arc::clone(k),
+                None => Arc::new(key),
+            })
+            .collect();
+
+        self.insert(&key_chain, value)
+    }
+
+    /// Insert a value by a key chain, even if the last key in the key chain is reserved. It returns a `Err(LeveledHashMapError)` instance to describe the reason of the inserting failure.
+    /// ```
+    /// use std::sync::Arc;
+    ///
+    /// use leveled_hash_map::LeveledHashMap;
+    ///
+    /// let mut map: LeveledHashMap<&'static str, String> = LeveledHashMap::new();
+    ///
+    /// map.reserve_key(0, "__meta");
+    ///
+    /// assert!(map.insert(&[Arc::new("__meta")], "metadata".to_string()).is_err());
+    ///
+    /// map.insert_reserved(&[Arc::new("__meta")], "metadata".to_string()).unwrap();
+    ///
+    /// assert_eq!("metadata", map.get(&[Arc::new("__meta")]).unwrap());
+    /// ```
+    pub fn insert_reserved(
+        &mut self,
+        key_chain: &[Arc<K>],
+        value: V,
+    ) -> Result<Option<V>, LeveledHashMapError<K>> {
+        let result = self.insert_inner(key_chain, value);
+
+        if result.is_ok() {
+            let level = key_chain.len() - 1;
+
+            if let Some(generations) = self.generations.as_mut() {
+                generations.mark_changed(level, &key_chain[level]);
+            }
+
+            #[cfg(feature = "stats")]
+            self.record_access(level, &key_chain[level], true);
+        }
+
+        result
+    }
+
+    fn insert_inner(
+        &mut self,
+        key_chain: &[Arc<K>],
+        value: V,
+    ) -> Result<Option<V>, LeveledHashMapError<K>> {
+        let key_chain_len = key_chain.len();
+
+        if key_chain_len == 0 {
+            return Err(LeveledHashMapError::KeyChainEmpty);
+        }
+
+        let key_chain_len_dec = key_chain_len - 1;
+
+        if key_chain_len_dec > self.pool.len() {
+            return Err(LeveledHashMapError::KeyTooMany);
+        }
+
+        if !self.is_value_allowed(key_chain_len_dec, &value) {
+            return Err(LeveledHashMapError::ValueNotAllowed {
+                level: key_chain_len_dec,
+                key:   Arc::clone(&key_chain[key_chain_len_dec]),
+            });
+        }
+
+        match self.get_inner(key_chain, 0) {
+            Ok(_) => {
+                self.check_overwrite(key_chain_len_dec, &key_chain[key_chain_len_dec])?;
+                self.apply_overwrite_policy(key_chain_len_dec, &key_chain[key_chain_len_dec]);
+
+                if key_chain_len_dec > 0 {
+                    Ok(self.pool[key_chain_len_dec]
+                        .insert(
+                            Arc::clone(&key_chain[key_chain_len_dec]),
+                            (Some(Arc::clone(&key_chain[key_chain_len_dec - 1])), value),
+                        )
+                        .map(|v| v.1))
+                } else {
+                    Ok(self.pool[0].insert(Arc::clone(&key_chain[0]), (None, value)).map(|v| v.1))
+                }
+            },
+            Err(err) => match err {
+                LeveledHashMapError::KeyTooMany => {
+                    let mut map = HashMap::new();
+
+                    if self.pool.is_empty() {
+                        self.check_limits(0, None, 1)?;
+
+                        map.insert(Arc::clone(&key_chain[0]), (None, value));
+
+                        self.pool.push(map);
+
+                        let mut map = HashMap::new();
+
+                        map.insert(Arc::clone(&key_chain[0]), HashSet::new());
+
+                        self.sub.push(map);
+
+                        Ok(None)
+                    } else {
+                        // the parent needs to exist at the current max level
+                        self.get_inner(&key_chain[..key_chain_len_dec], 0)?;
+                        self.check_limits(
+                            key_chain_len_dec,
+                            Some(&key_chain[key_chain_len_dec - 1]),
+                            1,
+                        )?;
+
+                        map.insert(
+                            Arc::clone(&key_chain[key_chain_len_dec]),
+                            (Some(Arc::clone(&key_chain[key_chain_len_dec - 1])), value),
+                        );
+
+                        self.pool.push(map);
 
                         let mut map = HashMap::new();
 
@@ -718,19 +1932,24 @@ impl<K: Eq + Hash, V> LeveledHashMap<K, V> {
                         Ok(None)
                     }
                 },
-                LeveledHashMapError::KeyChainIncorrect {
-                    level,
-                    key,
-                    last_key,
-                } => Err(LeveledHashMapError::KeyChainIncorrect {
-                    level,
-                    key,
-                    last_key,
-                }),
                 LeveledHashMapError::KeyNotExist {
                     level,
                     key,
                 } => {
+                    // only the last key is allowed to be absent
+                    if level < key_chain_len_dec {
+                        return Err(LeveledHashMapError::KeyNotExist {
+                            level,
+                            key,
+                        });
+                    }
+
+                    self.check_limits(
+                        level,
+                        if level > 0 { Some(&key_chain[level - 1]) } else { None },
+                        1,
+                    )?;
+
                     self.sub[level]
                         .insert(Arc::clone(&key_chain[key_chain_len_dec]), HashSet::new());
                     if level > 0 {
@@ -747,11 +1966,14 @@ impl<K: Eq + Hash, V> LeveledHashMap<K, V> {
                     }
                     Ok(None)
                 },
+                err => Err(err),
             },
         }
     }
 
     /// Insert values by a key chain and a `HashMap` instance and a level which the key chain starts with. It returns a `Err(LeveledHashMapError)` instance to describe the reason of the getting failure.
+    ///
+    /// The whole batch is validated before anything is inserted, so either all of the values are inserted, or none of them are inserted and the map stays unchanged.
     /// ```
     /// use std::{collections::HashMap, sync::Arc};
     ///
@@ -788,7 +2010,7 @@ impl<K: Eq + Hash, V> LeveledHashMap<K, V> {
             return Err(LeveledHashMapError::KeyTooMany);
         }
 
-        match self.get_professional(key_chain, start_level) {
+        match self.get_inner(key_chain, start_level) {
             Ok(_) => {
                 let key_chain_len_dec = key_chain_len - 1;
 
@@ -796,33 +2018,56 @@ impl<K: Eq + Hash, V> LeveledHashMap<K, V> {
 
                 let level = key_chain_len + start_level;
 
-                if level >= self.pool.len() {
-                    self.pool.push(HashMap::new());
-                    self.sub.push(HashMap::new());
-                }
-
                 let last_key = &key_chain[key_chain_len_dec];
 
-                let mut temp = HashMap::new();
+                // validate the whole batch before changing anything
+                let value = self.check_value_types(level, value)?;
 
-                for (k, v) in value {
-                    let k = Arc::new(k);
-
-                    if let Some((pk, _)) = self.pool[level].get(&Arc::clone(&k)) {
+                for k in value.keys() {
+                    if let Some((stored_key, (pk, _))) =
+                        self.pool.get(level).and_then(|m| m.get_key_value(k))
+                    {
                         if last_key.ne(pk.as_ref().unwrap()) {
                             return Err(LeveledHashMapError::KeyChainIncorrect {
                                 level,
-                                key: Arc::clone(&k),
+                                key: Arc::clone(stored_key),
                                 last_key: pk.as_ref().map(Arc::clone),
                             });
                         }
                     }
 
-                    temp.insert(k, v);
+                    self.check_reserved(level, k)?;
+                    self.check_overwrite(level, k)?;
+                }
+
+                let new_keys = match self.pool.get(level) {
+                    Some(m) => value.keys().filter(|k| !m.contains_key(*k)).count(),
+                    None => value.len(),
+                };
+
+                self.check_limits(level, Some(last_key), new_keys)?;
+
+                if level >= self.pool.len() {
+                    self.pool.push(HashMap::new());
+                    self.sub.push(HashMap::new());
                 }
 
-                for (k, v) in temp {
-                    match self.pool[level].insert(Arc::clone(&k), (Some(Arc::clone(last_key)), v)) {
+                for (k, v) in value {
+                    self.apply_overwrite_policy(level, &k);
+
+                    let k = Arc::new(k);
+
+                    if let Some(generations) = self.generations.as_mut() {
+                        generations.mark_changed(level, &k);
+                    }
+
+                    let old =
+                        self.pool[level].insert(Arc::clone(&k), (Some(Arc::clone(last_key)), v));
+
+                    #[cfg(feature = "stats")]
+                    self.record_access(level, &k, true);
+
+                    match old {
                         Some((_, v)) => {
                             previous.insert(k, v);
                         },
@@ -836,28 +2081,40 @@ impl<K: Eq + Hash, V> LeveledHashMap<K, V> {
                 Ok(previous)
             },
             Err(err) => match err {
-                LeveledHashMapError::KeyChainIncorrect {
-                    level,
-                    key,
-                    last_key,
-                } => Err(LeveledHashMapError::KeyChainIncorrect {
-                    level,
-                    key,
-                    last_key,
-                }),
                 LeveledHashMapError::KeyNotExist {
-                    level,
-                    key,
-                } => Err(LeveledHashMapError::KeyNotExist {
-                    level,
-                    key,
+                    ..
+                } => Err(LeveledHashMapError::ChildrenOfMissingParent {
+                    parent_chain: key_chain.to_vec(),
+                    children:     value.len(),
                 }),
-                LeveledHashMapError::KeyTooMany => Err(LeveledHashMapError::KeyTooMany),
+                // the last parent key would be at the level right after the max level, so it is missing as well
+                LeveledHashMapError::KeyTooMany
+                    if key_chain_len + start_level <= self.pool.len() + 1 =>
+                {
+                    Err(LeveledHashMapError::ChildrenOfMissingParent {
+                        parent_chain: key_chain.to_vec(),
+                        children:     value.len(),
+                    })
+                },
                 LeveledHashMapError::KeyChainEmpty => {
                     if start_level > 0 {
                         return Err(LeveledHashMapError::KeyChainEmpty);
                     }
 
+                    let value = self.check_value_types(0, value)?;
+
+                    for k in value.keys() {
+                        self.check_reserved(0, k)?;
+                        self.check_overwrite(0, k)?;
+                    }
+
+                    let new_keys = match self.pool.first() {
+                        Some(m) => value.keys().filter(|k| !m.contains_key(*k)).count(),
+                        None => value.len(),
+                    };
+
+                    self.check_limits(0, None, new_keys)?;
+
                     if self.pool.is_empty() {
                         self.pool.push(HashMap::new());
                         self.sub.push(HashMap::new());
@@ -866,8 +2123,20 @@ impl<K: Eq + Hash, V> LeveledHashMap<K, V> {
                     let mut previous = HashMap::new();
 
                     for (k, v) in value {
+                        self.apply_overwrite_policy(0, &k);
+
                         let k = Arc::new(k);
-                        match self.pool[0].insert(Arc::clone(&k), (None, v)) {
+
+                        if let Some(generations) = self.generations.as_mut() {
+                            generations.mark_changed(0, &k);
+                        }
+
+                        let old = self.pool[0].insert(Arc::clone(&k), (None, v));
+
+                        #[cfg(feature = "stats")]
+                        self.record_access(0, &k, true);
+
+                        match old {
                             Some((_, v)) => {
                                 previous.insert(k, v);
                             },
@@ -879,13 +2148,87 @@ impl<K: Eq + Hash, V> LeveledHashMap<K, V> {
 
                     Ok(previous)
                 },
+                err => Err(err),
             },
         }
     }
 
-    /// Get the keys at a specific level.
+    /// Insert `(key_chain, value)` tuples, where every key chain starts at Level 0. The tuples are inserted from the shortest key chain to the longest one, so parents can come after their children. The whole batch is validated against a copy of the keys of the map before anything is inserted, which takes time proportional to the number of entries in the map, so either all of the tuples are inserted, or none of them are inserted and the error of the first tuple which cannot be inserted is returned.
     /// ```
-    /// use std::{collections::HashMap, sync::Arc};
+    /// use std::sync::Arc;
+    ///
+    /// use leveled_hash_map::{LeveledHashMap, LeveledHashMapError};
+    ///
+    /// let mut map: LeveledHashMap<&'static str, u8> = LeveledHashMap::new();
+    ///
+    /// map.try_extend(vec![
+    ///     (vec![Arc::new("food"), Arc::new("dessert")], 2),
+    ///     (vec![Arc::new("food")], 1),
+    /// ])
+    /// .unwrap();
+    ///
+    /// match map.try_extend(vec![
+    ///     (vec![Arc::new("animal")], 3),
+    ///     (vec![Arc::new("plant"), Arc::new("tree")], 4),
+    /// ]) {
+    ///     Err(LeveledHashMapError::KeyNotExist {
+    ///         level,
+    ///         key,
+    ///     }) => {
+    ///         assert_eq!(0, level);
+    ///         assert_eq!(Arc::new("plant"), key);
+    ///     },
+    ///     _ => unreachable!(),
+    /// }
+    ///
+    /// assert_eq!(None, map.get(&[Arc::new("animal")]));
+    /// assert_eq!(1, map.keys(0).unwrap().len());
+    /// ```
+    pub fn try_extend<I: IntoIterator<Item = (Vec<Arc<K>>, V)>>(
+        &mut self,
+        iter: I,
+    ) -> Result<(), LeveledHashMapError<K>> {
+        let mut entries: Vec<(Vec<Arc<K>>, V)> = iter.into_iter().collect();
+
+        entries.sort_by_key(|(key_chain, _)| key_chain.len());
+
+        {
+            let mut shadow: LeveledHashMap<K, ()> = LeveledHashMap::new();
+
+            shadow.pool = self
+                .pool
+                .iter()
+                .map(|m| m.iter().map(|(k, (pk, _))| (Arc::clone(k), (pk.clone(), ()))).collect())
+                .collect();
+            shadow.sub = self.sub.clone();
+            shadow.overwrite_policy = self.overwrite_policy;
+            shadow.reserved = self.reserved.clone();
+            shadow.limits = self.limits.clone();
+
+            for (key_chain, value) in entries.iter() {
+                shadow.insert(key_chain, ())?;
+
+                let level = key_chain.len() - 1;
+
+                if !self.is_value_allowed(level, value) {
+                    return Err(LeveledHashMapError::ValueNotAllowed {
+                        level,
+                        key: Arc::clone(&key_chain[level]),
+                    });
+                }
+            }
+        }
+
+        for (key_chain, value) in entries {
+            self.insert(&key_chain, value)?;
+        }
+
+        Ok(())
+    }
+
+    /// Insert a value as a child of an existing parent key at a specific level, without the key chain from Level 0. It returns a `Err(LeveledHashMapError)` instance to describe the reason of the inserting failure.
+    /// ```
+    /// use std::sync::Arc;
     ///
     /// use leveled_hash_map::LeveledHashMap;
     ///
@@ -893,8 +2236,129 @@ impl<K: Eq + Hash, V> LeveledHashMap<K, V> {
     ///
     /// map.insert(&[Arc::new("food")], "食物".to_string()).unwrap();
     ///
-    /// let mut insert_map = HashMap::new();
-    ///
+    /// map.insert(&[Arc::new("food"), Arc::new("dessert")], "甜點".to_string())
+    ///     .unwrap();
+    ///
+    /// let dessert = Arc::new("dessert");
+    ///
+    /// map.insert_child(&dessert, 1, "cake", "蛋糕".to_string()).unwrap();
+    ///
+    /// let result = map
+    ///     .get(&[Arc::new("food"), Arc::new("dessert"), Arc::new("cake")])
+    ///     .unwrap();
+    ///
+    /// assert_eq!("蛋糕", result);
+    /// ```
+    pub fn insert_child(
+        &mut self,
+        parent_key: &Arc<K>,
+        parent_level: usize,
+        key: K,
+        value: V,
+    ) -> Result<Option<V>, LeveledHashMapError<K>> {
+        let parent_key = match self.pool.get(parent_level).and_then(|m| m.get_key_value(parent_key))
+        {
+            Some((pk, _)) => Arc::clone(pk),
+            None => {
+                return Err(LeveledHashMapError::KeyNotExist {
+                    level: parent_level,
+                    key:   Arc::clone(parent_key),
+                })
+            },
+        };
+
+        let level = parent_level + 1;
+
+        self.check_reserved(level, &key)?;
+
+        if !self.is_value_allowed(level, &value) {
+            return Err(LeveledHashMapError::ValueNotAllowed {
+                level,
+                key: Arc::new(key),
+            });
+        }
+
+        if !self.pool.get(level).map(|m| m.contains_key(&key)).unwrap_or(false) {
+            self.check_limits(level, Some(&parent_key), 1)?;
+        }
+
+        if level == self.pool.len() {
+            self.pool.push(HashMap::new());
+            self.sub.push(HashMap::new());
+        }
+
+        let (key, previous) = match self.pool[level].get_key_value(&key) {
+            Some((k, (pk, _))) => {
+                if parent_key.ne(pk.as_ref().unwrap()) {
+                    return Err(LeveledHashMapError::KeyChainIncorrect {
+                        level,
+                        key: Arc::clone(k),
+                        last_key: pk.as_ref().map(Arc::clone),
+                    });
+                }
+
+                let k = Arc::clone(k);
+
+                self.check_overwrite(level, &k)?;
+                self.apply_overwrite_policy(level, &k);
+
+                let previous = mem::replace(&mut self.pool[level].get_mut(&k).unwrap().1, value);
+
+                (k, Some(previous))
+            },
+            None => {
+                let k = Arc::new(key);
+
+                self.pool[level].insert(Arc::clone(&k), (Some(Arc::clone(&parent_key)), value));
+                self.sub[level].insert(Arc::clone(&k), HashSet::new());
+                self.sub[parent_level].get_mut(&parent_key).unwrap().insert(Arc::clone(&k));
+
+                (k, None)
+            },
+        };
+
+        if let Some(generations) = self.generations.as_mut() {
+            generations.mark_changed(level, &key);
+        }
+
+        #[cfg(feature = "stats")]
+        self.record_access(level, &key, true);
+
+        Ok(previous)
+    }
+
+    /// Get the max level of the map, which is the deepest level holding or having held entries, or `None` if the map has no levels. It can be used to validate the level which a key chain starts with.
+    /// ```
+    /// use std::sync::Arc;
+    ///
+    /// use leveled_hash_map::LeveledHashMap;
+    ///
+    /// let mut map: LeveledHashMap<&'static str, u8> = LeveledHashMap::new();
+    ///
+    /// assert_eq!(None, map.max_level());
+    ///
+    /// map.insert(&[Arc::new("food")], 1).unwrap();
+    /// map.insert(&[Arc::new("food"), Arc::new("dessert")], 2).unwrap();
+    ///
+    /// assert_eq!(Some(1), map.max_level());
+    /// ```
+    #[inline]
+    pub fn max_level(&self) -> Option<usize> {
+        self.pool.len().checked_sub(1)
+    }
+
+    /// Get the keys at a specific level.
+    /// ```
+    /// use std::{collections::HashMap, sync::Arc};
+    ///
+    /// use leveled_hash_map::LeveledHashMap;
+    ///
+    /// let mut map: LeveledHashMap<&'static str, String> = LeveledHashMap::new();
+    ///
+    /// map.insert(&[Arc::new("food")], "食物".to_string()).unwrap();
+    ///
+    /// let mut insert_map = HashMap::new();
+    ///
     /// insert_map.insert("dessert", "甜點".to_string());
     /// insert_map.insert("meat", "肉類".to_string());
     ///
@@ -912,6 +2376,1339 @@ impl<K: Eq + Hash, V> LeveledHashMap<K, V> {
     pub fn keys(&self, level: usize) -> Option<&HashMap<Arc<K>, HashSet<Arc<K>>>> {
         self.sub.get(level)
     }
+
+    /// Get a depth-first iterator over the key chains of an entry and all of its descendants by a key chain which starts at Level 0. If the key chain is empty, it iterates the whole map. Parents always come before their children, and the values are never touched.
+    /// ```
+    /// use std::sync::Arc;
+    ///
+    /// use leveled_hash_map::LeveledHashMap;
+    ///
+    /// let mut map: LeveledHashMap<&'static str, u8> = LeveledHashMap::new();
+    ///
+    /// map.insert(&[Arc::new("food")], 1).unwrap();
+    /// map.insert(&[Arc::new("food"), Arc::new("dessert")], 2).unwrap();
+    /// map.insert(&[Arc::new("food"), Arc::new("dessert"), Arc::new("cake")], 3)
+    ///     .unwrap();
+    /// map.insert(&[Arc::new("animal")], 4).unwrap();
+    ///
+    /// let key_chains: Vec<Vec<Arc<&'static str>>> = map
+    ///     .keys_deep(&[Arc::new("food"), Arc::new("dessert")])
+    ///     .unwrap()
+    ///     .collect();
+    ///
+    /// assert_eq!(
+    ///     vec![vec![Arc::new("food"), Arc::new("dessert")], vec![
+    ///         Arc::new("food"),
+    ///         Arc::new("dessert"),
+    ///         Arc::new("cake")
+    ///     ],],
+    ///     key_chains
+    /// );
+    ///
+    /// assert_eq!(4, map.keys_deep(&[]).unwrap().count());
+    /// ```
+    pub fn keys_deep(
+        &self,
+        prefix_chain: &[Arc<K>],
+    ) -> Result<KeysDeep<'_, K>, LeveledHashMapError<K>> {
+        if prefix_chain.is_empty() {
+            return Ok(KeysDeep {
+                sub:   &self.sub,
+                path:  Vec::new(),
+                stack: self
+                    .sub
+                    .first()
+                    .map(|m| m.keys().map(|k| (0, k)).collect())
+                    .unwrap_or_default(),
+            });
+        }
+
+        self.get_inner(prefix_chain, 0)?;
+
+        let level = prefix_chain.len() - 1;
+
+        let path = prefix_chain[..level]
+            .iter()
+            .enumerate()
+            .map(|(l, k)| Arc::clone(self.sub[l].get_key_value(k).unwrap().0))
+            .collect();
+
+        let key = self.sub[level].get_key_value(&prefix_chain[level]).unwrap().0;
+
+        Ok(KeysDeep {
+            sub: &self.sub,
+            path,
+            stack: vec![(level, key)],
+        })
+    }
+
+    /// Get a depth-first iterator over the key chains of all entries, where every key chain starts at Level 0. Parents always come before their children, and the values are never touched. It is the same as `keys_deep(&[])`, which cannot fail.
+    /// ```
+    /// use std::sync::Arc;
+    ///
+    /// use leveled_hash_map::LeveledHashMap;
+    ///
+    /// let mut map: LeveledHashMap<&'static str, u8> = LeveledHashMap::new();
+    ///
+    /// map.insert(&[Arc::new("food")], 1).unwrap();
+    /// map.insert(&[Arc::new("food"), Arc::new("dessert")], 2).unwrap();
+    ///
+    /// let paths: Vec<Vec<Arc<&'static str>>> = map.paths().collect();
+    ///
+    /// assert_eq!(
+    ///     vec![vec![Arc::new("food")], vec![
+    ///         Arc::new("food"),
+    ///         Arc::new("dessert")
+    ///     ]],
+    ///     paths
+    /// );
+    /// ```
+    #[inline]
+    pub fn paths(&self) -> KeysDeep<'_, K> {
+        KeysDeep {
+            sub:   &self.sub,
+            path:  Vec::new(),
+            stack: self.sub.first().map(|m| m.keys().map(|k| (0, k)).collect()).unwrap_or_default(),
+        }
+    }
+
+    /// Get an iterator over the entries at a specific level. It yields `(key, parent_key, value)` tuples, and knows its exact length.
+    /// ```
+    /// use std::sync::Arc;
+    ///
+    /// use leveled_hash_map::LeveledHashMap;
+    ///
+    /// let mut map: LeveledHashMap<&'static str, u8> = LeveledHashMap::new();
+    ///
+    /// map.insert(&[Arc::new("food")], 1).unwrap();
+    /// map.insert(&[Arc::new("food"), Arc::new("dessert")], 2).unwrap();
+    /// map.insert(&[Arc::new("food"), Arc::new("meat")], 3).unwrap();
+    ///
+    /// let iter = map.iter_level(1).unwrap();
+    ///
+    /// assert_eq!(2, iter.len());
+    ///
+    /// for (_key, parent_key, _value) in iter {
+    ///     assert_eq!(Some(Arc::new("food")), parent_key);
+    /// }
+    ///
+    /// assert!(map.iter_level(2).is_none());
+    /// ```
+    #[inline]
+    pub fn iter_level(&self, level: usize) -> Option<LevelIter<'_, K, V>> {
+        self.pool.get(level).map(|m| LevelIter {
+            inner: m.iter()
+        })
+    }
+
+    /// Get an iterator over the links from the entries at a specific level to their parents, without touching the values. It yields `(key, parent_key)` tuples, and knows its exact length. The parent keys are always `None` at Level 0.
+    /// ```
+    /// use std::sync::Arc;
+    ///
+    /// use leveled_hash_map::LeveledHashMap;
+    ///
+    /// let mut map: LeveledHashMap<&'static str, u8> = LeveledHashMap::new();
+    ///
+    /// map.insert(&[Arc::new("food")], 1).unwrap();
+    /// map.insert(&[Arc::new("food"), Arc::new("dessert")], 2).unwrap();
+    ///
+    /// let links: Vec<_> = map.parent_links(1).unwrap().collect();
+    ///
+    /// assert_eq!(vec![(&Arc::new("dessert"), Some(&Arc::new("food")))], links);
+    /// ```
+    #[inline]
+    pub fn parent_links(&self, level: usize) -> Option<ParentLinks<'_, K, V>> {
+        self.pool.get(level).map(|m| ParentLinks {
+            inner: m.iter()
+        })
+    }
+
+    /// Get a mutable iterator over the entries at a specific level. It yields `(key, parent_key, value)` tuples, and knows its exact length. If generation tracking is enabled, all entries at the level are stamped as changed.
+    /// ```
+    /// use std::sync::Arc;
+    ///
+    /// use leveled_hash_map::LeveledHashMap;
+    ///
+    /// let mut map: LeveledHashMap<&'static str, u8> = LeveledHashMap::new();
+    ///
+    /// map.insert(&[Arc::new("food")], 1).unwrap();
+    /// map.insert(&[Arc::new("food"), Arc::new("dessert")], 2).unwrap();
+    /// map.insert(&[Arc::new("food"), Arc::new("meat")], 3).unwrap();
+    ///
+    /// for (_key, _parent_key, value) in map.iter_level_mut(1).unwrap() {
+    ///     *value *= 10;
+    /// }
+    ///
+    /// assert_eq!(Some(&20), map.get(&[Arc::new("food"), Arc::new("dessert")]));
+    /// assert_eq!(Some(&30), map.get(&[Arc::new("food"), Arc::new("meat")]));
+    /// ```
+    pub fn iter_level_mut(&mut self, level: usize) -> Option<LevelIterMut<'_, K, V>> {
+        let map = self.pool.get_mut(level)?;
+
+        if let Some(generations) = self.generations.as_mut() {
+            for key in map.keys() {
+                generations.mark_changed(level, key);
+            }
+        }
+
+        Some(LevelIterMut {
+            inner: map.iter_mut()
+        })
+    }
+
+    /// Get an iterator over the entries at a specific level grouped by their parents, computed from the child sets of the previous level. It yields `(parent_key, children)` pairs, where `children` is an iterator of `(key, value)` pairs. Parents without children are skipped, and the entries at Level 0 form one group whose parent key is `None`. It returns `None` if the level does not exist.
+    /// ```
+    /// use std::sync::Arc;
+    ///
+    /// use leveled_hash_map::LeveledHashMap;
+    ///
+    /// let mut map: LeveledHashMap<&'static str, u8> = LeveledHashMap::new();
+    ///
+    /// map.insert(&[Arc::new("food")], 1).unwrap();
+    /// map.insert(&[Arc::new("food"), Arc::new("dessert")], 2).unwrap();
+    /// map.insert(&[Arc::new("food"), Arc::new("meat")], 3).unwrap();
+    /// map.insert(&[Arc::new("animal")], 4).unwrap();
+    /// map.insert(&[Arc::new("animal"), Arc::new("dog")], 5).unwrap();
+    ///
+    /// let mut totals: Vec<(&str, u8)> = map
+    ///     .children_by_parent(1)
+    ///     .unwrap()
+    ///     .map(|(parent_key, children)| {
+    ///         (*parent_key.unwrap().as_ref(), children.map(|(_, v)| *v).sum())
+    ///     })
+    ///     .collect();
+    ///
+    /// totals.sort();
+    ///
+    /// assert_eq!(vec![("animal", 5), ("food", 5)], totals);
+    /// ```
+    pub fn children_by_parent(&self, level: usize) -> Option<ChildrenByParent<'_, K, V>> {
+        let map = self.pool.get(level)?;
+
+        Some(if level == 0 {
+            ChildrenByParent {
+                map,
+                roots: Some(&self.sub[0]),
+                parents: None,
+            }
+        } else {
+            ChildrenByParent {
+                map,
+                roots: None,
+                parents: Some(self.sub[level - 1].iter()),
+            }
+        })
+    }
+
+    /// Apply a closure to every value at a specific level with its key, by iterating the level directly without reconstructing any key chain. It returns the number of the patched entries, which is 0 if the level does not exist. If generation tracking is enabled, all entries at the level are stamped as changed.
+    /// ```
+    /// use std::sync::Arc;
+    ///
+    /// use leveled_hash_map::LeveledHashMap;
+    ///
+    /// let mut map: LeveledHashMap<&'static str, u32> = LeveledHashMap::new();
+    ///
+    /// map.insert(&[Arc::new("taiwan")], 0).unwrap();
+    /// map.insert(&[Arc::new("taiwan"), Arc::new("taipei")], 100).unwrap();
+    /// map.insert(&[Arc::new("taiwan"), Arc::new("tainan")], 80).unwrap();
+    ///
+    /// assert_eq!(2, map.patch_level(1, |_key, price| *price += 10));
+    ///
+    /// assert_eq!(Some(&110), map.get(&[Arc::new("taiwan"), Arc::new("taipei")]));
+    /// assert_eq!(Some(&90), map.get(&[Arc::new("taiwan"), Arc::new("tainan")]));
+    /// assert_eq!(0, map.patch_level(2, |_key, price| *price += 10));
+    /// ```
+    pub fn patch_level<F: FnMut(&K, &mut V)>(&mut self, level: usize, mut f: F) -> usize {
+        let map = match self.pool.get_mut(level) {
+            Some(map) => map,
+            None => return 0,
+        };
+
+        for (key, (_, value)) in map.iter_mut() {
+            if let Some(generations) = self.generations.as_mut() {
+                generations.mark_changed(level, key);
+            }
+
+            f(key.as_ref(), value);
+        }
+
+        map.len()
+    }
+
+    /// Get an iterator over all entries. It lazily walks the map depth-first, parents before their children, and yields `(key_chain, value)` tuples where every key chain starts at Level 0.
+    /// ```
+    /// use std::sync::Arc;
+    ///
+    /// use leveled_hash_map::LeveledHashMap;
+    ///
+    /// let mut map: LeveledHashMap<&'static str, u8> = LeveledHashMap::new();
+    ///
+    /// map.insert(&[Arc::new("food")], 1).unwrap();
+    /// map.insert(&[Arc::new("food"), Arc::new("dessert")], 2).unwrap();
+    ///
+    /// let mut iter = map.iter();
+    ///
+    /// assert_eq!(2, iter.len());
+    /// assert_eq!(Some((vec![Arc::new("food")], &1)), iter.next());
+    /// assert_eq!(
+    ///     Some((vec![Arc::new("food"), Arc::new("dessert")], &2)),
+    ///     iter.next()
+    /// );
+    /// assert_eq!(None, iter.next());
+    /// ```
+    #[inline]
+    pub fn iter(&self) -> Iter<'_, K, V> {
+        Iter {
+            pool:      &self.pool,
+            sub:       &self.sub,
+            path:      Vec::new(),
+            stack:     self
+                .pool
+                .first()
+                .map(|m| m.keys().map(|k| (0, k)).collect())
+                .unwrap_or_default(),
+            remaining: self.pool.iter().map(|m| m.len()).sum(),
+        }
+    }
+
+    /// Get a mutable iterator over all entries. It yields `(key_chain, value)` tuples level by level, where every key chain starts at Level 0, so parents are always yielded before their children. If generation tracking is enabled, all entries are stamped as changed.
+    /// ```
+    /// use std::sync::Arc;
+    ///
+    /// use leveled_hash_map::LeveledHashMap;
+    ///
+    /// let mut map: LeveledHashMap<&'static str, u32> = LeveledHashMap::new();
+    ///
+    /// map.insert(&[Arc::new("food")], 100).unwrap();
+    /// map.insert(&[Arc::new("food"), Arc::new("dessert")], 200).unwrap();
+    ///
+    /// for (key_chain, v) in map.iter_mut() {
+    ///     *v += key_chain.len() as u32;
+    /// }
+    ///
+    /// assert_eq!(Some(&101), map.get(&[Arc::new("food")]));
+    /// assert_eq!(Some(&202), map.get(&[Arc::new("food"), Arc::new("dessert")]));
+    /// ```
+    pub fn iter_mut(&mut self) -> IterMut<'_, K, V> {
+        self.mark_all_changed();
+
+        let remaining = self.pool.iter().map(|m| m.len()).sum();
+
+        IterMut {
+            levels: self.pool.iter_mut(),
+            current: None,
+            parents: HashMap::new(),
+            chains: HashMap::new(),
+            remaining,
+        }
+    }
+
+    /// Get a pre-order depth-first iterator over an entry and all of its descendants by a key chain which starts at Level 0. It yields `(key_chain, value)` tuples, parents before their children, and every key chain starts at Level 0.
+    /// ```
+    /// use std::sync::Arc;
+    ///
+    /// use leveled_hash_map::LeveledHashMap;
+    ///
+    /// let mut map: LeveledHashMap<&'static str, u8> = LeveledHashMap::new();
+    ///
+    /// map.insert(&[Arc::new("food")], 1).unwrap();
+    /// map.insert(&[Arc::new("food"), Arc::new("dessert")], 2).unwrap();
+    /// map.insert(&[Arc::new("food"), Arc::new("dessert"), Arc::new("cake")], 3)
+    ///     .unwrap();
+    /// map.insert(&[Arc::new("animal")], 4).unwrap();
+    ///
+    /// let mut iter =
+    ///     map.iter_subtree(&[Arc::new("food"), Arc::new("dessert")]).unwrap();
+    ///
+    /// assert_eq!(2, iter.len());
+    /// assert_eq!(
+    ///     Some((vec![Arc::new("food"), Arc::new("dessert")], &2)),
+    ///     iter.next()
+    /// );
+    /// assert_eq!(
+    ///     Some((
+    ///         vec![Arc::new("food"), Arc::new("dessert"), Arc::new("cake")],
+    ///         &3
+    ///     )),
+    ///     iter.next()
+    /// );
+    /// assert_eq!(None, iter.next());
+    /// ```
+    pub fn iter_subtree(
+        &self,
+        key_chain: &[Arc<K>],
+    ) -> Result<Iter<'_, K, V>, LeveledHashMapError<K>> {
+        if key_chain.is_empty() {
+            return Err(LeveledHashMapError::KeyChainEmpty);
+        }
+
+        self.get_inner(key_chain, 0)?;
+
+        let level = key_chain.len() - 1;
+
+        let key = self.pool[level].get_key_value(&key_chain[level]).unwrap().0;
+
+        Ok(Iter {
+            pool:      &self.pool,
+            sub:       &self.sub,
+            path:      self.key_chain_of(level, key)[..level].to_vec(),
+            stack:     vec![(level, key)],
+            remaining: self.subtree_len(level, key),
+        })
+    }
+
+    /// Get a mutable pre-order depth-first iterator over an entry and all of its descendants by a key chain which starts at Level 0. It yields `(key_chain, value)` tuples, parents before their children, and every key chain starts at Level 0. The order is computed eagerly, and the values below the entry are picked out by visiting every entry at the levels which the subtree spans, so the cost grows with the sizes of those levels rather than the size of the subtree. If generation tracking is enabled, all entries in the subtree are stamped as changed.
+    /// ```
+    /// use std::sync::Arc;
+    ///
+    /// use leveled_hash_map::LeveledHashMap;
+    ///
+    /// let mut map: LeveledHashMap<&'static str, u8> = LeveledHashMap::new();
+    ///
+    /// map.insert(&[Arc::new("food")], 1).unwrap();
+    /// map.insert(&[Arc::new("food"), Arc::new("dessert")], 2).unwrap();
+    /// map.insert(&[Arc::new("animal")], 3).unwrap();
+    ///
+    /// for (_key_chain, value) in
+    ///     map.iter_subtree_mut(&[Arc::new("food")]).unwrap()
+    /// {
+    ///     *value += 10;
+    /// }
+    ///
+    /// assert_eq!(Some(&12), map.get(&[Arc::new("food"), Arc::new("dessert")]));
+    /// assert_eq!(Some(&3), map.get(&[Arc::new("animal")]));
+    /// ```
+    pub fn iter_subtree_mut(
+        &mut self,
+        key_chain: &[Arc<K>],
+    ) -> Result<SubtreeIterMut<'_, K, V>, LeveledHashMapError<K>> {
+        let order: Vec<(usize, Vec<Arc<K>>)> = self
+            .iter_subtree(key_chain)?
+            .map(|(key_chain, _)| (key_chain.len() - 1, key_chain))
+            .collect();
+
+        let start_level = key_chain.len() - 1;
+
+        let mut wanted: Vec<HashSet<Arc<K>>> = Vec::new();
+
+        for (level, key_chain) in order.iter() {
+            let l = level - start_level;
+
+            if wanted.len() <= l {
+                wanted.push(HashSet::new());
+            }
+
+            wanted[l].insert(Arc::clone(&key_chain[*level]));
+        }
+
+        if let Some(generations) = self.generations.as_mut() {
+            for (l, keys) in wanted.iter().enumerate() {
+                for key in keys {
+                    generations.mark_changed(start_level + l, key);
+                }
+            }
+        }
+
+        // every level is borrowed mutably only once, the root is looked up directly, and only the values of the subtree are picked out of the deeper levels
+        let (root_map, deeper) = self.pool[start_level..].split_first_mut().unwrap();
+
+        let mut values: Vec<HashMap<&Arc<K>, &mut V>> = Vec::with_capacity(wanted.len());
+
+        values.push(
+            root_map
+                .get_mut(&key_chain[start_level])
+                .map(|(_, v)| (&key_chain[start_level], v))
+                .into_iter()
+                .collect(),
+        );
+
+        values.extend(deeper.iter_mut().zip(wanted.iter().skip(1)).map(|(map, keys)| {
+            map.iter_mut().filter(|(k, _)| keys.contains(*k)).map(|(k, (_, v))| (k, v)).collect()
+        }));
+
+        let entries: Vec<(Vec<Arc<K>>, &mut V)> = order
+            .into_iter()
+            .map(|(level, key_chain)| {
+                let v = values[level - start_level].remove(&key_chain[level]).unwrap();
+
+                (key_chain, v)
+            })
+            .collect();
+
+        Ok(SubtreeIterMut {
+            inner: entries.into_iter()
+        })
+    }
+
+    /// Get a breadth-first iterator over all entries. It yields `(key_chain, value)` tuples level by level, so all entries at Level n come before any entry at Level n + 1, and every key chain starts at Level 0.
+    /// ```
+    /// use std::sync::Arc;
+    ///
+    /// use leveled_hash_map::LeveledHashMap;
+    ///
+    /// let mut map: LeveledHashMap<&'static str, u8> = LeveledHashMap::new();
+    ///
+    /// map.insert(&[Arc::new("food")], 1).unwrap();
+    /// map.insert(&[Arc::new("food"), Arc::new("dessert")], 2).unwrap();
+    /// map.insert(&[Arc::new("animal")], 3).unwrap();
+    ///
+    /// let levels: Vec<usize> = map
+    ///     .iter_breadth_first()
+    ///     .map(|(key_chain, _)| key_chain.len())
+    ///     .collect();
+    ///
+    /// assert_eq!(vec![1, 1, 2], levels);
+    /// ```
+    #[inline]
+    pub fn iter_breadth_first(&self) -> BreadthFirstIter<'_, K, V> {
+        BreadthFirstIter {
+            pool:      &self.pool,
+            sub:       &self.sub,
+            queue:     self
+                .pool
+                .first()
+                .map(|m| m.keys().map(|k| (Vec::new(), k)).collect())
+                .unwrap_or_default(),
+            remaining: self.pool.iter().map(|m| m.len()).sum(),
+        }
+    }
+
+    /// Get a breadth-first iterator over an entry and all of its descendants by a key chain which starts at Level 0. It yields `(key_chain, value)` tuples level by level, and every key chain starts at Level 0.
+    /// ```
+    /// use std::sync::Arc;
+    ///
+    /// use leveled_hash_map::LeveledHashMap;
+    ///
+    /// let mut map: LeveledHashMap<&'static str, u8> = LeveledHashMap::new();
+    ///
+    /// map.insert(&[Arc::new("food")], 1).unwrap();
+    /// map.insert(&[Arc::new("food"), Arc::new("dessert")], 2).unwrap();
+    /// map.insert(&[Arc::new("food"), Arc::new("dessert"), Arc::new("cake")], 3)
+    ///     .unwrap();
+    /// map.insert(&[Arc::new("food"), Arc::new("meat")], 4).unwrap();
+    /// map.insert(&[Arc::new("animal")], 5).unwrap();
+    ///
+    /// let values: Vec<u8> = map
+    ///     .iter_breadth_first_from(&[Arc::new("food")])
+    ///     .unwrap()
+    ///     .map(|(_, v)| *v)
+    ///     .collect();
+    ///
+    /// assert_eq!(4, values.len());
+    /// assert_eq!(1, values[0]);
+    /// assert_eq!(3, values[3]);
+    /// ```
+    pub fn iter_breadth_first_from(
+        &self,
+        key_chain: &[Arc<K>],
+    ) -> Result<BreadthFirstIter<'_, K, V>, LeveledHashMapError<K>> {
+        if key_chain.is_empty() {
+            return Err(LeveledHashMapError::KeyChainEmpty);
+        }
+
+        self.get_inner(key_chain, 0)?;
+
+        let level = key_chain.len() - 1;
+
+        let key = self.pool[level].get_key_value(&key_chain[level]).unwrap().0;
+
+        let mut queue = VecDeque::with_capacity(1);
+
+        queue.push_back((self.key_chain_of(level, key)[..level].to_vec(), key));
+
+        Ok(BreadthFirstIter {
+            pool: &self.pool,
+            sub: &self.sub,
+            queue,
+            remaining: self.subtree_len(level, key),
+        })
+    }
+
+    /// Get a pre-order depth-first iterator over the entries without children. It yields `(key_chain, value)` tuples, and every key chain starts at Level 0.
+    /// ```
+    /// use std::sync::Arc;
+    ///
+    /// use leveled_hash_map::LeveledHashMap;
+    ///
+    /// let mut map: LeveledHashMap<&'static str, u8> = LeveledHashMap::new();
+    ///
+    /// map.insert(&[Arc::new("food")], 1).unwrap();
+    /// map.insert(&[Arc::new("food"), Arc::new("dessert")], 2).unwrap();
+    /// map.insert(&[Arc::new("animal")], 3).unwrap();
+    ///
+    /// let mut values: Vec<u8> = map.leaves().map(|(_, v)| *v).collect();
+    ///
+    /// values.sort();
+    ///
+    /// assert_eq!(vec![2, 3], values);
+    /// ```
+    #[inline]
+    pub fn leaves(&self) -> Leaves<'_, K, V> {
+        Leaves {
+            inner: self.iter()
+        }
+    }
+
+    /// Get a pre-order depth-first iterator over the entries without children in the subtree of an entry by a key chain which starts at Level 0. The entry itself is included if it has no children. It yields `(key_chain, value)` tuples, and every key chain starts at Level 0.
+    /// ```
+    /// use std::sync::Arc;
+    ///
+    /// use leveled_hash_map::LeveledHashMap;
+    ///
+    /// let mut map: LeveledHashMap<&'static str, u8> = LeveledHashMap::new();
+    ///
+    /// map.insert(&[Arc::new("food")], 1).unwrap();
+    /// map.insert(&[Arc::new("food"), Arc::new("dessert")], 2).unwrap();
+    /// map.insert(&[Arc::new("animal")], 3).unwrap();
+    ///
+    /// let leaves: Vec<_> = map.leaves_of(&[Arc::new("food")]).unwrap().collect();
+    ///
+    /// assert_eq!(vec![(vec![Arc::new("food"), Arc::new("dessert")], &2)], leaves);
+    /// ```
+    #[inline]
+    pub fn leaves_of(
+        &self,
+        key_chain: &[Arc<K>],
+    ) -> Result<Leaves<'_, K, V>, LeveledHashMapError<K>> {
+        Ok(Leaves {
+            inner: self.iter_subtree(key_chain)?
+        })
+    }
+
+    /// Get a pre-order depth-first iterator over the descendants of an entry by a key chain which starts at Level 0, not including the entry itself. It yields `(depth, key_chain, value)` tuples, where `depth` is 1 for the children of the entry, and every key chain starts at Level 0.
+    /// ```
+    /// use std::sync::Arc;
+    ///
+    /// use leveled_hash_map::LeveledHashMap;
+    ///
+    /// let mut map: LeveledHashMap<&'static str, u8> = LeveledHashMap::new();
+    ///
+    /// map.insert(&[Arc::new("food")], 1).unwrap();
+    /// map.insert(&[Arc::new("food"), Arc::new("dessert")], 2).unwrap();
+    /// map.insert(&[Arc::new("food"), Arc::new("dessert"), Arc::new("cake")], 3)
+    ///     .unwrap();
+    ///
+    /// let descendants: Vec<(usize, u8)> = map
+    ///     .descendants(&[Arc::new("food")])
+    ///     .unwrap()
+    ///     .map(|(depth, _, v)| (depth, *v))
+    ///     .collect();
+    ///
+    /// assert_eq!(vec![(1, 2), (2, 3)], descendants);
+    /// ```
+    pub fn descendants(
+        &self,
+        key_chain: &[Arc<K>],
+    ) -> Result<Descendants<'_, K, V>, LeveledHashMapError<K>> {
+        let mut inner = self.iter_subtree(key_chain)?;
+
+        inner.next();
+
+        Ok(Descendants {
+            inner,
+            level: key_chain.len() - 1,
+        })
+    }
+
+    /// Get an iterator over the siblings of an entry by a key chain which starts at Level 0, which are the other children of its parent, or the other entries at Level 0 if the entry is at Level 0. The siblings are yielded as `(key, value)` pairs in arbitrary order.
+    /// ```
+    /// use std::sync::Arc;
+    ///
+    /// use leveled_hash_map::LeveledHashMap;
+    ///
+    /// let mut map: LeveledHashMap<&'static str, u8> = LeveledHashMap::new();
+    ///
+    /// map.insert(&[Arc::new("food")], 1).unwrap();
+    /// map.insert(&[Arc::new("food"), Arc::new("dessert")], 2).unwrap();
+    /// map.insert(&[Arc::new("food"), Arc::new("meat")], 3).unwrap();
+    /// map.insert(&[Arc::new("animal")], 4).unwrap();
+    ///
+    /// let siblings: Vec<_> = map
+    ///     .siblings(&[Arc::new("food"), Arc::new("dessert")])
+    ///     .unwrap()
+    ///     .collect();
+    ///
+    /// assert_eq!(vec![(Arc::new("meat"), &3)], siblings);
+    ///
+    /// let siblings: Vec<_> = map.siblings(&[Arc::new("food")]).unwrap().collect();
+    ///
+    /// assert_eq!(vec![(Arc::new("animal"), &4)], siblings);
+    /// ```
+    pub fn siblings(
+        &self,
+        key_chain: &[Arc<K>],
+    ) -> Result<Siblings<'_, K, V>, LeveledHashMapError<K>> {
+        self.get_inner(key_chain, 0)?;
+
+        let level = key_chain.len() - 1;
+
+        let keys = if level == 0 {
+            SiblingKeys::Roots(self.sub[0].keys())
+        } else {
+            SiblingKeys::Children(self.sub[level - 1][&key_chain[level - 1]].iter())
+        };
+
+        Ok(Siblings {
+            map: &self.pool[level],
+            keys,
+            key: self.pool[level].get_key_value(&key_chain[level]).unwrap().0,
+        })
+    }
+
+    /// Get an iterator over the ancestors of an entry by a key chain which starts at Level 0. It yields `(level, key, value)` tuples from the parent of the entry up to Level 0, so it is empty for an entry at Level 0.
+    /// ```
+    /// use std::sync::Arc;
+    ///
+    /// use leveled_hash_map::LeveledHashMap;
+    ///
+    /// let mut map: LeveledHashMap<&'static str, u8> = LeveledHashMap::new();
+    ///
+    /// map.insert(&[Arc::new("food")], 1).unwrap();
+    /// map.insert(&[Arc::new("food"), Arc::new("dessert")], 2).unwrap();
+    /// map.insert(&[Arc::new("food"), Arc::new("dessert"), Arc::new("cake")], 3)
+    ///     .unwrap();
+    ///
+    /// let ancestors: Vec<_> = map
+    ///     .ancestors(&[Arc::new("food"), Arc::new("dessert"), Arc::new("cake")])
+    ///     .unwrap()
+    ///     .collect();
+    ///
+    /// assert_eq!(
+    ///     vec![(1, Arc::new("dessert"), &2), (0, Arc::new("food"), &1)],
+    ///     ancestors
+    /// );
+    /// ```
+    pub fn ancestors(
+        &self,
+        key_chain: &[Arc<K>],
+    ) -> Result<Ancestors<'_, K, V>, LeveledHashMapError<K>> {
+        self.get_inner(key_chain, 0)?;
+
+        let level = key_chain.len() - 1;
+
+        let next = self.pool[level][&key_chain[level]].0.as_ref().map(|pk| (level - 1, pk));
+
+        Ok(Ancestors {
+            pool: &self.pool,
+            next,
+        })
+    }
+
+    /// Get an iterator which interleaves the entries of different top-level subtrees in a round-robin way, so that every subtree is covered early instead of being exhausted one by one. Each subtree is walked depth-first, parents before their children. It yields `(key_chain, value)` tuples, and every key chain starts at Level 0.
+    /// ```
+    /// use std::sync::Arc;
+    ///
+    /// use leveled_hash_map::LeveledHashMap;
+    ///
+    /// let mut map: LeveledHashMap<&'static str, u8> = LeveledHashMap::new();
+    ///
+    /// map.insert(&[Arc::new("food")], 1).unwrap();
+    /// map.insert(&[Arc::new("food"), Arc::new("dessert")], 1).unwrap();
+    /// map.insert(&[Arc::new("animal")], 2).unwrap();
+    /// map.insert(&[Arc::new("animal"), Arc::new("dog")], 2).unwrap();
+    ///
+    /// let values: Vec<u8> = map.iter_interleaved().map(|(_, v)| *v).collect();
+    ///
+    /// assert_ne!(values[0], values[1]);
+    /// assert_ne!(values[2], values[3]);
+    /// ```
+    #[inline]
+    pub fn iter_interleaved(&self) -> Interleaved<'_, K, V> {
+        self.iter_interleaved_weighted(|_| 1)
+    }
+
+    /// Get an iterator which interleaves the entries of different top-level subtrees, where each subtree yields as many entries in a row as the weight of its root key, computed by a closure. Weights less than 1 are treated as 1. Each subtree is walked depth-first, parents before their children. It yields `(key_chain, value)` tuples, and every key chain starts at Level 0.
+    /// ```
+    /// use std::sync::Arc;
+    ///
+    /// use leveled_hash_map::LeveledHashMap;
+    ///
+    /// let mut map: LeveledHashMap<&'static str, u8> = LeveledHashMap::new();
+    ///
+    /// map.insert(&[Arc::new("food")], 1).unwrap();
+    /// map.insert(&[Arc::new("food"), Arc::new("dessert")], 1).unwrap();
+    /// map.insert(&[Arc::new("food"), Arc::new("meat")], 1).unwrap();
+    /// map.insert(&[Arc::new("animal")], 2).unwrap();
+    /// map.insert(&[Arc::new("animal"), Arc::new("dog")], 2).unwrap();
+    ///
+    /// let values: Vec<u8> = map
+    ///     .iter_interleaved_weighted(|k| if *k == "food" { 2 } else { 1 })
+    ///     .map(|(_, v)| *v)
+    ///     .collect();
+    ///
+    /// assert_eq!(2, values.iter().take(3).filter(|v| **v == 1).count());
+    /// ```
+    pub fn iter_interleaved_weighted<F: FnMut(&K) -> usize>(
+        &self,
+        mut weight: F,
+    ) -> Interleaved<'_, K, V> {
+        let cursors = match self.pool.first() {
+            Some(map) => map
+                .keys()
+                .map(|k| SubtreeCursor {
+                    path:   Vec::new(),
+                    stack:  vec![(0, k)],
+                    weight: weight(k.as_ref()).max(1),
+                    used:   0,
+                })
+                .collect(),
+            None => VecDeque::new(),
+        };
+
+        Interleaved {
+            pool: &self.pool,
+            sub: &self.sub,
+            cursors,
+            remaining: self.pool.iter().map(|m| m.len()).sum(),
+        }
+    }
+
+    /// Get an iterator over the values of all entries, level by level.
+    /// ```
+    /// use std::sync::Arc;
+    ///
+    /// use leveled_hash_map::LeveledHashMap;
+    ///
+    /// let mut map: LeveledHashMap<&'static str, u32> = LeveledHashMap::new();
+    ///
+    /// map.insert(&[Arc::new("food")], 100).unwrap();
+    /// map.insert(&[Arc::new("food"), Arc::new("dessert")], 200).unwrap();
+    ///
+    /// assert_eq!(300, map.values().sum::<u32>());
+    /// ```
+    #[inline]
+    pub fn values(&self) -> Values<'_, K, V> {
+        Values {
+            levels:    self.pool.iter(),
+            current:   None,
+            remaining: self.pool.iter().map(|m| m.len()).sum(),
+        }
+    }
+
+    /// Get a mutable iterator over the values of all entries, level by level. If generation tracking is enabled, all entries are stamped as changed.
+    /// ```
+    /// use std::sync::Arc;
+    ///
+    /// use leveled_hash_map::LeveledHashMap;
+    ///
+    /// let mut map: LeveledHashMap<&'static str, u32> = LeveledHashMap::new();
+    ///
+    /// map.insert(&[Arc::new("food")], 100).unwrap();
+    /// map.insert(&[Arc::new("food"), Arc::new("dessert")], 200).unwrap();
+    ///
+    /// for v in map.values_mut() {
+    ///     *v *= 2;
+    /// }
+    ///
+    /// assert_eq!(Some(&400), map.get(&[Arc::new("food"), Arc::new("dessert")]));
+    /// ```
+    pub fn values_mut(&mut self) -> ValuesMut<'_, K, V> {
+        self.mark_all_changed();
+
+        let remaining = self.pool.iter().map(|m| m.len()).sum();
+
+        ValuesMut {
+            levels: self.pool.iter_mut(),
+            current: None,
+            remaining,
+        }
+    }
+
+    /// Consume the map and get an iterator over the values of all entries, level by level.
+    /// ```
+    /// use std::sync::Arc;
+    ///
+    /// use leveled_hash_map::LeveledHashMap;
+    ///
+    /// let mut map: LeveledHashMap<&'static str, String> = LeveledHashMap::new();
+    ///
+    /// map.insert(&[Arc::new("food")], "Food".to_string()).unwrap();
+    /// map.insert(&[Arc::new("food"), Arc::new("dessert")], "Dessert".to_string())
+    ///     .unwrap();
+    ///
+    /// let values: Vec<String> = map.into_values().collect();
+    ///
+    /// assert_eq!(vec!["Food".to_string(), "Dessert".to_string()], values);
+    /// ```
+    #[inline]
+    pub fn into_values(self) -> IntoValues<K, V> {
+        let remaining = self.pool.iter().map(|m| m.len()).sum();
+
+        IntoValues {
+            levels: self.pool.into_iter(),
+            current: None,
+            remaining,
+        }
+    }
+
+    /// Enable generation tracking. Every insertion, removal and mutable access bumps the generation of the map and stamps the affected entry, so that `export_since` can find out what has changed. Entries which already exist are stamped as changed at the time of enabling.
+    /// ```
+    /// use std::sync::Arc;
+    ///
+    /// use leveled_hash_map::LeveledHashMap;
+    ///
+    /// let mut map: LeveledHashMap<&'static str, u8> = LeveledHashMap::new();
+    ///
+    /// assert_eq!(None, map.generation());
+    ///
+    /// map.track_generations();
+    ///
+    /// map.insert(&[Arc::new("food")], 1).unwrap();
+    ///
+    /// assert_eq!(Some(1), map.generation());
+    /// ```
+    pub fn track_generations(&mut self) {
+        if self.generations.is_some() {
+            return;
+        }
+
+        let mut generations =
+            Generations {
+                current: 0, changed: Vec::new(), removed: Vec::new(), flushed: None
+            };
+
+        for (level, map) in self.pool.iter().enumerate() {
+            for key in map.keys() {
+                generations.mark_changed(level, key);
+            }
+        }
+
+        self.generations = Some(generations);
+    }
+
+    /// Get the current generation of the map. It returns `None` if generation tracking is not enabled.
+    #[inline]
+    pub fn generation(&self) -> Option<u64> {
+        self.generations.as_ref().map(|g| g.current)
+    }
+
+    /// Export the entries which have been changed or removed after a specific generation, for replicating the map incrementally. It returns `None` if generation tracking is not enabled. The removals are remembered until they are dropped by `prune_generations`.
+    /// ```
+    /// use std::sync::Arc;
+    ///
+    /// use leveled_hash_map::LeveledHashMap;
+    ///
+    /// let mut map: LeveledHashMap<&'static str, u8> = LeveledHashMap::new();
+    ///
+    /// map.track_generations();
+    ///
+    /// map.insert(&[Arc::new("food")], 1).unwrap();
+    /// map.insert(&[Arc::new("animal")], 2).unwrap();
+    ///
+    /// let generation = map.generation().unwrap();
+    ///
+    /// map.insert(&[Arc::new("food"), Arc::new("dessert")], 3).unwrap();
+    /// map.remove(&[Arc::new("animal")]).unwrap();
+    ///
+    /// let export = map.export_since(generation).unwrap();
+    ///
+    /// assert_eq!(map.generation().unwrap(), export.generation);
+    /// assert_eq!(vec![(0, Arc::new("animal"))], export.removed);
+    /// assert_eq!(
+    ///     vec![(vec![Arc::new("food"), Arc::new("dessert")], &3)],
+    ///     export.changed
+    /// );
+    /// ```
+    pub fn export_since(&self, generation: u64) -> Option<IncrementalExport<'_, K, V>> {
+        let generations = self.generations.as_ref()?;
+
+        let mut removed = Vec::new();
+        let mut changed = Vec::new();
+
+        for (level, map) in generations.removed.iter().enumerate() {
+            for (key, g) in map {
+                if *g > generation {
+                    removed.push((level, Arc::clone(key)));
+                }
+            }
+        }
+
+        for (level, map) in generations.changed.iter().enumerate() {
+            for (key, g) in map {
+                if *g > generation {
+                    let value = &self.pool[level][key].1;
+
+                    changed.push((self.key_chain_of(level, key), value));
+                }
+            }
+        }
+
+        Some(IncrementalExport {
+            generation: generations.current,
+            removed,
+            changed,
+        })
+    }
+
+    /// Drop the stamps of the entries which were removed at or before a specific generation, and get the number of dropped stamps. The stamps of removed entries are otherwise kept forever, so call this with the oldest generation which any consumer of `export_since` or `flush_dirty` has caught up with. Afterwards, exporting since an older generation no longer reports those removals.
+    /// ```
+    /// use std::sync::Arc;
+    ///
+    /// use leveled_hash_map::LeveledHashMap;
+    ///
+    /// let mut map: LeveledHashMap<&'static str, u8> = LeveledHashMap::new();
+    ///
+    /// map.track_generations();
+    ///
+    /// map.insert(&[Arc::new("food")], 1).unwrap();
+    /// map.insert(&[Arc::new("animal")], 2).unwrap();
+    /// map.remove(&[Arc::new("animal")]).unwrap();
+    ///
+    /// let generation = map.export_since(0).unwrap().generation;
+    ///
+    /// map.remove(&[Arc::new("food")]).unwrap();
+    ///
+    /// assert_eq!(1, map.prune_generations(generation));
+    ///
+    /// assert_eq!(
+    ///     vec![(0, Arc::new("food"))],
+    ///     map.export_since(generation).unwrap().removed
+    /// );
+    /// assert_eq!(
+    ///     vec![(0, Arc::new("food"))],
+    ///     map.export_since(0).unwrap().removed
+    /// );
+    /// ```
+    pub fn prune_generations(&mut self, generation: u64) -> usize {
+        let generations = match self.generations.as_mut() {
+            Some(generations) => generations,
+            None => return 0,
+        };
+
+        let mut count = 0;
+
+        for map in generations.removed.iter_mut() {
+            let len = map.len();
+
+            map.retain(|_, g| *g > generation);
+
+            count += len - map.len();
+        }
+
+        count
+    }
+
+    /// Check whether a key is not reserved at a level.
+    fn check_reserved(&self, level: usize, key: &K) -> Result<(), LeveledHashMapError<K>> {
+        if let Some(k) = self.reserved.get(level).and_then(|r| r.get(key)) {
+            return Err(LeveledHashMapError::KeyReserved {
+                level,
+                key: Arc::clone(k),
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Check whether the values of a batch are allowed at a level. The batch is given back if they are.
+    fn check_value_types(
+        &self,
+        level: usize,
+        values: HashMap<K, V>,
+    ) -> Result<HashMap<K, V>, LeveledHashMapError<K>> {
+        if values.values().all(|v| self.is_value_allowed(level, v)) {
+            return Ok(values);
+        }
+
+        let (key, _) = values.into_iter().find(|(_, v)| !self.is_value_allowed(level, v)).unwrap();
+
+        Err(LeveledHashMapError::ValueNotAllowed {
+            level,
+            key: Arc::new(key),
+        })
+    }
+
+    /// Check whether the value of an existing entry is allowed to be overwritten under the overwrite policy.
+    fn check_overwrite(&self, level: usize, key: &K) -> Result<(), LeveledHashMapError<K>> {
+        if self.overwrite_policy == OverwritePolicy::Error {
+            if let Some((k, children)) = self.sub.get(level).and_then(|m| m.get_key_value(key)) {
+                if !children.is_empty() {
+                    return Err(LeveledHashMapError::KeyHasChildren {
+                        level,
+                        key: Arc::clone(k),
+                    });
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Apply the overwrite policy to an existing entry whose value is about to be overwritten.
+    fn apply_overwrite_policy(&mut self, level: usize, key: &K) {
+        if self.overwrite_policy == OverwritePolicy::ClearSubtree {
+            #[cfg(feature = "log")]
+            let slow_op = match self.sub.get(level).and_then(|m| m.get_key_value(key)) {
+                Some((key, _)) => self
+                    .slow_op_key_chain(level, key)
+                    .map(|key_chain| (key_chain, self.subtree_len(level, key) - 1)),
+                None => None,
+            };
+
+            if let Some(children) = self.sub.get_mut(level).and_then(|m| m.get_mut(key)) {
+                for c in mem::take(children) {
+                    self.remove_node(level + 1, &c);
+                }
+            }
+
+            #[cfg(feature = "log")]
+            if let Some((key_chain, count)) = slow_op {
+                self.report_slow_op("overwrite", Some(key_chain), count);
+            }
+        }
+    }
+
+    /// Count an entry and all of its descendants.
+    fn subtree_len(&self, level: usize, key: &Arc<K>) -> usize {
+        let mut count = 0;
+        let mut stack = vec![(level, key)];
+
+        while let Some((level, key)) = stack.pop() {
+            count += 1;
+
+            stack.extend(self.sub[level][key].iter().map(|c| (level + 1, c)));
+        }
+
+        count
+    }
+
+    /// Stamp all entries as changed if generation tracking is enabled, before handing out mutable access to every value.
+    fn mark_all_changed(&mut self) {
+        if let Some(generations) = self.generations.as_mut() {
+            for (level, map) in self.pool.iter().enumerate() {
+                for key in map.keys() {
+                    generations.mark_changed(level, key);
+                }
+            }
+        }
+    }
+
+    /// Count how many keys at the start of a key chain which starts at Level 0 exist in the map with the right parents.
+    fn existing_prefix_len(&self, key_chain: &[Arc<K>]) -> usize {
+        let mut parent: Option<&Arc<K>> = None;
+
+        for (level, key) in key_chain.iter().enumerate() {
+            match self.pool.get(level).and_then(|map| map.get(key)) {
+                Some((pk, _)) if pk.as_ref() == parent => parent = Some(key),
+                _ => return level,
+            }
+        }
+
+        key_chain.len()
+    }
+
+    /// Rebuild the full key chain of an existing entry by following its parent keys.
+    fn key_chain_of(&self, level: usize, key: &Arc<K>) -> Vec<Arc<K>> {
+        let mut key_chain = Vec::with_capacity(level + 1);
+
+        key_chain.push(Arc::clone(key));
+
+        let mut parent = self.pool[level][key].0.as_ref();
+
+        for l in (0..level).rev() {
+            let pk = parent.unwrap();
+
+            key_chain.push(Arc::clone(pk));
+
+            parent = self.pool[l][pk].0.as_ref();
+        }
+
+        key_chain.reverse();
+
+        key_chain
+    }
+}
+
+impl<K: Eq + Hash, V> IntoIterator for LeveledHashMap<K, V> {
+    type IntoIter = IntoIter<K, V>;
+    type Item = (Vec<Arc<K>>, V);
+
+    /// Consume the map and get an iterator over all entries. It yields `(key_chain, value)` tuples level by level, where every key chain starts at Level 0, so parents are always yielded before their children.
+    /// ```
+    /// use std::sync::Arc;
+    ///
+    /// use leveled_hash_map::LeveledHashMap;
+    ///
+    /// let mut map: LeveledHashMap<&'static str, String> = LeveledHashMap::new();
+    ///
+    /// map.insert(&[Arc::new("food")], "Food".to_string()).unwrap();
+    /// map.insert(&[Arc::new("food"), Arc::new("dessert")], "Dessert".to_string())
+    ///     .unwrap();
+    ///
+    /// let mut iter = map.into_iter();
+    ///
+    /// assert_eq!(Some((vec![Arc::new("food")], "Food".to_string())), iter.next());
+    /// assert_eq!(
+    ///     Some((
+    ///         vec![Arc::new("food"), Arc::new("dessert")],
+    ///         "Dessert".to_string()
+    ///     )),
+    ///     iter.next()
+    /// );
+    /// assert_eq!(None, iter.next());
+    /// ```
+    #[inline]
+    fn into_iter(self) -> IntoIter<K, V> {
+        let remaining = self.pool.iter().map(|m| m.len()).sum();
+
+        IntoIter {
+            levels: self.pool.into_iter(),
+            current: None,
+            parents: HashMap::new(),
+            chains: HashMap::new(),
+            remaining,
+        }
+    }
+}
+
+impl<'a, K: Eq + Hash, V> IntoIterator for &'a LeveledHashMap<K, V> {
+    type IntoIter = Iter<'a, K, V>;
+    type Item = (Vec<Arc<K>>, &'a V);
+
+    #[inline]
+    fn into_iter(self) -> Iter<'a, K, V> {
+        self.iter()
+    }
+}
+
+impl<'a, K: Eq + Hash, V> IntoIterator for &'a mut LeveledHashMap<K, V> {
+    type IntoIter = IterMut<'a, K, V>;
+    type Item = (Vec<Arc<K>>, &'a mut V);
+
+    #[inline]
+    fn into_iter(self) -> IterMut<'a, K, V> {
+        self.iter_mut()
+    }
+}
+
+impl<K: Eq + Hash, V> Extend<(Vec<Arc<K>>, V)> for LeveledHashMap<K, V> {
+    /// Insert `(key_chain, value)` tuples, where every key chain starts at Level 0. The tuples are inserted from the shortest key chain to the longest one, so parents can come after their children. Use `try_extend` to get the error instead of panicking.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a key chain cannot be inserted, e.g. its parent does not exist. The whole batch is validated first, so the map stays unchanged in that case.
+    ///
+    /// ```
+    /// use std::sync::Arc;
+    ///
+    /// use leveled_hash_map::LeveledHashMap;
+    ///
+    /// let mut map: LeveledHashMap<&'static str, u8> = LeveledHashMap::new();
+    ///
+    /// map.insert(&[Arc::new("food")], 1).unwrap();
+    /// map.insert(&[Arc::new("food"), Arc::new("dessert")], 2).unwrap();
+    ///
+    /// let mut other: LeveledHashMap<&'static str, u8> = LeveledHashMap::new();
+    ///
+    /// other.extend(vec![
+    ///     (vec![Arc::new("animal"), Arc::new("dog")], 4),
+    ///     (vec![Arc::new("animal")], 3),
+    /// ]);
+    /// other.extend(map);
+    ///
+    /// assert_eq!(Some(&2), other.get(&[Arc::new("food"), Arc::new("dessert")]));
+    /// assert_eq!(Some(&4), other.get(&[Arc::new("animal"), Arc::new("dog")]));
+    /// ```
+    fn extend<I: IntoIterator<Item = (Vec<Arc<K>>, V)>>(&mut self, iter: I) {
+        if let Err(err) = self.try_extend(iter) {
+            panic!("{}", err);
+        }
+    }
+}
+
+impl<'a, K: Eq + Hash, V> Index<&'a [Arc<K>]> for LeveledHashMap<K, V> {
+    type Output = V;
+
+    /// Get a value by a key chain which starts at Level 0.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the entry does not exist.
+    ///
+    /// ```
+    /// use std::sync::Arc;
+    ///
+    /// use leveled_hash_map::LeveledHashMap;
+    ///
+    /// let mut map: LeveledHashMap<&'static str, u8> = LeveledHashMap::new();
+    ///
+    /// map.insert(&[Arc::new("food")], 1).unwrap();
+    ///
+    /// let key_chain = vec![Arc::new("food")];
+    ///
+    /// assert_eq!(1, map[key_chain.as_slice()]);
+    /// assert_eq!(1, map[&key_chain]);
+    /// ```
+    #[inline]
+    fn index(&self, key_chain: &'a [Arc<K>]) -> &V {
+        match self.try_get(key_chain, 0) {
+            Ok(value) => value,
+            Err(err) => panic!("{}", err),
+        }
+    }
+}
+
+impl<'a, K: Eq + Hash, V> IndexMut<&'a [Arc<K>]> for LeveledHashMap<K, V> {
+    /// Get a mutable value by a key chain which starts at Level 0.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the entry does not exist.
+    ///
+    /// ```
+    /// use std::sync::Arc;
+    ///
+    /// use leveled_hash_map::LeveledHashMap;
+    ///
+    /// let mut map: LeveledHashMap<&'static str, u8> = LeveledHashMap::new();
+    ///
+    /// map.insert(&[Arc::new("food")], 1).unwrap();
+    ///
+    /// let key_chain = vec![Arc::new("food")];
+    ///
+    /// map[&key_chain] += 1;
+    ///
+    /// assert_eq!(2, map[&key_chain]);
+    /// ```
+    #[inline]
+    fn index_mut(&mut self, key_chain: &'a [Arc<K>]) -> &mut V {
+        match self.try_get_mut(key_chain, 0) {
+            Ok(value) => value,
+            Err(err) => panic!("{}", err),
+        }
+    }
+}
+
+impl<'a, K: Eq + Hash, V> Index<&'a Vec<Arc<K>>> for LeveledHashMap<K, V> {
+    type Output = V;
+
+    #[inline]
+    fn index(&self, key_chain: &'a Vec<Arc<K>>) -> &V {
+        &self[key_chain.as_slice()]
+    }
+}
+
+impl<'a, K: Eq + Hash, V> IndexMut<&'a Vec<Arc<K>>> for LeveledHashMap<K, V> {
+    #[inline]
+    fn index_mut(&mut self, key_chain: &'a Vec<Arc<K>>) -> &mut V {
+        &mut self[key_chain.as_slice()]
+    }
+}
+
+impl<'a, K: Eq + Hash, V> Index<&'a KeyChain<K>> for LeveledHashMap<K, V> {
+    type Output = V;
+
+    #[inline]
+    fn index(&self, key_chain: &'a KeyChain<K>) -> &V {
+        &self[key_chain.as_slice()]
+    }
+}
+
+impl<'a, K: Eq + Hash, V> IndexMut<&'a KeyChain<K>> for LeveledHashMap<K, V> {
+    #[inline]
+    fn index_mut(&mut self, key_chain: &'a KeyChain<K>) -> &mut V {
+        &mut self[key_chain.as_slice()]
+    }
 }
 
 impl<K: Eq + Hash, V> Default for LeveledHashMap<K, V> {
@@ -0,0 +1,96 @@
+use std::{hash::Hash, sync::Arc};
+
+use crate::{LeveledHashMap, LeveledHashMapError};
+
+/// Entry count limits of a level of a `LeveledHashMap`, which are enforced by the insertion methods.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct LevelLimits {
+    /// The maximum number of entries at the level.
+    pub max_entries:  Option<usize>,
+    /// The maximum number of children which each entry at the level may have.
+    pub max_children: Option<usize>,
+}
+
+impl<K: Eq + Hash, V> LeveledHashMap<K, V> {
+    /// Set the entry count limits of a specific level. Once set, the insertion methods refuse to add new entries beyond the limits with a `LeveledHashMapError::LevelFull` or `LeveledHashMapError::TooManyChildren` error. Overwriting existing entries is always allowed, and existing entries are not removed even if they are already beyond the limits.
+    /// ```
+    /// use std::sync::Arc;
+    ///
+    /// use leveled_hash_map::{LevelLimits, LeveledHashMap};
+    ///
+    /// let mut map: LeveledHashMap<&'static str, u8> = LeveledHashMap::new();
+    ///
+    /// map.set_level_limits(0, LevelLimits {
+    ///     max_entries:  Some(1),
+    ///     max_children: Some(2),
+    /// });
+    ///
+    /// map.insert(&[Arc::new("food")], 1).unwrap();
+    /// map.insert(&[Arc::new("food"), Arc::new("dessert")], 2).unwrap();
+    /// map.insert(&[Arc::new("food"), Arc::new("meat")], 3).unwrap();
+    ///
+    /// assert!(map.insert(&[Arc::new("food"), Arc::new("fruit")], 4).is_err());
+    /// assert!(map.insert(&[Arc::new("animal")], 5).is_err());
+    ///
+    /// // overwriting is fine
+    /// map.insert(&[Arc::new("food")], 6).unwrap();
+    /// ```
+    pub fn set_level_limits(&mut self, level: usize, limits: LevelLimits) {
+        while self.limits.len() <= level {
+            self.limits.push(LevelLimits::default());
+        }
+
+        self.limits[level] = limits;
+    }
+
+    /// Get the entry count limits of a specific level.
+    #[inline]
+    pub fn level_limits(&self, level: usize) -> LevelLimits {
+        self.limits.get(level).copied().unwrap_or_default()
+    }
+
+    /// Remove the entry count limits of all levels.
+    #[inline]
+    pub fn clear_limits(&mut self) {
+        self.limits.clear();
+    }
+
+    /// Check whether a number of new entries can be added to a level under a parent without exceeding the limits.
+    pub(crate) fn check_limits(
+        &self,
+        level: usize,
+        parent_key: Option<&Arc<K>>,
+        additional: usize,
+    ) -> Result<(), LeveledHashMapError<K>> {
+        if additional == 0 {
+            return Ok(());
+        }
+
+        if let Some(limit) = self.level_limits(level).max_entries {
+            if self.pool.get(level).map(|m| m.len()).unwrap_or(0) + additional > limit {
+                return Err(LeveledHashMapError::LevelFull {
+                    level,
+                    limit,
+                });
+            }
+        }
+
+        if let Some(parent_key) = parent_key {
+            let parent_level = level - 1;
+
+            if let Some(limit) = self.level_limits(parent_level).max_children {
+                if let Some((pk, children)) = self.sub[parent_level].get_key_value(parent_key) {
+                    if children.len() + additional > limit {
+                        return Err(LeveledHashMapError::TooManyChildren {
+                            level: parent_level,
+                            key: Arc::clone(pk),
+                            limit,
+                        });
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,79 @@
+use std::{hash::Hash, sync::Arc};
+
+use crate::{LeveledHashMap, LeveledHashMapError};
+
+/// A value along with its metadata, such as timestamps, sources or flags. Use `LeveledHashMap<K, WithMeta<V, M>>` to enable the metadata APIs.
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct WithMeta<V, M> {
+    /// The value.
+    pub value: V,
+    /// The metadata of the value.
+    pub meta:  M,
+}
+
+impl<K: Eq + Hash, V, M> LeveledHashMap<K, WithMeta<V, M>> {
+    /// Insert a value with its metadata by a key chain. It returns a `Err(LeveledHashMapError)` instance to describe the reason of the inserting failure.
+    /// ```
+    /// use std::sync::Arc;
+    ///
+    /// use leveled_hash_map::{LeveledHashMap, WithMeta};
+    ///
+    /// let mut map: LeveledHashMap<&'static str, WithMeta<String, u32>> =
+    ///     LeveledHashMap::new();
+    ///
+    /// map.insert_with_meta(&[Arc::new("food")], "食物".to_string(), 0).unwrap();
+    ///
+    /// assert_eq!(
+    ///     Some((&"食物".to_string(), &0)),
+    ///     map.get_with_meta(&[Arc::new("food")])
+    /// );
+    /// ```
+    #[inline]
+    pub fn insert_with_meta(
+        &mut self,
+        key_chain: &[Arc<K>],
+        value: V,
+        meta: M,
+    ) -> Result<Option<WithMeta<V, M>>, LeveledHashMapError<K>> {
+        self.insert(key_chain, WithMeta {
+            value,
+            meta,
+        })
+    }
+
+    /// Get a value and its metadata by a key chain. The key chain starts at Level 0.
+    #[inline]
+    pub fn get_with_meta(&self, key_chain: &[Arc<K>]) -> Option<(&V, &M)> {
+        self.get(key_chain).map(|v| (&v.value, &v.meta))
+    }
+
+    /// Update the metadata of an entry by a key chain without touching its value. The key chain starts at Level 0. It returns a `Err(LeveledHashMapError)` instance to describe the reason of the getting failure.
+    /// ```
+    /// use std::sync::Arc;
+    ///
+    /// use leveled_hash_map::{LeveledHashMap, WithMeta};
+    ///
+    /// let mut map: LeveledHashMap<&'static str, WithMeta<String, u32>> =
+    ///     LeveledHashMap::new();
+    ///
+    /// map.insert_with_meta(&[Arc::new("food")], "食物".to_string(), 0).unwrap();
+    ///
+    /// // count the revisions
+    /// map.touch(&[Arc::new("food")], |revision| *revision += 1).unwrap();
+    ///
+    /// assert_eq!(&1, map.get_with_meta(&[Arc::new("food")]).unwrap().1);
+    /// ```
+    #[inline]
+    pub fn touch<F: FnOnce(&mut M)>(
+        &mut self,
+        key_chain: &[Arc<K>],
+        f: F,
+    ) -> Result<(), LeveledHashMapError<K>> {
+        let (_, v) = self.get_professional_mut(key_chain, 0)?;
+
+        f(&mut v.meta);
+
+        Ok(())
+    }
+}
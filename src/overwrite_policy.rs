@@ -0,0 +1,54 @@
+use std::hash::Hash;
+
+use crate::LeveledHashMap;
+
+/// Policies of a `LeveledHashMap` for overwriting the value of an entry which has children.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum OverwritePolicy {
+    /// Keep the descendants of the entry. This is the default policy.
+    KeepSubtree,
+    /// Remove the descendants of the entry.
+    ClearSubtree,
+    /// Refuse to overwrite the value and return a `LeveledHashMapError::KeyHasChildren` error.
+    Error,
+}
+
+impl Default for OverwritePolicy {
+    #[inline]
+    fn default() -> Self {
+        OverwritePolicy::KeepSubtree
+    }
+}
+
+impl<K: Eq + Hash, V> LeveledHashMap<K, V> {
+    /// Get the policy for overwriting the value of an entry which has children.
+    #[inline]
+    pub fn overwrite_policy(&self) -> OverwritePolicy {
+        self.overwrite_policy
+    }
+
+    /// Set the policy for overwriting the value of an entry which has children. It affects `insert`, `insert_many` and `insert_child`.
+    /// ```
+    /// use std::sync::Arc;
+    ///
+    /// use leveled_hash_map::{LeveledHashMap, OverwritePolicy};
+    ///
+    /// let mut map: LeveledHashMap<&'static str, u8> = LeveledHashMap::new();
+    ///
+    /// map.insert(&[Arc::new("food")], 1).unwrap();
+    /// map.insert(&[Arc::new("food"), Arc::new("dessert")], 2).unwrap();
+    ///
+    /// map.set_overwrite_policy(OverwritePolicy::Error);
+    ///
+    /// assert!(map.insert(&[Arc::new("food")], 10).is_err());
+    ///
+    /// map.set_overwrite_policy(OverwritePolicy::ClearSubtree);
+    ///
+    /// assert_eq!(Some(1), map.insert(&[Arc::new("food")], 10).unwrap());
+    /// assert_eq!(None, map.get(&[Arc::new("food"), Arc::new("dessert")]));
+    /// ```
+    #[inline]
+    pub fn set_overwrite_policy(&mut self, policy: OverwritePolicy) {
+        self.overwrite_policy = policy;
+    }
+}
@@ -0,0 +1,137 @@
+use std::{hash::Hash, sync::Arc};
+
+use rayon::{
+    iter::{
+        plumbing::{Consumer, ProducerCallback, UnindexedConsumer},
+        IndexedParallelIterator, IntoParallelIterator, ParallelIterator,
+    },
+    vec,
+};
+
+use crate::LeveledHashMap;
+
+/// A parallel iterator over the entries at a specific level of a `LeveledHashMap`, created by `LeveledHashMap::par_iter_level`. It yields `(key, parent_key, value)` tuples in arbitrary order, and is an `IndexedParallelIterator`, so it can be split evenly and zipped.
+#[derive(Debug)]
+pub struct ParLevelIter<'a, K, V> {
+    inner: vec::IntoIter<(&'a Arc<K>, Option<&'a Arc<K>>, &'a V)>,
+}
+
+/// A parallel mutable iterator over the entries at a specific level of a `LeveledHashMap`, created by `LeveledHashMap::par_iter_level_mut`. It yields `(key, parent_key, value)` tuples in arbitrary order, and is an `IndexedParallelIterator`, so it can be split evenly and zipped.
+#[derive(Debug)]
+pub struct ParLevelIterMut<'a, K, V> {
+    inner: vec::IntoIter<(&'a Arc<K>, Option<&'a Arc<K>>, &'a mut V)>,
+}
+
+macro_rules! impl_parallel_iterator {
+    ($name:ident, $item:ty, $bound:ident) => {
+        impl<'a, K: Send + Sync, V: $bound> ParallelIterator for $name<'a, K, V> {
+            type Item = $item;
+
+            #[inline]
+            fn drive_unindexed<C: UnindexedConsumer<Self::Item>>(self, consumer: C) -> C::Result {
+                self.inner.drive_unindexed(consumer)
+            }
+
+            #[inline]
+            fn opt_len(&self) -> Option<usize> {
+                Some(self.inner.len())
+            }
+        }
+
+        impl<'a, K: Send + Sync, V: $bound> IndexedParallelIterator for $name<'a, K, V> {
+            #[inline]
+            fn len(&self) -> usize {
+                self.inner.len()
+            }
+
+            #[inline]
+            fn drive<C: Consumer<Self::Item>>(self, consumer: C) -> C::Result {
+                self.inner.drive(consumer)
+            }
+
+            #[inline]
+            fn with_producer<CB: ProducerCallback<Self::Item>>(self, callback: CB) -> CB::Output {
+                self.inner.with_producer(callback)
+            }
+        }
+    };
+}
+
+impl_parallel_iterator!(ParLevelIter, (&'a Arc<K>, Option<&'a Arc<K>>, &'a V), Sync);
+impl_parallel_iterator!(ParLevelIterMut, (&'a Arc<K>, Option<&'a Arc<K>>, &'a mut V), Send);
+
+impl<K: Eq + Hash, V> LeveledHashMap<K, V> {
+    /// Get a parallel iterator over the entries at a specific level. The references to the entries are collected first, so the level can be split into even shards instead of going through `par_bridge`.
+    /// ```
+    /// use std::sync::Arc;
+    ///
+    /// use leveled_hash_map::LeveledHashMap;
+    /// use rayon::prelude::*;
+    ///
+    /// let mut map: LeveledHashMap<&'static str, u32> = LeveledHashMap::new();
+    ///
+    /// map.insert(&[Arc::new("food")], 1).unwrap();
+    /// map.insert(&[Arc::new("food"), Arc::new("dessert")], 2).unwrap();
+    /// map.insert(&[Arc::new("food"), Arc::new("meat")], 3).unwrap();
+    ///
+    /// let iter = map.par_iter_level(1).unwrap();
+    ///
+    /// assert_eq!(2, iter.len());
+    /// assert_eq!(5, iter.map(|(_key, _parent_key, value)| *value).sum::<u32>());
+    ///
+    /// assert!(map.par_iter_level(2).is_none());
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "rayon")))]
+    pub fn par_iter_level(&self, level: usize) -> Option<ParLevelIter<'_, K, V>>
+    where
+        K: Send + Sync,
+        V: Sync, {
+        let map = self.pool.get(level)?;
+
+        let entries: Vec<_> = map.iter().map(|(k, (pk, v))| (k, pk.as_ref(), v)).collect();
+
+        Some(ParLevelIter {
+            inner: entries.into_par_iter()
+        })
+    }
+
+    /// Get a parallel mutable iterator over the entries at a specific level. The references to the entries are collected first, so the level can be split into even shards instead of going through `par_bridge`. If generation tracking is enabled, all entries at the level are stamped as changed.
+    /// ```
+    /// use std::sync::Arc;
+    ///
+    /// use leveled_hash_map::LeveledHashMap;
+    /// use rayon::prelude::*;
+    ///
+    /// let mut map: LeveledHashMap<&'static str, u32> = LeveledHashMap::new();
+    ///
+    /// map.insert(&[Arc::new("food")], 1).unwrap();
+    /// map.insert(&[Arc::new("food"), Arc::new("dessert")], 2).unwrap();
+    /// map.insert(&[Arc::new("food"), Arc::new("meat")], 3).unwrap();
+    ///
+    /// map.par_iter_level_mut(1)
+    ///     .unwrap()
+    ///     .for_each(|(_key, _parent_key, value)| *value *= 10);
+    ///
+    /// assert_eq!(Some(&20), map.get(&[Arc::new("food"), Arc::new("dessert")]));
+    /// assert_eq!(Some(&30), map.get(&[Arc::new("food"), Arc::new("meat")]));
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "rayon")))]
+    pub fn par_iter_level_mut(&mut self, level: usize) -> Option<ParLevelIterMut<'_, K, V>>
+    where
+        K: Send + Sync,
+        V: Send, {
+        let map = self.pool.get_mut(level)?;
+
+        if let Some(generations) = self.generations.as_mut() {
+            for key in map.keys() {
+                generations.mark_changed(level, key);
+            }
+        }
+
+        let entries: Vec<_> = map.iter_mut().map(|(k, (pk, v))| (k, pk.as_ref(), v)).collect();
+
+        Some(ParLevelIterMut {
+            inner: entries.into_par_iter()
+        })
+    }
+}
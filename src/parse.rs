@@ -0,0 +1,189 @@
+use std::{
+    error::Error,
+    fmt::{self, Display, Formatter},
+    sync::Arc,
+};
+
+/// The limits used by `parse_chain` for parsing untrusted path strings.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChainLimits {
+    /// The separator between keys. The default value is `'/'`.
+    pub separator:                char,
+    /// The max number of keys. The default value is `32`.
+    pub max_segments:             usize,
+    /// The max length of a key in bytes. The default value is `255`.
+    pub max_segment_len:          usize,
+    /// Characters which are not allowed in keys. The default value is empty.
+    pub disallowed_characters:    Vec<char>,
+    /// Whether control characters are allowed in keys. The default value is `false`.
+    pub allow_control_characters: bool,
+}
+
+impl Default for ChainLimits {
+    #[inline]
+    fn default() -> Self {
+        ChainLimits {
+            separator:                '/',
+            max_segments:             32,
+            max_segment_len:          255,
+            disallowed_characters:    Vec::new(),
+            allow_control_characters: false,
+        }
+    }
+}
+
+/// Possible errors come from `parse_chain`. Segment indices start from 0 and positions are byte offsets in the whole string.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseChainError {
+    /// The string is empty.
+    Empty,
+    /// There are more keys than allowed.
+    TooManySegments { max: usize },
+    /// A key is empty, e.g. the string starts or ends with the separator, or has two separators in a row.
+    EmptySegment { segment: usize, position: usize },
+    /// A key is longer than allowed.
+    SegmentTooLong { segment: usize, position: usize, max: usize },
+    /// A key contains a character which is not allowed.
+    DisallowedCharacter { segment: usize, position: usize, character: char },
+}
+
+impl Display for ParseChainError {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter) -> Result<(), fmt::Error> {
+        match self {
+            ParseChainError::Empty => f.write_str("The path is empty."),
+            ParseChainError::TooManySegments {
+                max,
+            } => f.write_fmt(format_args!("The path has more than {} keys.", max)),
+            ParseChainError::EmptySegment {
+                segment,
+                position,
+            } => f.write_fmt(format_args!("Key {} at byte {} is empty.", segment, position)),
+            ParseChainError::SegmentTooLong {
+                segment,
+                position,
+                max,
+            } => f.write_fmt(format_args!(
+                "Key {} at byte {} is longer than {} bytes.",
+                segment, position, max
+            )),
+            ParseChainError::DisallowedCharacter {
+                segment,
+                position,
+                character,
+            } => f.write_fmt(format_args!(
+                "Key {} contains a disallowed character {:?} at byte {}.",
+                segment, character, position
+            )),
+        }
+    }
+}
+
+impl Error for ParseChainError {}
+
+/// Parse an untrusted path string such as `"food/dessert"` into a key chain. The whole string is validated against the limits before anything is allocated, and the validation stops at the first violation, so a huge input cannot cause a huge allocation.
+/// ```
+/// use std::sync::Arc;
+///
+/// use leveled_hash_map::{parse_chain, ChainLimits, ParseChainError};
+///
+/// let limits = ChainLimits {
+///     max_segments: 2,
+///     ..ChainLimits::default()
+/// };
+///
+/// assert_eq!(
+///     vec![Arc::new("food".to_string()), Arc::new("dessert".to_string())],
+///     parse_chain("food/dessert", &limits).unwrap()
+/// );
+///
+/// assert_eq!(
+///     Err(ParseChainError::TooManySegments {
+///         max: 2
+///     }),
+///     parse_chain("food/dessert/cake", &limits)
+/// );
+///
+/// assert_eq!(
+///     Err(ParseChainError::EmptySegment {
+///         segment: 1, position: 5
+///     }),
+///     parse_chain("food//dessert", &limits)
+/// );
+///
+/// assert_eq!(
+///     Err(ParseChainError::DisallowedCharacter {
+///         segment:   0,
+///         position:  4,
+///         character: '\n',
+///     }),
+///     parse_chain("food\n", &limits)
+/// );
+/// ```
+pub fn parse_chain(s: &str, limits: &ChainLimits) -> Result<Vec<Arc<String>>, ParseChainError> {
+    if s.is_empty() {
+        return Err(ParseChainError::Empty);
+    }
+
+    let mut segments = 1;
+    let mut segment_start = 0;
+
+    for (position, c) in s.char_indices() {
+        if c == limits.separator {
+            if position == segment_start {
+                return Err(ParseChainError::EmptySegment {
+                    segment: segments - 1,
+                    position,
+                });
+            }
+
+            if segments == limits.max_segments {
+                return Err(ParseChainError::TooManySegments {
+                    max: limits.max_segments
+                });
+            }
+
+            segments += 1;
+            segment_start = position + c.len_utf8();
+
+            continue;
+        }
+
+        if (c.is_control() && !limits.allow_control_characters)
+            || limits.disallowed_characters.contains(&c)
+        {
+            return Err(ParseChainError::DisallowedCharacter {
+                segment: segments - 1,
+                position,
+                character: c,
+            });
+        }
+
+        if position + c.len_utf8() - segment_start > limits.max_segment_len {
+            return Err(ParseChainError::SegmentTooLong {
+                segment:  segments - 1,
+                position: segment_start,
+                max:      limits.max_segment_len,
+            });
+        }
+    }
+
+    if segment_start == s.len() {
+        return Err(ParseChainError::EmptySegment {
+            segment:  segments - 1,
+            position: segment_start,
+        });
+    }
+
+    if limits.max_segments == 0 {
+        return Err(ParseChainError::TooManySegments {
+            max: 0
+        });
+    }
+
+    let mut key_chain = Vec::with_capacity(segments);
+
+    key_chain.extend(s.split(limits.separator).map(|k| Arc::new(k.to_string())));
+
+    Ok(key_chain)
+}
@@ -0,0 +1,338 @@
+use std::{
+    any::{type_name, Any, TypeId},
+    error::Error,
+    fmt::{self, Debug, Display, Formatter},
+    hash::Hash,
+    sync::Arc,
+};
+
+use crate::{LeveledHashMap, LeveledHashMapError};
+
+/// A typed view of a value of type `V`, usually a variant of an enum. Implement it for the per-level types to use `LeveledHashMap::get_as`.
+/// ```
+/// use leveled_hash_map::Projection;
+///
+/// pub struct Country {
+///     pub name: String,
+/// }
+///
+/// pub enum Place {
+///     Country(Country),
+///     City(String),
+/// }
+///
+/// impl Projection<Place> for Country {
+///     fn project(value: &Place) -> Option<&Self> {
+///         match value {
+///             Place::Country(c) => Some(c),
+///             _ => None,
+///         }
+///     }
+/// }
+/// ```
+pub trait Projection<V> {
+    /// Borrow the value as this type, or return `None` if the value is not of this type.
+    fn project(value: &V) -> Option<&Self>;
+}
+
+impl<T: Any> Projection<Box<dyn Any>> for T {
+    #[inline]
+    fn project(value: &Box<dyn Any>) -> Option<&Self> {
+        value.downcast_ref()
+    }
+}
+
+impl<T: Any> Projection<Box<dyn Any + Send>> for T {
+    #[inline]
+    fn project(value: &Box<dyn Any + Send>) -> Option<&Self> {
+        value.downcast_ref()
+    }
+}
+
+impl<T: Any> Projection<Box<dyn Any + Send + Sync>> for T {
+    #[inline]
+    fn project(value: &Box<dyn Any + Send + Sync>) -> Option<&Self> {
+        value.downcast_ref()
+    }
+}
+
+#[inline]
+fn is_projectable<V, T: Projection<V>>(value: &V) -> bool {
+    T::project(value).is_some()
+}
+
+/// A type allowed at a level by `LeveledHashMap::allow_type_at_level`.
+pub(crate) struct AllowedType<V> {
+    type_id: TypeId,
+    allows:  fn(&V) -> bool,
+}
+
+impl<V> Debug for AllowedType<V> {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let mut s = f.debug_struct("AllowedType");
+        s.field("TypeId", &self.type_id);
+        s.finish()
+    }
+}
+
+/// Possible errors come from `LeveledHashMap::get_as` and `LeveledHashMap::get_as_mut`.
+pub enum ProjectionError<K> {
+    /// The entry cannot be found.
+    Map(LeveledHashMapError<K>),
+    /// Another type is registered for the level of the entry.
+    LevelMismatch { level: usize, registered: &'static str, requested: &'static str },
+    /// The value cannot be projected to the requested type.
+    ValueMismatch { level: usize, key: Arc<K>, requested: &'static str },
+}
+
+impl<K> Debug for ProjectionError<K> {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter) -> Result<(), fmt::Error> {
+        match self {
+            ProjectionError::Map(err) => f.debug_tuple("Map").field(err).finish(),
+            ProjectionError::LevelMismatch {
+                level,
+                registered,
+                requested,
+            } => {
+                let mut s = f.debug_struct("LevelMismatch");
+                s.field("Level", level);
+                s.field("Registered", registered);
+                s.field("Requested", requested);
+                s.finish()
+            },
+            ProjectionError::ValueMismatch {
+                level,
+                requested,
+                ..
+            } => {
+                let mut s = f.debug_struct("ValueMismatch");
+                s.field("Level", level);
+                s.field("Requested", requested);
+                s.finish()
+            },
+        }
+    }
+}
+
+impl<K> Display for ProjectionError<K> {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter) -> Result<(), fmt::Error> {
+        match self {
+            ProjectionError::Map(err) => Display::fmt(err, f),
+            ProjectionError::LevelMismatch {
+                level,
+                registered,
+                requested,
+            } => f.write_fmt(format_args!(
+                "The values at level {} are projected to `{}`, not `{}`.",
+                level, registered, requested
+            )),
+            ProjectionError::ValueMismatch {
+                level,
+                requested,
+                ..
+            } => f.write_fmt(format_args!(
+                "The value at level {} cannot be projected to `{}`.",
+                level, requested
+            )),
+        }
+    }
+}
+
+impl<K> Error for ProjectionError<K> {}
+
+impl<K: Eq + Hash, V> LeveledHashMap<K, V> {
+    /// Register the type which the values at a specific level are projected to, replacing the previous one. `get_as` refuses to project the values at the level to any other type.
+    pub fn register_projection<T: Projection<V> + 'static>(&mut self, level: usize) {
+        while self.projections.len() <= level {
+            self.projections.push(None);
+        }
+
+        self.projections[level] = Some((TypeId::of::<T>(), type_name::<T>()));
+    }
+
+    /// Unregister the type which the values at a specific level are projected to.
+    #[inline]
+    pub fn unregister_projection(&mut self, level: usize) {
+        if let Some(p) = self.projections.get_mut(level) {
+            *p = None;
+        }
+    }
+
+    /// Get a value by a key chain which starts at Level 0, borrowed as a specific type. It returns a `Err(ProjectionError)` instance if the entry cannot be found, another type is registered for the level, or the value is not of the type.
+    /// ```
+    /// use std::sync::Arc;
+    ///
+    /// use leveled_hash_map::{LeveledHashMap, Projection, ProjectionError};
+    ///
+    /// #[derive(Debug, PartialEq)]
+    /// struct Country(&'static str);
+    ///
+    /// #[derive(Debug, PartialEq)]
+    /// struct Region(&'static str);
+    ///
+    /// enum Place {
+    ///     Country(Country),
+    ///     Region(Region),
+    /// }
+    ///
+    /// impl Projection<Place> for Country {
+    ///     fn project(value: &Place) -> Option<&Self> {
+    ///         match value {
+    ///             Place::Country(c) => Some(c),
+    ///             _ => None,
+    ///         }
+    ///     }
+    /// }
+    ///
+    /// impl Projection<Place> for Region {
+    ///     fn project(value: &Place) -> Option<&Self> {
+    ///         match value {
+    ///             Place::Region(r) => Some(r),
+    ///             _ => None,
+    ///         }
+    ///     }
+    /// }
+    ///
+    /// let mut map: LeveledHashMap<&'static str, Place> = LeveledHashMap::new();
+    ///
+    /// map.register_projection::<Country>(0);
+    /// map.register_projection::<Region>(1);
+    ///
+    /// map.insert(&[Arc::new("tw")], Place::Country(Country("Taiwan"))).unwrap();
+    /// map.insert(
+    ///     &[Arc::new("tw"), Arc::new("north")],
+    ///     Place::Region(Region("Northern")),
+    /// )
+    /// .unwrap();
+    ///
+    /// assert_eq!(
+    ///     &Country("Taiwan"),
+    ///     map.get_as::<Country>(&[Arc::new("tw")]).unwrap()
+    /// );
+    /// assert_eq!(
+    ///     &Region("Northern"),
+    ///     map.get_as::<Region>(&[Arc::new("tw"), Arc::new("north")]).unwrap()
+    /// );
+    ///
+    /// match map.get_as::<Region>(&[Arc::new("tw")]) {
+    ///     Err(ProjectionError::LevelMismatch {
+    ///         level, ..
+    ///     }) => assert_eq!(0, level),
+    ///     _ => unreachable!(),
+    /// }
+    /// ```
+    pub fn get_as<T: Projection<V> + 'static>(
+        &self,
+        key_chain: &[Arc<K>],
+    ) -> Result<&T, ProjectionError<K>> {
+        let (_, value) = self.get_professional(key_chain, 0).map_err(ProjectionError::Map)?;
+
+        let level = key_chain.len() - 1;
+
+        self.check_projection::<T>(level)?;
+
+        T::project(value).ok_or_else(|| ProjectionError::ValueMismatch {
+            level,
+            key: Arc::clone(&key_chain[level]),
+            requested: type_name::<T>(),
+        })
+    }
+
+    /// Check whether the type registered for a specific level, if any, is `T`.
+    pub(crate) fn check_projection<T: 'static>(
+        &self,
+        level: usize,
+    ) -> Result<(), ProjectionError<K>> {
+        if let Some(Some((type_id, registered))) = self.projections.get(level) {
+            if *type_id != TypeId::of::<T>() {
+                return Err(ProjectionError::LevelMismatch {
+                    level,
+                    registered,
+                    requested: type_name::<T>(),
+                });
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Allow the values which can be projected to a specific type at a specific level. Once any type is allowed at a level, the insertion methods refuse the values which cannot be projected to any of the allowed types at the level with a `LeveledHashMapError::ValueNotAllowed` error. Existing entries and mutable accesses are not checked.
+    ///
+    /// For `Box<dyn Any>` values, every `'static` type is a projection, so the types can be allowed directly.
+    /// ```
+    /// use std::sync::Arc;
+    ///
+    /// use leveled_hash_map::{LeveledHashMap, Projection};
+    ///
+    /// struct Country;
+    /// struct City;
+    ///
+    /// enum Place {
+    ///     Country(Country),
+    ///     City(City),
+    /// }
+    ///
+    /// impl Projection<Place> for Country {
+    ///     fn project(value: &Place) -> Option<&Self> {
+    ///         match value {
+    ///             Place::Country(c) => Some(c),
+    ///             _ => None,
+    ///         }
+    ///     }
+    /// }
+    ///
+    /// impl Projection<Place> for City {
+    ///     fn project(value: &Place) -> Option<&Self> {
+    ///         match value {
+    ///             Place::City(c) => Some(c),
+    ///             _ => None,
+    ///         }
+    ///     }
+    /// }
+    ///
+    /// let mut map: LeveledHashMap<&'static str, Place> = LeveledHashMap::new();
+    ///
+    /// map.allow_type_at_level::<Country>(0);
+    /// map.allow_type_at_level::<City>(1);
+    ///
+    /// assert!(map.insert(&[Arc::new("taipei")], Place::City(City)).is_err());
+    ///
+    /// map.insert(&[Arc::new("tw")], Place::Country(Country)).unwrap();
+    /// map.insert(&[Arc::new("tw"), Arc::new("taipei")], Place::City(City))
+    ///     .unwrap();
+    /// ```
+    pub fn allow_type_at_level<T: Projection<V> + 'static>(&mut self, level: usize) {
+        while self.allowed_types.len() <= level {
+            self.allowed_types.push(Vec::new());
+        }
+
+        let type_id = TypeId::of::<T>();
+
+        if self.allowed_types[level].iter().all(|t| t.type_id != type_id) {
+            self.allowed_types[level].push(AllowedType {
+                type_id,
+                allows: is_projectable::<V, T>,
+            });
+        }
+    }
+
+    /// Allow values of any type at a specific level again.
+    #[inline]
+    pub fn clear_allowed_types(&mut self, level: usize) {
+        if let Some(types) = self.allowed_types.get_mut(level) {
+            types.clear();
+        }
+    }
+
+    /// Check whether a value is allowed at a specific level by the types allowed with `allow_type_at_level`.
+    #[inline]
+    pub fn is_value_allowed(&self, level: usize, value: &V) -> bool {
+        match self.allowed_types.get(level) {
+            Some(types) if !types.is_empty() => types.iter().any(|t| (t.allows)(value)),
+            _ => true,
+        }
+    }
+}
@@ -0,0 +1,139 @@
+//! Python bindings built on PyO3. `PyLeveledHashMap` is exported to Python as `LeveledHashMap`, a dict-like class whose keys are tuples of strings (key chains) and whose values are arbitrary Python objects. Add it to a PyO3 module with `register`.
+//!
+//! ```python
+//! m = LeveledHashMap()
+//!
+//! m[("food",)] = 1
+//! m[("food", "dessert")] = {"name": "cake"}
+//!
+//! assert ("food", "dessert") in m
+//! assert list(m) == [("food",), ("food", "dessert")]
+//!
+//! del m[("food",)]  # removes the descendants as well
+//!
+//! assert len(m) == 0
+//! ```
+//!
+//! This feature needs Rust 1.83 or newer, as required by PyO3.
+
+// the code generated by PyO3 is not limited to the MSRV of this crate
+#![allow(clippy::incompatible_msrv)]
+
+use std::sync::Arc;
+
+use pyo3::{
+    exceptions::PyKeyError,
+    prelude::*,
+    types::{PyList, PyTuple},
+};
+
+use crate::{LeveledHashMap, LeveledHashMapError};
+
+/// A `LeveledHashMap<String, Py<PyAny>>` which can be used from Python.
+#[pyclass(name = "LeveledHashMap", module = "leveled_hash_map", mapping)]
+#[derive(Debug, Default)]
+pub struct PyLeveledHashMap {
+    /// The wrapped map.
+    pub inner: LeveledHashMap<String, Py<PyAny>>,
+}
+
+#[inline]
+fn to_key_chain(path: Vec<String>) -> Vec<Arc<String>> {
+    path.into_iter().map(Arc::new).collect()
+}
+
+#[inline]
+fn to_py_err(err: LeveledHashMapError<String>) -> PyErr {
+    PyKeyError::new_err(err.to_string())
+}
+
+impl PyLeveledHashMap {
+    fn paths(&self) -> Vec<Vec<Arc<String>>> {
+        match self.inner.keys_deep(&[]) {
+            Ok(keys) => keys.collect(),
+            Err(_) => Vec::new(),
+        }
+    }
+}
+
+#[pymethods]
+impl PyLeveledHashMap {
+    #[new]
+    fn new() -> Self {
+        PyLeveledHashMap::default()
+    }
+
+    fn __len__(&self) -> usize {
+        self.inner.pool.iter().map(|m| m.len()).sum()
+    }
+
+    fn __contains__(&self, path: Vec<String>) -> bool {
+        self.inner.get(&to_key_chain(path)).is_some()
+    }
+
+    fn __getitem__(&self, py: Python<'_>, path: Vec<String>) -> PyResult<Py<PyAny>> {
+        self.inner
+            .get_professional(&to_key_chain(path), 0)
+            .map(|(_, v)| v.clone_ref(py))
+            .map_err(to_py_err)
+    }
+
+    fn __setitem__(&mut self, path: Vec<String>, value: Py<PyAny>) -> PyResult<()> {
+        self.inner.insert(&to_key_chain(path), value).map(|_| ()).map_err(to_py_err)
+    }
+
+    fn __delitem__(&mut self, path: Vec<String>) -> PyResult<()> {
+        self.inner.remove_professional(&to_key_chain(path), 0).map(|_| ()).map_err(to_py_err)
+    }
+
+    fn __iter__<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
+        self.keys(py)?.into_any().try_iter().map(Bound::into_any)
+    }
+
+    /// Get a value by a key chain, or `default` if the key chain does not exist.
+    #[pyo3(signature = (path, default = None))]
+    fn get(
+        &self,
+        py: Python<'_>,
+        path: Vec<String>,
+        default: Option<Py<PyAny>>,
+    ) -> Option<Py<PyAny>> {
+        match self.inner.get(&to_key_chain(path)) {
+            Some(v) => Some(v.clone_ref(py)),
+            None => default,
+        }
+    }
+
+    /// Get the key chains of all entries, parents before their children.
+    fn keys<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyList>> {
+        let paths = self
+            .paths()
+            .into_iter()
+            .map(|p| PyTuple::new(py, p.iter().map(|k| k.as_str())))
+            .collect::<PyResult<Vec<_>>>()?;
+
+        PyList::new(py, paths)
+    }
+
+    /// Get the `(key_chain, value)` pairs of all entries, parents before their children.
+    fn items<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyList>> {
+        let items = self
+            .paths()
+            .into_iter()
+            .map(|p| {
+                let value = self.inner.get(&p).unwrap().clone_ref(py);
+                let path = PyTuple::new(py, p.iter().map(|k| k.as_str()))?;
+
+                Ok((path, value))
+            })
+            .collect::<PyResult<Vec<_>>>()?;
+
+        PyList::new(py, items)
+    }
+}
+
+/// Add the `LeveledHashMap` class to a Python module.
+#[inline]
+pub fn register(module: &Bound<'_, PyModule>) -> PyResult<()> {
+    module.add_class::<PyLeveledHashMap>()
+}
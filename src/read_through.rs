@@ -0,0 +1,196 @@
+use std::{
+    error::Error,
+    fmt::{self, Debug, Display, Formatter},
+    hash::Hash,
+    sync::Arc,
+};
+
+use crate::{LeveledHashMap, LeveledHashMapError};
+
+/// Policies of a `ReadThroughMap` for the missing ancestors of an entry being loaded.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AncestorPolicy {
+    /// Load the missing ancestors with the loader first, from the top down. If the loader does not find an ancestor, the entry is treated as not found. This is the default policy.
+    Load,
+    /// Refuse to load the entry and return a `LeveledHashMapError::KeyNotExist` error.
+    Fail,
+}
+
+impl Default for AncestorPolicy {
+    #[inline]
+    fn default() -> Self {
+        AncestorPolicy::Load
+    }
+}
+
+/// Possible errors come from `ReadThroughMap::get_or_load`.
+pub enum LoadError<K, E> {
+    /// The loader failed.
+    Loader(E),
+    /// The loaded value cannot be inserted into the map.
+    Map(LeveledHashMapError<K>),
+}
+
+impl<K, E: Debug> Debug for LoadError<K, E> {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter) -> Result<(), fmt::Error> {
+        match self {
+            LoadError::Loader(err) => f.debug_tuple("Loader").field(err).finish(),
+            LoadError::Map(err) => f.debug_tuple("Map").field(err).finish(),
+        }
+    }
+}
+
+impl<K, E: Display> Display for LoadError<K, E> {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter) -> Result<(), fmt::Error> {
+        match self {
+            LoadError::Loader(err) => Display::fmt(err, f),
+            LoadError::Map(err) => Display::fmt(err, f),
+        }
+    }
+}
+
+impl<K, E: Debug + Display> Error for LoadError<K, E> {}
+
+/// A `LeveledHashMap` in front of a slower hierarchical backend. On a miss, `get_or_load` calls the registered loader with the key chain, inserts the loaded value into the map and returns it.
+pub struct ReadThroughMap<K: Eq + Hash, V, F> {
+    map:             LeveledHashMap<K, V>,
+    loader:          F,
+    ancestor_policy: AncestorPolicy,
+}
+
+impl<K: Eq + Hash + Debug, V: Debug, F> Debug for ReadThroughMap<K, V, F> {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter) -> Result<(), fmt::Error> {
+        f.debug_struct("ReadThroughMap")
+            .field("map", &self.map)
+            .field("ancestor_policy", &self.ancestor_policy)
+            .finish()
+    }
+}
+
+impl<K: Eq + Hash, V, E, F: FnMut(&[Arc<K>]) -> Result<Option<V>, E>> ReadThroughMap<K, V, F> {
+    /// Create a new `ReadThroughMap` instance with a map (which can be empty or pre-populated) and a loader. The loader receives the key chain of a missing entry and returns `Ok(None)` if the entry does not exist in the backend either.
+    #[inline]
+    pub fn new(map: LeveledHashMap<K, V>, loader: F) -> Self {
+        ReadThroughMap {
+            map,
+            loader,
+            ancestor_policy: AncestorPolicy::default(),
+        }
+    }
+
+    /// Get the policy for the missing ancestors of an entry being loaded.
+    #[inline]
+    pub fn ancestor_policy(&self) -> AncestorPolicy {
+        self.ancestor_policy
+    }
+
+    /// Set the policy for the missing ancestors of an entry being loaded.
+    /// ```
+    /// use std::{convert::Infallible, sync::Arc};
+    ///
+    /// use leveled_hash_map::{AncestorPolicy, LeveledHashMap, ReadThroughMap};
+    ///
+    /// let mut map =
+    ///     ReadThroughMap::new(LeveledHashMap::new(), |_: &[Arc<&str>]| {
+    ///         Ok::<_, Infallible>(Some(0))
+    ///     });
+    ///
+    /// map.set_ancestor_policy(AncestorPolicy::Fail);
+    ///
+    /// assert!(map.get_or_load(&[Arc::new("food"), Arc::new("dessert")]).is_err());
+    /// assert_eq!(Some(&0), map.get_or_load(&[Arc::new("food")]).unwrap());
+    /// assert_eq!(
+    ///     Some(&0),
+    ///     map.get_or_load(&[Arc::new("food"), Arc::new("dessert")]).unwrap()
+    /// );
+    /// ```
+    #[inline]
+    pub fn set_ancestor_policy(&mut self, ancestor_policy: AncestorPolicy) {
+        self.ancestor_policy = ancestor_policy;
+    }
+
+    /// Get the cached map.
+    #[inline]
+    pub fn map(&self) -> &LeveledHashMap<K, V> {
+        &self.map
+    }
+
+    /// Get the cached map mutably, e.g. to invalidate entries by removing them.
+    #[inline]
+    pub fn map_mut(&mut self) -> &mut LeveledHashMap<K, V> {
+        &mut self.map
+    }
+
+    /// Take the cached map.
+    #[inline]
+    pub fn into_map(self) -> LeveledHashMap<K, V> {
+        self.map
+    }
+
+    /// Get a value by a key chain which starts at Level 0, loading it (and its missing ancestors, depending on the ancestor policy) with the loader if it is not in the map. It returns `Ok(None)` if the loader does not find the entry.
+    /// ```
+    /// use std::{convert::Infallible, sync::Arc};
+    ///
+    /// use leveled_hash_map::{LeveledHashMap, ReadThroughMap};
+    ///
+    /// let mut loads = 0;
+    ///
+    /// let mut map = ReadThroughMap::new(
+    ///     LeveledHashMap::new(),
+    ///     |key_chain: &[Arc<&str>]| {
+    ///         loads += 1;
+    ///
+    ///         Ok::<_, Infallible>(match key_chain.last().map(|k| **k) {
+    ///             Some("food") => Some(1),
+    ///             Some("dessert") => Some(2),
+    ///             _ => None,
+    ///         })
+    ///     },
+    /// );
+    ///
+    /// // loads "food" and then "dessert"
+    /// assert_eq!(
+    ///     Some(&2),
+    ///     map.get_or_load(&[Arc::new("food"), Arc::new("dessert")]).unwrap()
+    /// );
+    ///
+    /// // cached
+    /// assert_eq!(Some(&1), map.get_or_load(&[Arc::new("food")]).unwrap());
+    ///
+    /// assert_eq!(None, map.get_or_load(&[Arc::new("drink")]).unwrap());
+    ///
+    /// drop(map);
+    ///
+    /// assert_eq!(3, loads);
+    /// ```
+    pub fn get_or_load(&mut self, key_chain: &[Arc<K>]) -> Result<Option<&V>, LoadError<K, E>> {
+        if self.map.get(key_chain).is_none() {
+            for i in 0..key_chain.len() {
+                let prefix = &key_chain[..=i];
+
+                if self.map.get(prefix).is_some() {
+                    continue;
+                }
+
+                if i + 1 < key_chain.len() && self.ancestor_policy == AncestorPolicy::Fail {
+                    return Err(LoadError::Map(LeveledHashMapError::KeyNotExist {
+                        level: i,
+                        key:   Arc::clone(&key_chain[i]),
+                    }));
+                }
+
+                match (self.loader)(prefix).map_err(LoadError::Loader)? {
+                    Some(value) => {
+                        self.map.insert(prefix, value).map_err(LoadError::Map)?;
+                    },
+                    None => return Ok(None),
+                }
+            }
+        }
+
+        Ok(self.map.get(key_chain))
+    }
+}
@@ -0,0 +1,375 @@
+use std::{
+    collections::{HashMap, HashSet},
+    hash::Hash,
+    sync::Arc,
+};
+
+use crate::{LevelLimits, LeveledHashMap, LeveledHashMapError};
+
+impl<K: Eq + Hash, V> LeveledHashMap<K, V> {
+    /// Move an existing entry at a specific level, along with its descendants, under another parent whose key chain starts at Level 0. The entry is moved to Level 0 if `to_chain` is empty. The stored keys are kept, and it returns a `Err(LeveledHashMapError)` instance without changing anything if any key would be duplicated at its new level, or if the moved entries would break the reserved keys, the level limits or the allowed value types of their new levels, as the insertion methods do.
+    /// ```
+    /// use std::sync::Arc;
+    ///
+    /// use leveled_hash_map::LeveledHashMap;
+    ///
+    /// let mut map: LeveledHashMap<&'static str, u8> = LeveledHashMap::new();
+    ///
+    /// map.insert(&[Arc::new("food")], 1).unwrap();
+    /// map.insert(&[Arc::new("dessert")], 2).unwrap();
+    /// map.insert(&[Arc::new("dessert"), Arc::new("cake")], 3).unwrap();
+    ///
+    /// map.relevel_key(&Arc::new("dessert"), 0, &[Arc::new("food")]).unwrap();
+    ///
+    /// assert_eq!(None, map.get(&[Arc::new("dessert")]));
+    /// assert_eq!(Some(&2), map.get(&[Arc::new("food"), Arc::new("dessert")]));
+    /// assert_eq!(
+    ///     Some(&3),
+    ///     map.get(&[Arc::new("food"), Arc::new("dessert"), Arc::new("cake")])
+    /// );
+    /// ```
+    pub fn relevel_key(
+        &mut self,
+        key: &Arc<K>,
+        from_level: usize,
+        to_chain: &[Arc<K>],
+    ) -> Result<(), LeveledHashMapError<K>> {
+        if !self.pool.get(from_level).map(|m| m.contains_key(key)).unwrap_or(false) {
+            return Err(LeveledHashMapError::KeyNotExist {
+                level: from_level,
+                key:   Arc::clone(key),
+            });
+        }
+
+        if !to_chain.is_empty() {
+            self.get_inner(to_chain, 0)?;
+
+            if to_chain.get(from_level) == Some(key) {
+                // the entry cannot be moved into its own subtree
+                return Err(LeveledHashMapError::KeyDuplicated {
+                    level: from_level,
+                    key:   Arc::clone(key),
+                });
+            }
+        }
+
+        let to_level = to_chain.len();
+
+        // collect the keys of the subtree, depth by depth
+        let mut subtree: Vec<HashSet<Arc<K>>> = Vec::new();
+
+        let mut current = HashSet::new();
+        current.insert(Arc::clone(key));
+
+        while !current.is_empty() {
+            let level = from_level + subtree.len();
+
+            let mut next = HashSet::new();
+
+            for k in current.iter() {
+                for c in self.sub[level][k].iter() {
+                    next.insert(Arc::clone(c));
+                }
+            }
+
+            subtree.push(current);
+
+            current = next;
+        }
+
+        // validate the uniqueness of keys at the new levels
+        for (depth, keys) in subtree.iter().enumerate() {
+            let level = to_level + depth;
+
+            if let Some(map) = self.pool.get(level) {
+                let moving = level.checked_sub(from_level).and_then(|d| subtree.get(d));
+
+                for k in keys {
+                    if map.contains_key(k) && !moving.map(|m| m.contains(k)).unwrap_or(false) {
+                        return Err(LeveledHashMapError::KeyDuplicated {
+                            level,
+                            key: Arc::clone(k),
+                        });
+                    }
+                }
+            }
+        }
+
+        // validate the new levels as the insertion methods do
+        if to_level != from_level {
+            for (depth, keys) in subtree.iter().enumerate() {
+                let level = to_level + depth;
+                let old_level = from_level + depth;
+
+                let leaving = level.checked_sub(from_level).and_then(|d| subtree.get(d));
+
+                self.check_limits(
+                    level,
+                    None,
+                    keys.len().saturating_sub(leaving.map(|m| m.len()).unwrap_or(0)),
+                )?;
+
+                let max_children = self.level_limits(level).max_children;
+
+                for k in keys {
+                    self.check_reserved(level, k)?;
+
+                    if !self.is_value_allowed(level, &self.pool[old_level][k].1) {
+                        return Err(LeveledHashMapError::ValueNotAllowed {
+                            level,
+                            key: Arc::clone(k),
+                        });
+                    }
+
+                    if let Some(limit) = max_children {
+                        if self.sub[old_level][k].len() > limit {
+                            return Err(LeveledHashMapError::TooManyChildren {
+                                level,
+                                key: Arc::clone(k),
+                                limit,
+                            });
+                        }
+                    }
+                }
+            }
+        }
+
+        if let Some(new_parent) = to_chain.last() {
+            if self.pool[from_level][key].0.as_ref() != Some(new_parent) {
+                if let Some(limit) = self.level_limits(to_level - 1).max_children {
+                    let (pk, children) = self.sub[to_level - 1].get_key_value(new_parent).unwrap();
+
+                    if children.len() >= limit {
+                        return Err(LeveledHashMapError::TooManyChildren {
+                            level: to_level - 1,
+                            key: Arc::clone(pk),
+                            limit,
+                        });
+                    }
+                }
+            }
+        }
+
+        // detach the subtree
+        let old_parent = self.pool[from_level][key].0.clone();
+
+        if let Some(children) = old_parent.and_then(|pk| self.sub[from_level - 1].get_mut(&pk)) {
+            children.remove(key);
+        }
+
+        let mut detached: Vec<HashMap<Arc<K>, ((Option<Arc<K>>, V), HashSet<Arc<K>>)>> =
+            Vec::with_capacity(subtree.len());
+
+        for (depth, keys) in subtree.iter().enumerate() {
+            let level = from_level + depth;
+
+            let mut map = HashMap::with_capacity(keys.len());
+
+            for k in keys {
+                let (k, entry) = self.pool[level].remove_entry(k).unwrap();
+                let children = self.sub[level].remove(&k).unwrap();
+
+                if let Some(generations) = self.generations.as_mut() {
+                    generations.mark_removed(level, &k);
+                }
+
+                #[cfg(feature = "stats")]
+                self.forget_access(level, &k);
+
+                map.insert(k, (entry, children));
+            }
+
+            detached.push(map);
+        }
+
+        // attach the subtree to its new parent
+        let new_parent = to_chain.last().map(Arc::clone);
+
+        if let Some(new_parent) = new_parent.as_ref() {
+            let root = detached[0].keys().next().map(Arc::clone).unwrap();
+
+            self.sub[to_level - 1].get_mut(new_parent).unwrap().insert(root);
+        }
+
+        for (depth, map) in detached.into_iter().enumerate() {
+            let level = to_level + depth;
+
+            if level >= self.pool.len() {
+                self.pool.push(HashMap::new());
+                self.sub.push(HashMap::new());
+            }
+
+            for (k, ((pk, v), children)) in map {
+                let pk = if depth == 0 { new_parent.clone() } else { pk };
+
+                if let Some(generations) = self.generations.as_mut() {
+                    generations.mark_changed(level, &k);
+                }
+
+                self.sub[level].insert(Arc::clone(&k), children);
+                self.pool[level].insert(k, (pk, v));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Split a specific level into two adjacent levels by grouping its entries. Every entry at the level is moved under an intermediate entry whose key is given by `group` and whose value is created by `value`, and the intermediate entries are placed under the original parents, so the level and all deeper levels are shifted down by one. It returns the number of the created intermediate entries.
+    ///
+    /// Entries with different parents cannot share a group, so a `LeveledHashMapError::KeyDuplicated` error is returned without changing anything if a group key is used under two parents. Per-level settings such as reserved keys, limits and allowed types are shifted down along with their levels, and the intermediate level starts without any settings.
+    /// ```
+    /// use std::sync::Arc;
+    ///
+    /// use leveled_hash_map::LeveledHashMap;
+    ///
+    /// let mut map: LeveledHashMap<&'static str, u32> = LeveledHashMap::new();
+    ///
+    /// map.insert(&[Arc::new("tw")], 0).unwrap();
+    /// map.insert(&[Arc::new("tw"), Arc::new("taipei")], 2_500_000).unwrap();
+    /// map.insert(&[Arc::new("tw"), Arc::new("keelung")], 360_000).unwrap();
+    /// map.insert(&[Arc::new("tw"), Arc::new("kaohsiung")], 2_700_000).unwrap();
+    ///
+    /// let count = map
+    ///     .split_level(
+    ///         1,
+    ///         |key, _value| if *key == "kaohsiung" { "south" } else { "north" },
+    ///         |_group| 0,
+    ///     )
+    ///     .unwrap();
+    ///
+    /// assert_eq!(2, count);
+    /// assert_eq!(
+    ///     Some(&2_500_000),
+    ///     map.get(&[Arc::new("tw"), Arc::new("north"), Arc::new("taipei")])
+    /// );
+    /// assert_eq!(
+    ///     Some(&2_700_000),
+    ///     map.get(&[Arc::new("tw"), Arc::new("south"), Arc::new("kaohsiung")])
+    /// );
+    /// assert_eq!(None, map.get(&[Arc::new("tw"), Arc::new("taipei")]));
+    /// ```
+    pub fn split_level<G: FnMut(&K, &V) -> K, F: FnMut(&K) -> V>(
+        &mut self,
+        level: usize,
+        mut group: G,
+        mut value: F,
+    ) -> Result<usize, LeveledHashMapError<K>> {
+        if level >= self.pool.len() {
+            return Err(LeveledHashMapError::LevelOutOfRange {
+                level,
+                max: self.max_level(),
+            });
+        }
+
+        // the intermediate entries with their parents and members, and the group of every entry
+        let mut groups: HashMap<Arc<K>, (Option<Arc<K>>, HashSet<Arc<K>>)> = HashMap::new();
+        let mut assignments: Vec<(Arc<K>, Arc<K>)> = Vec::with_capacity(self.pool[level].len());
+
+        for (key, (parent_key, v)) in self.pool[level].iter() {
+            let group_key = group(key, v);
+
+            let group_key = match groups.get_key_value(&group_key) {
+                Some((group_key, (group_parent, _))) => {
+                    if group_parent != parent_key {
+                        return Err(LeveledHashMapError::KeyDuplicated {
+                            level,
+                            key: Arc::clone(group_key),
+                        });
+                    }
+
+                    Arc::clone(group_key)
+                },
+                None => {
+                    let group_key = Arc::new(group_key);
+
+                    groups.insert(Arc::clone(&group_key), (parent_key.clone(), HashSet::new()));
+
+                    group_key
+                },
+            };
+
+            groups.get_mut(&group_key).unwrap().1.insert(Arc::clone(key));
+            assignments.push((Arc::clone(key), group_key));
+        }
+
+        // the per-level settings move down along with their levels
+        if self.reserved.len() > level {
+            self.reserved.insert(level, HashSet::new());
+        }
+
+        if self.limits.len() > level {
+            self.limits.insert(level, LevelLimits::default());
+        }
+
+        if self.projections.len() > level {
+            self.projections.insert(level, None);
+        }
+
+        if self.allowed_types.len() > level {
+            self.allowed_types.insert(level, Vec::new());
+        }
+
+        // forget the old positions of the entries which are shifted down
+        if let Some(generations) = self.generations.as_mut() {
+            generations.ensure_level(level);
+
+            // the removals at the level keep their position, because removing the entries at the level also removes everything below it in the older copies of the map
+            generations.changed.insert(level, HashMap::new());
+            generations.removed.insert(level + 1, HashMap::new());
+
+            for k in self.pool[level].keys() {
+                generations.mark_removed(level, k);
+            }
+        }
+
+        #[cfg(feature = "stats")]
+        for (l, map) in self.pool.iter().enumerate().skip(level) {
+            for k in map.keys() {
+                self.forget_access(l, k);
+            }
+        }
+
+        // point the original parents to the intermediate entries
+        if level > 0 {
+            for children in self.sub[level - 1].values_mut() {
+                children.clear();
+            }
+
+            for (group_key, (parent_key, _)) in groups.iter() {
+                let parent_key = parent_key.as_ref().unwrap();
+
+                self.sub[level - 1].get_mut(parent_key).unwrap().insert(Arc::clone(group_key));
+            }
+        }
+
+        for (key, group_key) in assignments {
+            self.pool[level].get_mut(&key).unwrap().0 = Some(group_key);
+        }
+
+        // insert the intermediate level
+        let count = groups.len();
+
+        let mut pool = HashMap::with_capacity(count);
+        let mut sub = HashMap::with_capacity(count);
+
+        for (group_key, (parent_key, members)) in groups {
+            let v = value(&group_key);
+
+            sub.insert(Arc::clone(&group_key), members);
+            pool.insert(group_key, (parent_key, v));
+        }
+
+        self.pool.insert(level, pool);
+        self.sub.insert(level, sub);
+
+        if let Some(generations) = self.generations.as_mut() {
+            for (l, map) in self.pool.iter().enumerate().skip(level) {
+                for k in map.keys() {
+                    generations.mark_changed(l, k);
+                }
+            }
+        }
+
+        Ok(count)
+    }
+}
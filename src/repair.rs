@@ -0,0 +1,109 @@
+use std::{
+    collections::{HashMap, HashSet},
+    hash::Hash,
+    sync::Arc,
+};
+
+use crate::LeveledHashMap;
+
+/// Strategies of `LeveledHashMap::repair` for handling orphan entries.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RepairStrategy {
+    /// Remove the orphan entries along with their descendants.
+    Remove,
+    /// Move the orphan entries along with their descendants to Level 0. Orphans which cannot be moved because some keys would be duplicated are removed.
+    MoveToTop,
+}
+
+impl<K: Eq + Hash, V> LeveledHashMap<K, V> {
+    /// Find the entries whose recorded parent key does not exist at the superior level, as `(level, key)` pairs ordered by level. A consistent map has no orphans.
+    /// ```
+    /// use std::sync::Arc;
+    ///
+    /// use leveled_hash_map::LeveledHashMap;
+    ///
+    /// let mut map: LeveledHashMap<&'static str, u8> = LeveledHashMap::new();
+    ///
+    /// map.insert(&[Arc::new("food")], 1).unwrap();
+    /// map.insert(&[Arc::new("food"), Arc::new("dessert")], 2).unwrap();
+    ///
+    /// assert!(map.find_orphans().is_empty());
+    /// ```
+    pub fn find_orphans(&self) -> Vec<(usize, Arc<K>)> {
+        let mut orphans = Vec::new();
+
+        for (level, map) in self.pool.iter().enumerate() {
+            for (key, (pk, _)) in map {
+                let orphan = match pk {
+                    Some(pk) => level == 0 || !self.pool[level - 1].contains_key(pk),
+                    None => level > 0,
+                };
+
+                if orphan {
+                    orphans.push((level, Arc::clone(key)));
+                }
+            }
+        }
+
+        orphans
+    }
+
+    /// Repair the map. The child key sets are rebuilt from the recorded parent keys, and the orphan entries are handled by a strategy. It returns the orphans which are removed, as `(level, key)` pairs.
+    /// ```
+    /// use std::sync::Arc;
+    ///
+    /// use leveled_hash_map::{LeveledHashMap, RepairStrategy};
+    ///
+    /// let mut map: LeveledHashMap<&'static str, u8> = LeveledHashMap::new();
+    ///
+    /// map.insert(&[Arc::new("food")], 1).unwrap();
+    /// map.insert(&[Arc::new("food"), Arc::new("dessert")], 2).unwrap();
+    ///
+    /// assert!(map.repair(RepairStrategy::Remove).is_empty());
+    ///
+    /// assert_eq!(Some(&2), map.get(&[Arc::new("food"), Arc::new("dessert")]));
+    /// ```
+    pub fn repair(&mut self, strategy: RepairStrategy) -> Vec<(usize, Arc<K>)> {
+        let orphans = self.find_orphans();
+
+        // rebuild the child key sets
+        let mut sub: Vec<HashMap<Arc<K>, HashSet<Arc<K>>>> = self
+            .pool
+            .iter()
+            .map(|map| map.keys().map(|k| (Arc::clone(k), HashSet::new())).collect())
+            .collect();
+
+        for (level, map) in self.pool.iter().enumerate().skip(1) {
+            for (key, (pk, _)) in map {
+                if let Some(children) = pk.as_ref().and_then(|pk| sub[level - 1].get_mut(pk)) {
+                    children.insert(Arc::clone(key));
+                }
+            }
+        }
+
+        self.sub = sub;
+
+        let mut removed = Vec::new();
+
+        for (level, key) in orphans {
+            match strategy {
+                RepairStrategy::Remove => {
+                    self.remove_node(level, &key);
+
+                    removed.push((level, key));
+                },
+                RepairStrategy::MoveToTop => {
+                    if level == 0 {
+                        self.pool[0].get_mut(&key).unwrap().0 = None;
+                    } else if self.relevel_key(&key, level, &[]).is_err() {
+                        self.remove_node(level, &key);
+
+                        removed.push((level, key));
+                    }
+                },
+            }
+        }
+
+        removed
+    }
+}
@@ -0,0 +1,68 @@
+use std::{collections::HashSet, hash::Hash, sync::Arc};
+
+use crate::LeveledHashMap;
+
+impl<K: Eq + Hash, V> LeveledHashMap<K, V> {
+    /// Reserve a key at a specific level. A reserved key is refused by `insert`, `insert_many` and `insert_child`, but can still be inserted by `insert_reserved`, so applications can store sidecar entries without colliding with user data. Entries which already exist are not affected. It returns `false` if the key has already been reserved at the level.
+    /// ```
+    /// use std::sync::Arc;
+    ///
+    /// use leveled_hash_map::LeveledHashMap;
+    ///
+    /// let mut map: LeveledHashMap<&'static str, u8> = LeveledHashMap::new();
+    ///
+    /// assert!(map.reserve_key(1, "__meta"));
+    /// assert!(map.is_reserved_key(1, &"__meta"));
+    /// assert!(!map.is_reserved_key(0, &"__meta"));
+    ///
+    /// map.insert(&[Arc::new("food")], 1).unwrap();
+    ///
+    /// assert!(map.insert(&[Arc::new("food"), Arc::new("__meta")], 2).is_err());
+    /// ```
+    pub fn reserve_key(&mut self, level: usize, key: K) -> bool {
+        while self.reserved.len() <= level {
+            self.reserved.push(HashSet::new());
+        }
+
+        self.reserved[level].insert(Arc::new(key))
+    }
+
+    /// Cancel the reservation of a key at a specific level. It returns `false` if the key was not reserved at the level.
+    #[inline]
+    pub fn unreserve_key(&mut self, level: usize, key: &K) -> bool {
+        self.reserved.get_mut(level).map(|r| r.remove(key)).unwrap_or(false)
+    }
+
+    /// Check whether a key is reserved at a specific level.
+    #[inline]
+    pub fn is_reserved_key(&self, level: usize, key: &K) -> bool {
+        self.reserved.get(level).map(|r| r.contains(key)).unwrap_or(false)
+    }
+
+    /// Get an iterator over the entries at a specific level like `iter_level`, but skip the entries whose keys are reserved at the level.
+    /// ```
+    /// use std::sync::Arc;
+    ///
+    /// use leveled_hash_map::LeveledHashMap;
+    ///
+    /// let mut map: LeveledHashMap<&'static str, u8> = LeveledHashMap::new();
+    ///
+    /// map.reserve_key(0, "__meta");
+    ///
+    /// map.insert(&[Arc::new("food")], 1).unwrap();
+    /// map.insert_reserved(&[Arc::new("__meta")], 0).unwrap();
+    ///
+    /// assert_eq!(2, map.iter_level(0).unwrap().count());
+    /// assert_eq!(1, map.iter_level_unreserved(0).unwrap().count());
+    /// ```
+    pub fn iter_level_unreserved(
+        &self,
+        level: usize,
+    ) -> Option<impl Iterator<Item = (Arc<K>, Option<Arc<K>>, &V)>> {
+        let reserved = self.reserved.get(level);
+
+        self.iter_level(level).map(move |iter| {
+            iter.filter(move |(k, ..)| !reserved.map(|r| r.contains(k)).unwrap_or(false))
+        })
+    }
+}
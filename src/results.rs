@@ -0,0 +1,185 @@
+use std::{collections::HashMap, hash::Hash, sync::Arc};
+
+use crate::{LeveledHashMap, LeveledHashMapError};
+
+/// An entry found by `LeveledHashMap::get_with_parent`.
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EntryWithParent<'a, K, V> {
+    /// The key of the parent, or `None` if the entry is at Level 0.
+    pub parent_key: Option<Arc<K>>,
+    /// The value of the entry.
+    pub value:      &'a V,
+}
+
+/// An entry found by `LeveledHashMap::get_with_parent_mut`.
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[derive(Debug, PartialEq, Eq)]
+pub struct EntryWithParentMut<'a, K, V> {
+    /// The key of the parent, or `None` if the entry is at Level 0.
+    pub parent_key: Option<Arc<K>>,
+    /// The value of the entry.
+    pub value:      &'a mut V,
+}
+
+/// An entry removed by `LeveledHashMap::remove_with_subtree`, along with its descendants.
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RemovedSubtree<K: Eq + Hash, V> {
+    /// The key of the parent, or `None` if the entry was at Level 0.
+    pub parent_key:  Option<Arc<K>>,
+    /// The value of the entry.
+    pub value:       V,
+    /// The removed descendants grouped by levels, starting from the level of the children. Each of them maps a key to its parent key and its value.
+    pub descendants: Vec<HashMap<Arc<K>, (Option<Arc<K>>, V)>>,
+}
+
+impl<K: Eq + Hash, V> LeveledHashMap<K, V> {
+    /// Get a value and its parent key by a key chain and a level which the key chain starts with. It returns a `Err(LeveledHashMapError)` instance to describe the reason of the getting failure.
+    /// ```
+    /// use std::sync::Arc;
+    ///
+    /// use leveled_hash_map::LeveledHashMap;
+    ///
+    /// let mut map: LeveledHashMap<&'static str, u8> = LeveledHashMap::new();
+    ///
+    /// map.insert(&[Arc::new("food")], 1).unwrap();
+    /// map.insert(&[Arc::new("food"), Arc::new("dessert")], 2).unwrap();
+    ///
+    /// let entry = map.get_with_parent(&[Arc::new("dessert")], 1).unwrap();
+    ///
+    /// assert_eq!(Some(Arc::new("food")), entry.parent_key);
+    /// assert_eq!(&2, entry.value);
+    /// ```
+    pub fn get_with_parent(
+        &self,
+        key_chain: &[Arc<K>],
+        start_level: usize,
+    ) -> Result<EntryWithParent<'_, K, V>, LeveledHashMapError<K>> {
+        let (parent_key, value) = self.get_inner(key_chain, start_level)?;
+
+        #[cfg(feature = "stats")]
+        {
+            let key_chain_len_dec = key_chain.len() - 1;
+
+            self.record_access(
+                key_chain_len_dec + start_level,
+                &key_chain[key_chain_len_dec],
+                false,
+            );
+        }
+
+        Ok(EntryWithParent {
+            parent_key: parent_key.cloned(),
+            value,
+        })
+    }
+
+    /// Get a mutable value and its parent key by a key chain and a level which the key chain starts with. It returns a `Err(LeveledHashMapError)` instance to describe the reason of the getting failure.
+    /// ```
+    /// use std::sync::Arc;
+    ///
+    /// use leveled_hash_map::LeveledHashMap;
+    ///
+    /// let mut map: LeveledHashMap<&'static str, u8> = LeveledHashMap::new();
+    ///
+    /// map.insert(&[Arc::new("food")], 1).unwrap();
+    /// map.insert(&[Arc::new("food"), Arc::new("dessert")], 2).unwrap();
+    ///
+    /// let entry = map.get_with_parent_mut(&[Arc::new("dessert")], 1).unwrap();
+    ///
+    /// assert_eq!(Some(Arc::new("food")), entry.parent_key);
+    ///
+    /// *entry.value += 1;
+    ///
+    /// assert_eq!(Some(&3), map.get(&[Arc::new("food"), Arc::new("dessert")]));
+    /// ```
+    pub fn get_with_parent_mut(
+        &mut self,
+        key_chain: &[Arc<K>],
+        start_level: usize,
+    ) -> Result<EntryWithParentMut<'_, K, V>, LeveledHashMapError<K>> {
+        let parent_key = self.get_inner(key_chain, start_level)?.0.cloned();
+
+        let key_chain_len_dec = key_chain.len() - 1;
+
+        let level = key_chain_len_dec + start_level;
+        let key = &key_chain[key_chain_len_dec];
+
+        if let Some(generations) = self.generations.as_mut() {
+            generations.mark_changed(level, key);
+        }
+
+        #[cfg(feature = "stats")]
+        self.record_access(level, key, true);
+
+        Ok(EntryWithParentMut {
+            parent_key,
+            value: &mut self.pool[level].get_mut(key).unwrap().1,
+        })
+    }
+
+    /// Remove a value along with its descendants by a key chain and a level which the key chain starts with. It returns a `Err(LeveledHashMapError)` instance to describe the reason of the removing failure. If tombstones are enabled, a clone of the removed subtree is also kept as a `Tombstone`.
+    /// ```
+    /// use std::sync::Arc;
+    ///
+    /// use leveled_hash_map::LeveledHashMap;
+    ///
+    /// let mut map: LeveledHashMap<&'static str, u8> = LeveledHashMap::new();
+    ///
+    /// map.insert(&[Arc::new("food")], 1).unwrap();
+    /// map.insert(&[Arc::new("food"), Arc::new("dessert")], 2).unwrap();
+    ///
+    /// let removed = map.remove_with_subtree(&[Arc::new("food")], 0).unwrap();
+    ///
+    /// assert_eq!(None, removed.parent_key);
+    /// assert_eq!(1, removed.value);
+    /// assert_eq!(
+    ///     &(Some(Arc::new("food")), 2),
+    ///     removed.descendants[0].get(&Arc::new("dessert")).unwrap()
+    /// );
+    /// ```
+    pub fn remove_with_subtree(
+        &mut self,
+        key_chain: &[Arc<K>],
+        start_level: usize,
+    ) -> Result<RemovedSubtree<K, V>, LeveledHashMapError<K>> {
+        self.get_inner(key_chain, start_level)?;
+
+        let key_chain_len_dec = key_chain.len() - 1;
+
+        let level = key_chain_len_dec + start_level;
+
+        #[cfg(feature = "stats")]
+        self.record_access(level, &key_chain[key_chain_len_dec], true);
+
+        #[cfg(feature = "log")]
+        let slow_op_key_chain = self.slow_op_key_chain(level, &key_chain[key_chain_len_dec]);
+
+        let tombstone_key_chain = if self.is_tombstones_enabled() {
+            Some(self.key_chain_of(level, &key_chain[key_chain_len_dec]))
+        } else {
+            None
+        };
+
+        let (parent_key, value, descendants) =
+            self.remove_node(level, &key_chain[key_chain_len_dec]);
+
+        if let Some(tombstone_key_chain) = tombstone_key_chain {
+            self.keep_tombstone(tombstone_key_chain, &value, &descendants);
+        }
+
+        #[cfg(feature = "log")]
+        self.report_slow_op(
+            "remove",
+            slow_op_key_chain,
+            1 + descendants.iter().map(|m| m.len()).sum::<usize>(),
+        );
+
+        Ok(RemovedSubtree {
+            parent_key,
+            value,
+            descendants,
+        })
+    }
+}
@@ -0,0 +1,92 @@
+use std::{hash::Hash, sync::Arc};
+
+use rand::Rng;
+
+use crate::LeveledHashMap;
+
+impl<K: Eq + Hash, V> LeveledHashMap<K, V> {
+    /// Walk down from Level 0 by choosing an entry among the candidates (the entries at Level 0 first, and then the children of the chosen entry) randomly in proportion to their weights, until reaching a leaf. It returns the key chain of the walked path, or `None` if no entry at Level 0 has a positive weight. Weights which are not positive or not finite are treated as zero, and the walk also stops at an entry whose children all have zero weights.
+    ///
+    /// The candidates are visited in the iteration order of the internal hash maps, so seeding the random number generator only reproduces the paths of the same map instance.
+    /// ```
+    /// use std::sync::Arc;
+    ///
+    /// use leveled_hash_map::LeveledHashMap;
+    /// use rand::{rngs::StdRng, SeedableRng};
+    ///
+    /// let mut map: LeveledHashMap<&'static str, f64> = LeveledHashMap::new();
+    ///
+    /// map.insert(&[Arc::new("sunny")], 0.7).unwrap();
+    /// map.insert(&[Arc::new("sunny"), Arc::new("go_out")], 1.0).unwrap();
+    /// map.insert(&[Arc::new("sunny"), Arc::new("stay_home")], 0.0).unwrap();
+    /// map.insert(&[Arc::new("rainy")], 0.3).unwrap();
+    ///
+    /// let mut rng = StdRng::seed_from_u64(0);
+    ///
+    /// for _ in 0..10 {
+    ///     let path = map.sample_path(&mut rng, |_, w| *w).unwrap();
+    ///
+    ///     assert!(
+    ///         path == [Arc::new("sunny"), Arc::new("go_out")]
+    ///             || path == [Arc::new("rainy")]
+    ///     );
+    /// }
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "rand")))]
+    pub fn sample_path<R: Rng + ?Sized, F: FnMut(&K, &V) -> f64>(
+        &self,
+        rng: &mut R,
+        mut weight: F,
+    ) -> Option<Vec<Arc<K>>> {
+        let mut path: Vec<Arc<K>> = Vec::new();
+
+        let mut candidates: Vec<&Arc<K>> = self.pool.first()?.keys().collect();
+
+        loop {
+            let level = path.len();
+
+            let weighted: Vec<(&Arc<K>, f64)> = candidates
+                .iter()
+                .map(|k| {
+                    let w = weight(k.as_ref(), &self.pool[level][*k].1);
+
+                    (*k, if w.is_finite() && w > 0.0 { w } else { 0.0 })
+                })
+                .filter(|(_, w)| *w > 0.0)
+                .collect();
+
+            let total: f64 = weighted.iter().map(|(_, w)| w).sum();
+
+            let chosen = match weighted.last() {
+                Some((last, _)) => {
+                    let mut r = rng.gen::<f64>() * total;
+
+                    weighted
+                        .iter()
+                        .find(|(_, w)| {
+                            if r < *w {
+                                true
+                            } else {
+                                r -= w;
+
+                                false
+                            }
+                        })
+                        .map(|(k, _)| *k)
+                        .unwrap_or(last)
+                },
+                None => break,
+            };
+
+            path.push(Arc::clone(chosen));
+
+            candidates = self.sub[level][chosen].iter().collect();
+        }
+
+        if path.is_empty() {
+            None
+        } else {
+            Some(path)
+        }
+    }
+}
@@ -0,0 +1,175 @@
+//! A flat serde representation of `LeveledHashMap`, which writes every entry with its key chain joined into a single key, such as `{"food/dessert/cake": value}`. Use it with `#[serde(with = "leveled_hash_map::serde_flat")]`. The keys need to implement `Display` and `FromStr`.
+//!
+//! The default separator is `/`. The `dotted` submodule uses `.` instead, and `serialize_with_separator` and `deserialize_with_separator` can be wrapped for any other separator. A key which contains the separator cannot be serialized.
+//!
+//! ```
+//! use std::sync::Arc;
+//!
+//! use leveled_hash_map::LeveledHashMap;
+//! use serde::{Deserialize, Serialize};
+//!
+//! #[derive(Serialize, Deserialize)]
+//! struct Config {
+//!     #[serde(with = "leveled_hash_map::serde_flat::dotted")]
+//!     settings: LeveledHashMap<String, u16>,
+//! }
+//!
+//! let config: Config =
+//!     serde_json::from_str(r#"{"settings":{"server.port":8080,"server":1}}"#)
+//!         .unwrap();
+//!
+//! assert_eq!(
+//!     Some(&8080),
+//!     config.settings.get(&[
+//!         Arc::new("server".to_string()),
+//!         Arc::new("port".to_string())
+//!     ])
+//! );
+//!
+//! assert_eq!(
+//!     r#"{"settings":{"server":1,"server.port":8080}}"#,
+//!     serde_json::to_string(&config).unwrap()
+//! );
+//! ```
+
+use std::{
+    collections::HashMap,
+    fmt::{Display, Write},
+    hash::Hash,
+    str::FromStr,
+    sync::Arc,
+};
+
+use serde::{
+    de::Error as DeError,
+    ser::{Error as SerError, SerializeMap},
+    Deserialize, Deserializer, Serialize, Serializer,
+};
+
+use crate::LeveledHashMap;
+
+/// The default separator of the keys in a joined key chain.
+pub const DEFAULT_SEPARATOR: char = '/';
+
+/// Serialize a map with the keys in every key chain joined by `/`. Parents are written before their children.
+#[inline]
+pub fn serialize<K: Eq + Hash + Display, V: Serialize, S: Serializer>(
+    map: &LeveledHashMap<K, V>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    serialize_with_separator(map, DEFAULT_SEPARATOR, serializer)
+}
+
+/// Deserialize a map from key chains joined by `/`. Parents can be listed after their children.
+#[inline]
+pub fn deserialize<'de, K: Eq + Hash + FromStr, V: Deserialize<'de>, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<LeveledHashMap<K, V>, D::Error>
+where
+    K::Err: Display, {
+    deserialize_with_separator(DEFAULT_SEPARATOR, deserializer)
+}
+
+/// Serialize a map with the keys in every key chain joined by a specific separator. Parents are written before their children.
+pub fn serialize_with_separator<K: Eq + Hash + Display, V: Serialize, S: Serializer>(
+    map: &LeveledHashMap<K, V>,
+    separator: char,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    let mut s = serializer.serialize_map(Some(map.pool.iter().map(|m| m.len()).sum()))?;
+
+    let mut joined = String::new();
+
+    for key_chain in map.paths() {
+        joined.clear();
+
+        for (i, key) in key_chain.iter().enumerate() {
+            if i > 0 {
+                joined.push(separator);
+            }
+
+            let start = joined.len();
+
+            write!(joined, "{}", key).map_err(S::Error::custom)?;
+
+            if joined[start..].contains(separator) {
+                return Err(S::Error::custom(format_args!(
+                    "The key {:?} contains the separator {:?}.",
+                    &joined[start..],
+                    separator
+                )));
+            }
+        }
+
+        let level = key_chain.len() - 1;
+
+        s.serialize_entry(&joined, &map.pool[level][&key_chain[level]].1)?;
+    }
+
+    s.end()
+}
+
+/// Deserialize a map from key chains joined by a specific separator. Parents can be listed after their children.
+pub fn deserialize_with_separator<
+    'de,
+    K: Eq + Hash + FromStr,
+    V: Deserialize<'de>,
+    D: Deserializer<'de>,
+>(
+    separator: char,
+    deserializer: D,
+) -> Result<LeveledHashMap<K, V>, D::Error>
+where
+    K::Err: Display, {
+    let entries: HashMap<String, V> = HashMap::deserialize(deserializer)?;
+
+    let mut records = Vec::with_capacity(entries.len());
+
+    for (joined, value) in entries {
+        let mut key_chain = Vec::new();
+
+        for key in joined.split(separator) {
+            key_chain.push(Arc::new(key.parse::<K>().map_err(D::Error::custom)?));
+        }
+
+        records.push((key_chain, value));
+    }
+
+    records.sort_by_key(|(key_chain, _)| key_chain.len());
+
+    let mut map = LeveledHashMap::new();
+
+    for (key_chain, value) in records {
+        map.insert(&key_chain, value).map_err(D::Error::custom)?;
+    }
+
+    Ok(map)
+}
+
+/// The flat representation with the keys in every key chain joined by `.`, as dotted-key stores do. Use it with `#[serde(with = "leveled_hash_map::serde_flat::dotted")]`.
+pub mod dotted {
+    use std::{fmt::Display, hash::Hash, str::FromStr};
+
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    use crate::LeveledHashMap;
+
+    /// Serialize a map with the keys in every key chain joined by `.`.
+    #[inline]
+    pub fn serialize<K: Eq + Hash + Display, V: Serialize, S: Serializer>(
+        map: &LeveledHashMap<K, V>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        super::serialize_with_separator(map, '.', serializer)
+    }
+
+    /// Deserialize a map from key chains joined by `.`.
+    #[inline]
+    pub fn deserialize<'de, K: Eq + Hash + FromStr, V: Deserialize<'de>, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<LeveledHashMap<K, V>, D::Error>
+    where
+        K::Err: Display, {
+        super::deserialize_with_separator('.', deserializer)
+    }
+}
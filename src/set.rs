@@ -0,0 +1,168 @@
+use std::{
+    collections::{HashMap, HashSet},
+    hash::Hash,
+    sync::Arc,
+};
+
+use crate::{KeysDeep, LeveledHashMap, LeveledHashMapError};
+
+/// A structure to separate keys into different levels, like a `LeveledHashMap` without values. Every key which is not at the top level has a parent key at the superior level. Keys at the same level are unique, no matter what parent keys they have.
+#[derive(Debug)]
+pub struct LeveledHashSet<K: Eq + Hash> {
+    map: LeveledHashMap<K, ()>,
+}
+
+impl<K: Eq + Hash> LeveledHashSet<K> {
+    /// Create a new `LeveledHashSet` instance. The key needs to be implemented `Eq` and `Hash` traits.
+    /// ```
+    /// use leveled_hash_map::LeveledHashSet;
+    ///
+    /// let _set: LeveledHashSet<&'static str> = LeveledHashSet::new();
+    /// ```
+    #[inline]
+    pub fn new() -> LeveledHashSet<K> {
+        LeveledHashSet {
+            map: LeveledHashMap::new()
+        }
+    }
+
+    /// Check whether a key chain which starts at Level 0 is in the set.
+    /// ```
+    /// use std::sync::Arc;
+    ///
+    /// use leveled_hash_map::LeveledHashSet;
+    ///
+    /// let mut set: LeveledHashSet<&'static str> = LeveledHashSet::new();
+    ///
+    /// set.insert(&[Arc::new("admin")]).unwrap();
+    /// set.insert(&[Arc::new("admin"), Arc::new("users")]).unwrap();
+    ///
+    /// assert!(set.contains(&[Arc::new("admin"), Arc::new("users")]));
+    /// assert!(!set.contains(&[Arc::new("users")]));
+    /// ```
+    #[inline]
+    pub fn contains(&self, key_chain: &[Arc<K>]) -> bool {
+        self.map.contains_key_chain(key_chain)
+    }
+
+    /// Insert a key chain which starts at Level 0. The parent key chain needs to be in the set already. It returns `Ok(true)` if the key chain was not in the set, or a `Err(LeveledHashMapError)` instance to describe the reason of the insertion failure.
+    /// ```
+    /// use std::sync::Arc;
+    ///
+    /// use leveled_hash_map::LeveledHashSet;
+    ///
+    /// let mut set: LeveledHashSet<&'static str> = LeveledHashSet::new();
+    ///
+    /// assert!(set.insert(&[Arc::new("admin")]).unwrap());
+    /// assert!(!set.insert(&[Arc::new("admin")]).unwrap());
+    ///
+    /// assert!(set.insert(&[Arc::new("guest"), Arc::new("users")]).is_err());
+    /// ```
+    #[inline]
+    pub fn insert(&mut self, key_chain: &[Arc<K>]) -> Result<bool, LeveledHashMapError<K>> {
+        if self.contains(key_chain) {
+            return Ok(false);
+        }
+
+        self.map.insert(key_chain, ()).map(|_| true)
+    }
+
+    /// Remove a key chain which starts at Level 0 along with its descendants. It returns the removed descendant keys grouped by levels, starting from the level of the children, or `None` if the key chain is not in the set.
+    /// ```
+    /// use std::sync::Arc;
+    ///
+    /// use leveled_hash_map::LeveledHashSet;
+    ///
+    /// let mut set: LeveledHashSet<&'static str> = LeveledHashSet::new();
+    ///
+    /// set.insert(&[Arc::new("admin")]).unwrap();
+    /// set.insert(&[Arc::new("admin"), Arc::new("users")]).unwrap();
+    ///
+    /// let descendants = set.remove(&[Arc::new("admin")]).unwrap();
+    ///
+    /// assert_eq!(1, descendants.len());
+    /// assert!(descendants[0].contains(&Arc::new("users")));
+    /// assert!(!set.contains(&[Arc::new("admin")]));
+    /// ```
+    #[inline]
+    pub fn remove(&mut self, key_chain: &[Arc<K>]) -> Option<Vec<HashSet<Arc<K>>>> {
+        self.map.remove(key_chain).map(|(_, descendants)| {
+            descendants.into_iter().map(|m| m.into_keys().collect()).collect()
+        })
+    }
+
+    /// Get the keys at a specific level, with the child keys of each of them.
+    /// ```
+    /// use std::sync::Arc;
+    ///
+    /// use leveled_hash_map::LeveledHashSet;
+    ///
+    /// let mut set: LeveledHashSet<&'static str> = LeveledHashSet::new();
+    ///
+    /// set.insert(&[Arc::new("admin")]).unwrap();
+    /// set.insert(&[Arc::new("admin"), Arc::new("users")]).unwrap();
+    ///
+    /// assert_eq!(1, set.keys(0).unwrap()[&Arc::new("admin")].len());
+    /// ```
+    #[inline]
+    pub fn keys(&self, level: usize) -> Option<&HashMap<Arc<K>, HashSet<Arc<K>>>> {
+        self.map.keys(level)
+    }
+
+    /// Get an iterator over the key chains of all keys in pre-order (parents before their children). Every key chain starts at Level 0.
+    /// ```
+    /// use std::sync::Arc;
+    ///
+    /// use leveled_hash_map::LeveledHashSet;
+    ///
+    /// let mut set: LeveledHashSet<&'static str> = LeveledHashSet::new();
+    ///
+    /// set.insert(&[Arc::new("admin")]).unwrap();
+    /// set.insert(&[Arc::new("admin"), Arc::new("users")]).unwrap();
+    ///
+    /// assert_eq!(
+    ///     vec![vec![Arc::new("admin")], vec![
+    ///         Arc::new("admin"),
+    ///         Arc::new("users")
+    ///     ]],
+    ///     set.paths().collect::<Vec<_>>()
+    /// );
+    /// ```
+    #[inline]
+    pub fn paths(&self) -> KeysDeep<'_, K> {
+        self.map.paths()
+    }
+
+    /// Get the underlying `LeveledHashMap` instance, whose values are all `()`, to use its other traversal methods.
+    #[inline]
+    pub fn as_map(&self) -> &LeveledHashMap<K, ()> {
+        &self.map
+    }
+
+    /// Convert the set into a `LeveledHashMap` instance whose values are all `()`.
+    #[inline]
+    pub fn into_map(self) -> LeveledHashMap<K, ()> {
+        self.map
+    }
+}
+
+impl<K: Eq + Hash> Default for LeveledHashSet<K> {
+    #[inline]
+    fn default() -> Self {
+        LeveledHashSet::new()
+    }
+}
+
+impl<K: Eq + Hash, V> From<LeveledHashMap<K, V>> for LeveledHashSet<K> {
+    /// Keep the keys of a `LeveledHashMap` instance and drop its values.
+    #[inline]
+    fn from(map: LeveledHashMap<K, V>) -> Self {
+        let mut set = LeveledHashSet::new();
+
+        for (key_chain, _) in map.into_iter() {
+            set.map.insert(&key_chain, ()).unwrap();
+        }
+
+        set
+    }
+}
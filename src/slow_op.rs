@@ -0,0 +1,86 @@
+use std::{fmt::Debug, hash::Hash, sync::Arc};
+
+use crate::LeveledHashMap;
+
+/// The configuration of slow-operation warnings.
+pub(crate) struct SlowOpThreshold<K> {
+    threshold: usize,
+    report:    fn(&'static str, &[Arc<K>], usize),
+}
+
+impl<K> Debug for SlowOpThreshold<K> {
+    #[inline]
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> Result<(), std::fmt::Error> {
+        let mut s = f.debug_struct("SlowOpThreshold");
+        s.field("Threshold", &self.threshold);
+        s.finish()
+    }
+}
+
+fn report<K: Debug>(operation: &'static str, key_chain: &[Arc<K>], count: usize) {
+    log::warn!("{} touched {} entries under {:?}", operation, count, key_chain);
+}
+
+impl<K: Eq + Hash, V> LeveledHashMap<K, V> {
+    /// Log a warning through the `log` crate whenever a single operation, such as removing an entry along with its descendants, touches more than `threshold` entries. The warning includes the name of the operation, the number of touched entries and the key chain of the root entry, which starts at Level 0.
+    /// ```
+    /// use std::sync::Arc;
+    ///
+    /// use leveled_hash_map::LeveledHashMap;
+    ///
+    /// let mut map: LeveledHashMap<&'static str, u8> = LeveledHashMap::new();
+    ///
+    /// map.set_slow_op_threshold(1);
+    ///
+    /// map.insert(&[Arc::new("food")], 1).unwrap();
+    /// map.insert(&[Arc::new("food"), Arc::new("dessert")], 2).unwrap();
+    ///
+    /// assert_eq!(Some(1), map.slow_op_threshold());
+    ///
+    /// // logs "remove touched 2 entries under ["food"]"
+    /// map.remove(&[Arc::new("food")]).unwrap();
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "log")))]
+    #[inline]
+    pub fn set_slow_op_threshold(&mut self, threshold: usize)
+    where
+        K: Debug, {
+        self.slow_op = Some(SlowOpThreshold {
+            threshold,
+            report: report::<K>,
+        });
+    }
+
+    /// Get the threshold of slow-operation warnings, if it is set.
+    #[cfg_attr(docsrs, doc(cfg(feature = "log")))]
+    #[inline]
+    pub fn slow_op_threshold(&self) -> Option<usize> {
+        self.slow_op.as_ref().map(|s| s.threshold)
+    }
+
+    /// Stop logging slow-operation warnings.
+    #[cfg_attr(docsrs, doc(cfg(feature = "log")))]
+    #[inline]
+    pub fn clear_slow_op_threshold(&mut self) {
+        self.slow_op = None;
+    }
+
+    /// Rebuild the key chain of an entry which is about to be touched by an operation, if slow-operation warnings are enabled.
+    pub(crate) fn slow_op_key_chain(&self, level: usize, key: &Arc<K>) -> Option<Vec<Arc<K>>> {
+        self.slow_op.as_ref().map(|_| self.key_chain_of(level, key))
+    }
+
+    /// Log a warning if an operation has touched more entries than the threshold.
+    pub(crate) fn report_slow_op(
+        &self,
+        operation: &'static str,
+        key_chain: Option<Vec<Arc<K>>>,
+        count: usize,
+    ) {
+        if let (Some(slow_op), Some(key_chain)) = (self.slow_op.as_ref(), key_chain) {
+            if count > slow_op.threshold {
+                (slow_op.report)(operation, &key_chain, count);
+            }
+        }
+    }
+}
@@ -0,0 +1,373 @@
+use std::{
+    collections::HashMap,
+    hash::Hash,
+    sync::{Arc, Mutex, MutexGuard, PoisonError},
+    time::{Duration, Instant},
+};
+
+use crate::LeveledHashMap;
+
+/// The numbers of reads and writes of an entry and its descendants, recorded when access tracking is enabled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct AccessCount {
+    /// The number of reads.
+    pub reads:  u64,
+    /// The number of writes.
+    pub writes: u64,
+}
+
+impl AccessCount {
+    /// The total number of accesses.
+    #[inline]
+    pub fn total(&self) -> u64 {
+        self.reads.saturating_add(self.writes)
+    }
+}
+
+/// Per-entry access counts of the entries above a specific depth, and per-entry last access times.
+#[derive(Debug)]
+pub(crate) struct AccessStats<K: Eq + Hash> {
+    depth:  usize,
+    counts: Vec<HashMap<Arc<K>, AccessCount>>,
+    times:  Option<AccessTimes<K>>,
+}
+
+/// The last access times of the entries which have been accessed since access time tracking was enabled.
+#[derive(Debug)]
+struct AccessTimes<K: Eq + Hash> {
+    since: Instant,
+    times: Vec<HashMap<Arc<K>, Instant>>,
+}
+
+#[inline]
+fn lock<K: Eq + Hash>(stats: &Mutex<AccessStats<K>>) -> MutexGuard<'_, AccessStats<K>> {
+    stats.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+impl<K: Eq + Hash, V> LeveledHashMap<K, V> {
+    /// Start counting the reads and writes of the entries from Level 0 to Level `depth - 1` (at least Level 0). An access to an entry is counted on the entry itself and on all of its ancestors within the depth, so with a depth of `1`, the counts are per top-level subtree. Calling this method again changes the depth and resets the counts.
+    ///
+    /// Reads are counted by the `get*` methods and writes are counted by the `get*_mut`, `insert*` and `remove*` methods. The counts of an entry are dropped when the entry is removed or moved to another level.
+    /// ```
+    /// use std::sync::Arc;
+    ///
+    /// use leveled_hash_map::LeveledHashMap;
+    ///
+    /// let mut map: LeveledHashMap<&'static str, u8> = LeveledHashMap::new();
+    ///
+    /// map.track_access(1);
+    ///
+    /// map.insert(&[Arc::new("food")], 1).unwrap();
+    /// map.insert(&[Arc::new("food"), Arc::new("dessert")], 2).unwrap();
+    /// map.insert(&[Arc::new("animal")], 3).unwrap();
+    ///
+    /// map.get(&[Arc::new("food"), Arc::new("dessert")]);
+    /// map.get(&[Arc::new("food"), Arc::new("dessert")]);
+    ///
+    /// let count = map.access_count(&[Arc::new("food")]).unwrap();
+    ///
+    /// assert_eq!(2, count.reads);
+    /// assert_eq!(2, count.writes);
+    ///
+    /// // not counted, because Level 1 is out of the depth
+    /// assert_eq!(
+    ///     None,
+    ///     map.access_count(&[Arc::new("food"), Arc::new("dessert")])
+    /// );
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "stats")))]
+    pub fn track_access(&mut self, depth: usize) {
+        match self.stats.as_mut() {
+            Some(stats) => {
+                let stats = stats.get_mut().unwrap_or_else(PoisonError::into_inner);
+
+                stats.depth = depth.max(1);
+                stats.counts.clear();
+            },
+            None => {
+                self.stats = Some(Mutex::new(AccessStats {
+                    depth:  depth.max(1),
+                    counts: Vec::new(),
+                    times:  None,
+                }));
+            },
+        }
+    }
+
+    /// Stop counting the reads and writes, and drop the counts.
+    #[cfg_attr(docsrs, doc(cfg(feature = "stats")))]
+    #[inline]
+    pub fn untrack_access(&mut self) {
+        self.untrack(|stats| {
+            stats.depth = 0;
+            stats.counts.clear();
+        });
+    }
+
+    /// Reset the counts without stopping counting.
+    #[cfg_attr(docsrs, doc(cfg(feature = "stats")))]
+    #[inline]
+    pub fn reset_access_counts(&self) {
+        if let Some(stats) = self.stats.as_ref() {
+            lock(stats).counts.clear();
+        }
+    }
+
+    /// Get the access count of an entry by a key chain which starts at Level 0. Only the last key is looked up, so the key chain is not validated. It returns `None` if access tracking is not enabled or the entry has not been counted.
+    #[cfg_attr(docsrs, doc(cfg(feature = "stats")))]
+    pub fn access_count(&self, key_chain: &[Arc<K>]) -> Option<AccessCount> {
+        let level = key_chain.len().checked_sub(1)?;
+
+        let stats = lock(self.stats.as_ref()?);
+
+        stats.counts.get(level)?.get(&key_chain[level]).copied()
+    }
+
+    /// Get the key chains and the access counts of the `n` most accessed entries (subtrees), most accessed first. It returns an empty `Vec` if access tracking is not enabled.
+    /// ```
+    /// use std::sync::Arc;
+    ///
+    /// use leveled_hash_map::LeveledHashMap;
+    ///
+    /// let mut map: LeveledHashMap<&'static str, u8> = LeveledHashMap::new();
+    ///
+    /// map.insert(&[Arc::new("food")], 1).unwrap();
+    /// map.insert(&[Arc::new("food"), Arc::new("dessert")], 2).unwrap();
+    /// map.insert(&[Arc::new("animal")], 3).unwrap();
+    ///
+    /// map.track_access(2);
+    ///
+    /// for _ in 0..3 {
+    ///     map.get(&[Arc::new("food"), Arc::new("dessert")]);
+    /// }
+    ///
+    /// map.get(&[Arc::new("animal")]);
+    ///
+    /// let hot = map.hot_subtrees(2);
+    ///
+    /// assert_eq!(vec![Arc::new("food")], hot[0].0);
+    /// assert_eq!(3, hot[0].1.reads);
+    /// assert_eq!(vec![Arc::new("food"), Arc::new("dessert")], hot[1].0);
+    /// assert_eq!(3, hot[1].1.reads);
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "stats")))]
+    pub fn hot_subtrees(&self, n: usize) -> Vec<(Vec<Arc<K>>, AccessCount)> {
+        let stats = match self.stats.as_ref() {
+            Some(stats) => lock(stats),
+            None => return Vec::new(),
+        };
+
+        let mut counts: Vec<(usize, &Arc<K>, AccessCount)> = stats
+            .counts
+            .iter()
+            .enumerate()
+            .flat_map(|(level, map)| map.iter().map(move |(k, c)| (level, k, *c)))
+            .filter(|(level, k, _)| {
+                self.pool.get(*level).map(|m| m.contains_key(*k)).unwrap_or(false)
+            })
+            .collect();
+
+        // ancestors first when the totals are equal
+        counts.sort_by(|a, b| b.2.total().cmp(&a.2.total()).then(a.0.cmp(&b.0)));
+
+        counts.into_iter().take(n).map(|(level, k, c)| (self.key_chain_of(level, k), c)).collect()
+    }
+
+    /// Count an access to an existing entry on the entry and its ancestors within the tracked depth.
+    pub(crate) fn record_access(&self, level: usize, key: &Arc<K>, write: bool) {
+        let mut stats = match self.stats.as_ref() {
+            Some(stats) => lock(stats),
+            None => return,
+        };
+
+        let now = Instant::now();
+
+        let mut level = level;
+        let mut key = key;
+
+        loop {
+            if let Some(times) = stats.times.as_mut() {
+                while times.times.len() <= level {
+                    times.times.push(HashMap::new());
+                }
+
+                times.times[level].insert(Arc::clone(key), now);
+            }
+
+            if level < stats.depth {
+                while stats.counts.len() <= level {
+                    stats.counts.push(HashMap::new());
+                }
+
+                let count = stats.counts[level].entry(Arc::clone(key)).or_default();
+
+                if write {
+                    count.writes = count.writes.saturating_add(1);
+                } else {
+                    count.reads = count.reads.saturating_add(1);
+                }
+            }
+
+            if level == 0 {
+                break;
+            }
+
+            match self.pool[level].get(key).and_then(|(pk, _)| pk.as_ref()) {
+                Some(pk) => {
+                    key = pk;
+                    level -= 1;
+                },
+                None => break,
+            }
+        }
+    }
+
+    /// Drop the access count and the last access time of an entry which is removed or moved.
+    pub(crate) fn forget_access(&self, level: usize, key: &Arc<K>) {
+        if let Some(stats) = self.stats.as_ref() {
+            let mut stats = lock(stats);
+
+            if let Some(map) = stats.counts.get_mut(level) {
+                map.remove(key);
+            }
+
+            if let Some(map) = stats.times.as_mut().and_then(|times| times.times.get_mut(level)) {
+                map.remove(key);
+            }
+        }
+    }
+
+    /// Stop one kind of tracking, and drop the whole tracking state if the other kind is not enabled either.
+    fn untrack<F: FnOnce(&mut AccessStats<K>)>(&mut self, f: F) {
+        if let Some(stats) = self.stats.as_mut() {
+            let stats = stats.get_mut().unwrap_or_else(PoisonError::into_inner);
+
+            f(stats);
+
+            if stats.depth == 0 && stats.times.is_none() {
+                self.stats = None;
+            }
+        }
+    }
+
+    /// Start recording the last access time of every entry, for pruning idle entries with `prune_idle`. An access to an entry also refreshes the times of all of its ancestors, so an ancestor is never older than its descendants. The entries which have not been accessed since this method was called count as accessed at the time it was called. Calling this method again resets the times.
+    ///
+    /// Accesses are the same as those counted by `track_access`, and the time of an entry is dropped when the entry is removed or moved to another level.
+    #[cfg_attr(docsrs, doc(cfg(feature = "stats")))]
+    pub fn track_access_times(&mut self) {
+        let times = AccessTimes {
+            since: Instant::now(), times: Vec::new()
+        };
+
+        match self.stats.as_mut() {
+            Some(stats) => {
+                stats.get_mut().unwrap_or_else(PoisonError::into_inner).times = Some(times);
+            },
+            None => {
+                self.stats = Some(Mutex::new(AccessStats {
+                    depth:  0,
+                    counts: Vec::new(),
+                    times:  Some(times),
+                }));
+            },
+        }
+    }
+
+    /// Stop recording the last access times, and drop the times.
+    #[cfg_attr(docsrs, doc(cfg(feature = "stats")))]
+    #[inline]
+    pub fn untrack_access_times(&mut self) {
+        self.untrack(|stats| stats.times = None);
+    }
+
+    /// Get the last access time of an entry by a key chain which starts at Level 0. It returns `None` if access time tracking is not enabled or the entry does not exist.
+    #[cfg_attr(docsrs, doc(cfg(feature = "stats")))]
+    pub fn last_access(&self, key_chain: &[Arc<K>]) -> Option<Instant> {
+        self.get_inner(key_chain, 0).ok()?;
+
+        let level = key_chain.len() - 1;
+
+        let stats = lock(self.stats.as_ref()?);
+        let times = stats.times.as_ref()?;
+
+        Some(
+            times
+                .times
+                .get(level)
+                .and_then(|m| m.get(&key_chain[level]))
+                .copied()
+                .unwrap_or(times.since),
+        )
+    }
+
+    /// Remove the entries which have not been accessed for at least a duration, along with their descendants, and get the number of removed entries. Since accessing an entry keeps its ancestors alive, an entry is only removed when its whole subtree is idle. Nothing is removed if access time tracking is not enabled.
+    /// ```no_run
+    /// use std::{sync::Arc, thread, time::Duration};
+    ///
+    /// use leveled_hash_map::LeveledHashMap;
+    ///
+    /// let mut map: LeveledHashMap<&'static str, u8> = LeveledHashMap::new();
+    ///
+    /// map.track_access_times();
+    ///
+    /// map.insert(&[Arc::new("session")], 1).unwrap();
+    /// map.insert(&[Arc::new("session"), Arc::new("cart")], 2).unwrap();
+    /// map.insert(&[Arc::new("session"), Arc::new("history")], 3).unwrap();
+    ///
+    /// thread::sleep(Duration::from_secs(60));
+    ///
+    /// map.get(&[Arc::new("session"), Arc::new("cart")]);
+    ///
+    /// assert_eq!(1, map.prune_idle(Duration::from_secs(60)));
+    ///
+    /// assert!(map.get(&[Arc::new("session"), Arc::new("cart")]).is_some());
+    /// assert!(map.get(&[Arc::new("session"), Arc::new("history")]).is_none());
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "stats")))]
+    pub fn prune_idle(&mut self, older_than: Duration) -> usize {
+        let now = Instant::now();
+
+        let idle: Vec<(usize, Arc<K>)> = {
+            let stats = match self.stats.as_ref() {
+                Some(stats) => lock(stats),
+                None => return 0,
+            };
+
+            let times = match stats.times.as_ref() {
+                Some(times) => times,
+                None => return 0,
+            };
+
+            let is_idle = |level: usize, key: &Arc<K>| {
+                let time =
+                    times.times.get(level).and_then(|m| m.get(key)).copied().unwrap_or(times.since);
+
+                now.saturating_duration_since(time) >= older_than
+            };
+
+            let mut idle = Vec::new();
+            let mut stack: Vec<(usize, &Arc<K>)> =
+                self.pool.first().map(|m| m.keys().map(|k| (0, k)).collect()).unwrap_or_default();
+
+            while let Some((level, key)) = stack.pop() {
+                if is_idle(level, key) {
+                    idle.push((level, Arc::clone(key)));
+                } else {
+                    stack.extend(self.sub[level][key].iter().map(|c| (level + 1, c)));
+                }
+            }
+
+            idle
+        };
+
+        let mut count = 0;
+
+        for (level, key) in idle {
+            let (_, _, descendants) = self.remove_node(level, &key);
+
+            count += 1 + descendants.iter().map(|m| m.len()).sum::<usize>();
+        }
+
+        count
+    }
+}
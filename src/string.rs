@@ -0,0 +1,117 @@
+use std::{collections::HashMap, sync::Arc};
+
+use crate::{borrowed::BorrowedKey, LeveledHashMap, LeveledHashMapError};
+
+/// A `LeveledHashMap` with `String` keys, which has helpers taking key chains of `&str`s, such as `get_str` and `insert_str`.
+pub type StringLeveledHashMap<V> = LeveledHashMap<String, V>;
+
+impl<V> LeveledHashMap<String, V> {
+    /// Get a value by a key chain of `&str`s which starts at Level 0, without allocating.
+    /// ```
+    /// use leveled_hash_map::StringLeveledHashMap;
+    ///
+    /// let mut map: StringLeveledHashMap<u8> = StringLeveledHashMap::new();
+    ///
+    /// map.insert_str(&["food"], 1).unwrap();
+    /// map.insert_str(&["food", "dessert"], 2).unwrap();
+    ///
+    /// assert_eq!(Some(&2), map.get_str(&["food", "dessert"]));
+    /// assert_eq!(None, map.get_str(&["dessert"]));
+    /// ```
+    #[inline]
+    pub fn get_str(&self, key_chain: &[&str]) -> Option<&V> {
+        self.get_ref::<str>(key_chain)
+    }
+
+    /// Get a mutable value by a key chain of `&str`s which starts at Level 0, without allocating.
+    /// ```
+    /// use leveled_hash_map::StringLeveledHashMap;
+    ///
+    /// let mut map: StringLeveledHashMap<u8> = StringLeveledHashMap::new();
+    ///
+    /// map.insert_str(&["food"], 1).unwrap();
+    ///
+    /// *map.get_str_mut(&["food"]).unwrap() += 1;
+    ///
+    /// assert_eq!(Some(&2), map.get_str(&["food"]));
+    /// ```
+    #[inline]
+    pub fn get_str_mut(&mut self, key_chain: &[&str]) -> Option<&mut V> {
+        self.get_ref_mut::<str>(key_chain)
+    }
+
+    /// Check whether an entry exists by a key chain of `&str`s which starts at Level 0, without allocating.
+    #[inline]
+    pub fn contains_str(&self, key_chain: &[&str]) -> bool {
+        self.get_ref_inner::<str>(key_chain).is_some()
+    }
+
+    /// Insert a value by a key chain of `&str`s which starts at Level 0. The keys which already exist at their levels are shared with the stored `Arc<String>`s, so only new keys are allocated. It works like `insert`.
+    /// ```
+    /// use leveled_hash_map::StringLeveledHashMap;
+    ///
+    /// let mut map: StringLeveledHashMap<u8> = StringLeveledHashMap::new();
+    ///
+    /// assert_eq!(None, map.insert_str(&["food"], 1).unwrap());
+    /// assert_eq!(None, map.insert_str(&["food", "dessert"], 2).unwrap());
+    /// assert_eq!(Some(2), map.insert_str(&["food", "dessert"], 3).unwrap());
+    ///
+    /// assert!(map.insert_str(&["drink", "tea"], 4).is_err());
+    /// ```
+    #[inline]
+    pub fn insert_str(
+        &mut self,
+        key_chain: &[&str],
+        value: V,
+    ) -> Result<Option<V>, LeveledHashMapError<String>> {
+        let key_chain = self.intern_str_key_chain(key_chain);
+
+        self.insert(&key_chain, value)
+    }
+
+    /// Remove an entry and its descendants by a key chain of `&str`s which starts at Level 0. It works like `remove`, and does not allocate if the entry does not exist.
+    /// ```
+    /// use leveled_hash_map::StringLeveledHashMap;
+    ///
+    /// let mut map: StringLeveledHashMap<u8> = StringLeveledHashMap::new();
+    ///
+    /// map.insert_str(&["food"], 1).unwrap();
+    /// map.insert_str(&["food", "dessert"], 2).unwrap();
+    ///
+    /// let (value, descendants) = map.remove_str(&["food"]).unwrap();
+    ///
+    /// assert_eq!(1, value);
+    /// assert_eq!(1, descendants[0].len());
+    /// assert!(map.get_str(&["food", "dessert"]).is_none());
+    /// ```
+    pub fn remove_str(
+        &mut self,
+        key_chain: &[&str],
+    ) -> Option<(V, Vec<HashMap<Arc<String>, (Option<Arc<String>>, V)>>)> {
+        if !self.contains_str(key_chain) {
+            return None;
+        }
+
+        let key_chain = self.intern_str_key_chain(key_chain);
+
+        self.remove(&key_chain)
+    }
+
+    /// Convert a key chain of `&str`s into `Arc<String>`s, reusing the stored keys at their levels.
+    fn intern_str_key_chain(&self, key_chain: &[&str]) -> Vec<Arc<String>> {
+        key_chain
+            .iter()
+            .enumerate()
+            .map(|(level, key)| {
+                match self
+                    .pool
+                    .get(level)
+                    .and_then(|m| m.get_key_value(key as &dyn BorrowedKey<str>))
+                {
+                    Some((k, _)) => Arc::clone(k),
+                    None => Arc::new(String::from(*key)),
+                }
+            })
+            .collect()
+    }
+}
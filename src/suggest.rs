@@ -0,0 +1,110 @@
+use std::{hash::Hash, sync::Arc};
+
+use crate::LeveledHashMap;
+
+/// Compute the Levenshtein distance between two strings, counted in `char`s.
+/// ```
+/// use leveled_hash_map::levenshtein;
+///
+/// assert_eq!(0, levenshtein("dessert", "dessert"));
+/// assert_eq!(1, levenshtein("desert", "dessert"));
+/// assert_eq!(3, levenshtein("kitten", "sitting"));
+/// ```
+pub fn levenshtein(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+
+    for (i, ca) in a.chars().enumerate() {
+        let mut diagonal = row[0];
+
+        row[0] = i + 1;
+
+        for (j, cb) in b.iter().enumerate() {
+            let above = row[j + 1];
+
+            row[j + 1] = if ca == *cb { diagonal } else { 1 + diagonal.min(above).min(row[j]) };
+
+            diagonal = above;
+        }
+    }
+
+    row[b.len()]
+}
+
+impl<K: Eq + Hash, V> LeveledHashMap<K, V> {
+    /// Suggest at most `max` existing keys at a specific level which are close to a (usually missing) key, for "did you mean" messages. The Levenshtein distance is used, and keys further away than a third of the length of the given key (but at least 1) are not suggested. The closest keys come first, and keys with the same distance are sorted alphabetically.
+    /// ```
+    /// use std::sync::Arc;
+    ///
+    /// use leveled_hash_map::LeveledHashMap;
+    ///
+    /// let mut map: LeveledHashMap<&'static str, u8> = LeveledHashMap::new();
+    ///
+    /// map.insert(&[Arc::new("food")], 1).unwrap();
+    /// map.insert(&[Arc::new("food"), Arc::new("dessert")], 2).unwrap();
+    /// map.insert(&[Arc::new("food"), Arc::new("desserts")], 3).unwrap();
+    /// map.insert(&[Arc::new("food"), Arc::new("meat")], 4).unwrap();
+    ///
+    /// assert_eq!(
+    ///     vec![Arc::new("dessert"), Arc::new("desserts")],
+    ///     map.suggest_keys(1, &"desert", 5)
+    /// );
+    /// assert!(map.suggest_keys(1, &"fruit", 5).is_empty());
+    /// ```
+    pub fn suggest_keys(&self, level: usize, key: &K, max: usize) -> Vec<Arc<K>>
+    where
+        K: AsRef<str>, {
+        let key = key.as_ref();
+        let threshold = (key.chars().count() / 3).max(1);
+
+        // the key itself breaks ties so that the result does not depend on the iteration order
+        self.suggest_keys_by(level, max, |k| {
+            let k = k.as_ref();
+            let distance = levenshtein(key, k);
+
+            if distance <= threshold {
+                Some((distance, k.to_string()))
+            } else {
+                None
+            }
+        })
+    }
+
+    /// Suggest at most `max` existing keys at a specific level with a closure computing the distance from each key to the wanted one. Keys for which the closure returns `None` are not suggested. The closest keys come first, and keys with the same distance are in arbitrary order.
+    /// ```
+    /// use std::sync::Arc;
+    ///
+    /// use leveled_hash_map::LeveledHashMap;
+    ///
+    /// let mut map: LeveledHashMap<u32, ()> = LeveledHashMap::new();
+    ///
+    /// map.insert(&[Arc::new(10)], ()).unwrap();
+    /// map.insert(&[Arc::new(20)], ()).unwrap();
+    /// map.insert(&[Arc::new(35)], ()).unwrap();
+    ///
+    /// let suggestions =
+    ///     map.suggest_keys_by(0, 2, |k| Some((*k as i64 - 33).abs()));
+    ///
+    /// assert_eq!(vec![Arc::new(35), Arc::new(20)], suggestions);
+    /// ```
+    pub fn suggest_keys_by<D: Ord, F: FnMut(&K) -> Option<D>>(
+        &self,
+        level: usize,
+        max: usize,
+        mut distance: F,
+    ) -> Vec<Arc<K>> {
+        let map = match self.pool.get(level) {
+            Some(map) => map,
+            None => return Vec::new(),
+        };
+
+        let mut candidates: Vec<(D, &Arc<K>)> =
+            map.keys().filter_map(|k| distance(k.as_ref()).map(|d| (d, k))).collect();
+
+        candidates.sort_by(|a, b| a.0.cmp(&b.0));
+        candidates.truncate(max);
+
+        candidates.into_iter().map(|(_, k)| Arc::clone(k)).collect()
+    }
+}
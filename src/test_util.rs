@@ -0,0 +1,374 @@
+//! Helpers for differential testing against a naive reference model of the semantics of `LeveledHashMap`, for checking the wrappers of `LeveledHashMap` in downstream crates.
+//!
+//! `OperationGenerator` produces random `Operation`s from a seed. Apply every operation to the code under test and to a `ReferenceModel`, and then compare their `Outcome`s and their entries.
+//!
+//! The model follows the default configuration of `LeveledHashMap`: overwriting a value keeps the subtree of the entry, and no key is reserved.
+//!
+//! ```
+//! use leveled_hash_map::test_util::run_differential;
+//!
+//! for seed in 0..10 {
+//!     run_differential(seed, 200, &["a", "b", "c"], 3).unwrap();
+//! }
+//! ```
+
+use std::{hash::Hash, sync::Arc};
+
+use crate::LeveledHashMap;
+
+/// An operation on a map. The key chains start at Level 0.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Operation<K, V> {
+    /// Insert a value.
+    Insert { key_chain: Vec<K>, value: V },
+    /// Remove an entry along with its descendants.
+    Remove { key_chain: Vec<K> },
+    /// Get a value.
+    Get { key_chain: Vec<K> },
+}
+
+/// The outcome of an `Operation`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Outcome<V> {
+    /// The operation succeeded. It holds the previous value for `Insert`, the removed value for `Remove` and the value for `Get`.
+    Succeeded(Option<V>),
+    /// The operation failed, e.g. the key chain is incorrect.
+    Failed,
+}
+
+/// A node of `ReferenceModel`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReferenceNode<K, V> {
+    /// The key of the node.
+    pub key:      K,
+    /// The value of the node.
+    pub value:    V,
+    /// The children of the node.
+    pub children: Vec<ReferenceNode<K, V>>,
+}
+
+/// A naive nested model of the semantics of `LeveledHashMap`, built on vectors and linear searches so that it is easy to verify by reading.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReferenceModel<K, V> {
+    /// The nodes at Level 0.
+    pub roots: Vec<ReferenceNode<K, V>>,
+}
+
+impl<K, V> Default for ReferenceModel<K, V> {
+    #[inline]
+    fn default() -> Self {
+        ReferenceModel {
+            roots: Vec::new()
+        }
+    }
+}
+
+impl<K: Eq + Clone, V: Clone> ReferenceModel<K, V> {
+    /// Create an empty model.
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Get a value by a key chain.
+    pub fn get(&self, key_chain: &[K]) -> Option<&V> {
+        let (last, parents) = key_chain.split_last()?;
+
+        let mut nodes = &self.roots;
+
+        for k in parents {
+            nodes = &nodes.iter().find(|n| n.key == *k)?.children;
+        }
+
+        nodes.iter().find(|n| n.key == *last).map(|n| &n.value)
+    }
+
+    /// Get the number of entries.
+    pub fn len(&self) -> usize {
+        fn count<K, V>(nodes: &[ReferenceNode<K, V>]) -> usize {
+            nodes.iter().map(|n| 1 + count(&n.children)).sum()
+        }
+
+        count(&self.roots)
+    }
+
+    /// Check whether the model is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.roots.is_empty()
+    }
+
+    /// Get the key chains and the values of all entries, parents before their children.
+    pub fn entries(&self) -> Vec<(Vec<K>, V)> {
+        fn collect<K: Clone, V: Clone>(
+            nodes: &[ReferenceNode<K, V>],
+            path: &mut Vec<K>,
+            entries: &mut Vec<(Vec<K>, V)>,
+        ) {
+            for n in nodes {
+                path.push(n.key.clone());
+
+                entries.push((path.clone(), n.value.clone()));
+
+                collect(&n.children, path, entries);
+
+                path.pop();
+            }
+        }
+
+        let mut entries = Vec::new();
+
+        collect(&self.roots, &mut Vec::new(), &mut entries);
+
+        entries
+    }
+
+    /// Apply an operation to the model.
+    pub fn apply(&mut self, operation: &Operation<K, V>) -> Outcome<V> {
+        match operation {
+            Operation::Insert {
+                key_chain,
+                value,
+            } => self.insert(key_chain, value.clone()),
+            Operation::Remove {
+                key_chain,
+            } => self.remove(key_chain),
+            Operation::Get {
+                key_chain,
+            } => Outcome::Succeeded(self.get(key_chain).cloned()),
+        }
+    }
+
+    fn insert(&mut self, key_chain: &[K], value: V) -> Outcome<V> {
+        let (last, parents) = match key_chain.split_last() {
+            Some(s) => s,
+            None => return Outcome::Failed,
+        };
+
+        // keys at the same level are unique, no matter what parent keys they have
+        let level = parents.len();
+        let exists_at_level = self.keys_at(level).any(|k| k == last);
+
+        let mut nodes = &mut self.roots;
+
+        for k in parents {
+            nodes = match nodes.iter_mut().find(|n| n.key == *k) {
+                Some(n) => &mut n.children,
+                None => return Outcome::Failed,
+            };
+        }
+
+        match nodes.iter_mut().find(|n| n.key == *last) {
+            Some(n) => Outcome::Succeeded(Some(std::mem::replace(&mut n.value, value))),
+            None if exists_at_level => Outcome::Failed,
+            None => {
+                nodes.push(ReferenceNode {
+                    key: last.clone(),
+                    value,
+                    children: Vec::new(),
+                });
+
+                Outcome::Succeeded(None)
+            },
+        }
+    }
+
+    fn remove(&mut self, key_chain: &[K]) -> Outcome<V> {
+        let (last, parents) = match key_chain.split_last() {
+            Some(s) => s,
+            None => return Outcome::Failed,
+        };
+
+        let mut nodes = &mut self.roots;
+
+        for k in parents {
+            nodes = match nodes.iter_mut().find(|n| n.key == *k) {
+                Some(n) => &mut n.children,
+                None => return Outcome::Failed,
+            };
+        }
+
+        match nodes.iter().position(|n| n.key == *last) {
+            Some(i) => Outcome::Succeeded(Some(nodes.remove(i).value)),
+            None => Outcome::Failed,
+        }
+    }
+
+    fn keys_at(&self, level: usize) -> impl Iterator<Item = &K> {
+        let mut nodes: Vec<&ReferenceNode<K, V>> = self.roots.iter().collect();
+
+        for _ in 0..level {
+            nodes = nodes.into_iter().flat_map(|n| n.children.iter()).collect();
+        }
+
+        nodes.into_iter().map(|n| &n.key)
+    }
+}
+
+/// Apply an operation to a `LeveledHashMap`.
+pub fn apply_to_map<K: Eq + Hash + Clone, V: Clone>(
+    map: &mut LeveledHashMap<K, V>,
+    operation: &Operation<K, V>,
+) -> Outcome<V> {
+    let to_key_chain = |key_chain: &[K]| -> Vec<Arc<K>> {
+        key_chain.iter().map(|k| Arc::new(k.clone())).collect()
+    };
+
+    match operation {
+        Operation::Insert {
+            key_chain,
+            value,
+        } => match map.insert(&to_key_chain(key_chain), value.clone()) {
+            Ok(previous) => Outcome::Succeeded(previous),
+            Err(_) => Outcome::Failed,
+        },
+        Operation::Remove {
+            key_chain,
+        } => match map.remove(&to_key_chain(key_chain)) {
+            Some((v, _)) => Outcome::Succeeded(Some(v)),
+            None => Outcome::Failed,
+        },
+        Operation::Get {
+            key_chain,
+        } => Outcome::Succeeded(map.get(&to_key_chain(key_chain)).cloned()),
+    }
+}
+
+/// Check whether a `LeveledHashMap` has exactly the same entries as a `ReferenceModel`.
+pub fn is_equivalent<K: Eq + Hash + Clone, V: Clone + PartialEq>(
+    map: &LeveledHashMap<K, V>,
+    model: &ReferenceModel<K, V>,
+) -> bool {
+    let entries = model.entries();
+
+    let len: usize = map.pool.iter().map(|m| m.len()).sum();
+
+    len == entries.len()
+        && entries.iter().all(|(key_chain, value)| {
+            let key_chain: Vec<Arc<K>> = key_chain.iter().map(|k| Arc::new(k.clone())).collect();
+
+            map.get(&key_chain) == Some(value)
+        })
+}
+
+/// A generator of random operations with a fixed seed. The key chains are built from a small set of keys so that the operations often hit existing entries.
+#[derive(Debug, Clone)]
+pub struct OperationGenerator<K> {
+    keys:      Vec<K>,
+    max_depth: usize,
+    state:     u64,
+}
+
+impl<K: Clone> OperationGenerator<K> {
+    /// Create a generator which builds key chains of `1..=max_depth` keys chosen from `keys`. `keys` must not be empty.
+    #[inline]
+    pub fn new(seed: u64, keys: Vec<K>, max_depth: usize) -> Self {
+        assert!(!keys.is_empty(), "`keys` must not be empty");
+
+        OperationGenerator {
+            keys,
+            max_depth: max_depth.max(1),
+            state: seed ^ 0x9E3779B97F4A7C15,
+        }
+    }
+
+    /// xorshift64*
+    fn next_u64(&mut self) -> u64 {
+        if self.state == 0 {
+            self.state = 0x9E3779B97F4A7C15;
+        }
+
+        self.state ^= self.state >> 12;
+        self.state ^= self.state << 25;
+        self.state ^= self.state >> 27;
+
+        self.state.wrapping_mul(0x2545F4914F6CDD1D)
+    }
+
+    fn next_key_chain(&mut self) -> Vec<K> {
+        let depth = (self.next_u64() % self.max_depth as u64) as usize + 1;
+
+        (0..depth)
+            .map(|_| {
+                let i = (self.next_u64() % self.keys.len() as u64) as usize;
+
+                self.keys[i].clone()
+            })
+            .collect()
+    }
+
+    /// Generate an operation. Half of the operations are `Insert`s, whose values are created from random numbers by `value`.
+    pub fn next_operation<V, F: FnOnce(u64) -> V>(&mut self, value: F) -> Operation<K, V> {
+        let kind = self.next_u64() % 4;
+        let key_chain = self.next_key_chain();
+
+        match kind {
+            0 | 1 => {
+                let r = self.next_u64();
+
+                Operation::Insert {
+                    key_chain,
+                    value: value(r),
+                }
+            },
+            2 => Operation::Remove {
+                key_chain,
+            },
+            _ => Operation::Get {
+                key_chain,
+            },
+        }
+    }
+}
+
+/// A difference between a `LeveledHashMap` and a `ReferenceModel` found by `run_differential`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Mismatch<K, V> {
+    /// The outcomes of an operation are different.
+    Outcome {
+        step:      usize,
+        operation: Operation<K, V>,
+        expected:  Outcome<V>,
+        actual:    Outcome<V>,
+    },
+    /// The entries are different after an operation.
+    State { step: usize, operation: Operation<K, V> },
+}
+
+/// Apply `count` random operations to a new `LeveledHashMap` and a new `ReferenceModel`, checking the outcome and the entries after every operation. The values are small numbers so that overwritten values are often equal.
+pub fn run_differential<K: Eq + Hash + Clone>(
+    seed: u64,
+    count: usize,
+    keys: &[K],
+    max_depth: usize,
+) -> Result<(), Mismatch<K, u64>> {
+    let mut generator = OperationGenerator::new(seed, keys.to_vec(), max_depth);
+
+    let mut map = LeveledHashMap::new();
+    let mut model = ReferenceModel::new();
+
+    for step in 0..count {
+        let operation = generator.next_operation(|r| r % 8);
+
+        let expected = model.apply(&operation);
+        let actual = apply_to_map(&mut map, &operation);
+
+        if expected != actual {
+            return Err(Mismatch::Outcome {
+                step,
+                operation,
+                expected,
+                actual,
+            });
+        }
+
+        if !is_equivalent(&map, &model) {
+            return Err(Mismatch::State {
+                step,
+                operation,
+            });
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,533 @@
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::{self, Debug, Formatter},
+    hash::Hash,
+    iter::FusedIterator,
+    sync::Arc,
+    vec,
+};
+
+use crate::{Iter, LeveledHashMap, LeveledHashMapError};
+
+/// A subtree which has been removed by `LeveledHashMap::soft_remove` while tombstones are enabled. It is hidden from all reads and iterators of the map, and is kept until `LeveledHashMap::compact` is called.
+#[derive(Debug, Clone)]
+pub struct Tombstone<K, V> {
+    /// The key chain of the removed entry, which starts at Level 0.
+    pub key_chain:   Vec<Arc<K>>,
+    /// The value of the removed entry.
+    pub value:       V,
+    /// The descendants of the removed entry. `descendants[n]` holds the entries which were n + 1 levels below it, along with their parent keys.
+    pub descendants: Vec<HashMap<Arc<K>, (Option<Arc<K>>, V)>>,
+}
+
+/// The tombstones kept by a `LeveledHashMap` while tombstones are enabled.
+pub(crate) struct Tombstones<K, V> {
+    kept:        Vec<Tombstone<K, V>>,
+    /// Copy the values of subtrees removed by the methods which return them, such as `remove`.
+    clone_value: fn(&V) -> V,
+}
+
+impl<K: Debug, V: Debug> Debug for Tombstones<K, V> {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let mut s = f.debug_struct("Tombstones");
+        s.field("Kept", &self.kept);
+        s.finish()
+    }
+}
+
+impl<K, V> Tombstone<K, V> {
+    /// The number of entries in the removed subtree, including its root.
+    #[inline]
+    pub fn len(&self) -> usize {
+        1 + self.descendants.iter().map(|m| m.len()).sum::<usize>()
+    }
+
+    /// A tombstone always holds at least its root entry, so it is never empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        false
+    }
+}
+
+impl<K: Eq + Hash, V> Tombstone<K, V> {
+    /// Get a value in the removed subtree by a key chain which starts at Level 0.
+    fn get(&self, key_chain: &[Arc<K>]) -> Option<&V> {
+        let base = self.key_chain.len();
+
+        if key_chain.len() < base || key_chain[..base] != self.key_chain[..] {
+            return None;
+        }
+
+        let mut value = &self.value;
+
+        for (i, key) in key_chain[base..].iter().enumerate() {
+            let (pk, v) = self.descendants.get(i)?.get(key)?;
+
+            if pk.as_ref() != Some(&key_chain[base + i - 1]) {
+                return None;
+            }
+
+            value = v;
+        }
+
+        Some(value)
+    }
+
+    /// Get all entries in the removed subtree, parents before their children, with key chains which start at Level 0.
+    fn entries(&self) -> Vec<(Vec<Arc<K>>, &V)> {
+        let mut entries = Vec::with_capacity(self.len());
+        let mut parents: HashMap<&Arc<K>, Vec<Arc<K>>> = HashMap::new();
+
+        entries.push((self.key_chain.clone(), &self.value));
+        parents.insert(self.key_chain.last().unwrap(), self.key_chain.clone());
+
+        for map in self.descendants.iter() {
+            let mut chains = HashMap::with_capacity(map.len());
+
+            for (k, (pk, v)) in map.iter() {
+                let mut key_chain =
+                    pk.as_ref().and_then(|pk| parents.get(pk)).cloned().unwrap_or_default();
+
+                key_chain.push(Arc::clone(k));
+
+                entries.push((key_chain.clone(), v));
+                chains.insert(k, key_chain);
+            }
+
+            parents = chains;
+        }
+
+        entries
+    }
+}
+
+/// The state of an entry yielded by `LeveledHashMap::iter_all_states`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EntryState {
+    /// The entry is in the map.
+    Live,
+    /// The entry has been soft-removed and is kept as a part of a `Tombstone`.
+    Deleted,
+}
+
+/// An iterator over the live entries and the soft-removed entries of a `LeveledHashMap`, created by `LeveledHashMap::iter_all_states`. It yields `(key_chain, value, state)` tuples, live entries first.
+#[derive(Debug)]
+pub struct AllStatesIter<'a, K, V> {
+    live:    Iter<'a, K, V>,
+    deleted: vec::IntoIter<(Vec<Arc<K>>, &'a V)>,
+}
+
+impl<'a, K: Eq + Hash, V> Iterator for AllStatesIter<'a, K, V> {
+    type Item = (Vec<Arc<K>>, &'a V, EntryState);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.live.next() {
+            Some((key_chain, v)) => Some((key_chain, v, EntryState::Live)),
+            None => self.deleted.next().map(|(key_chain, v)| (key_chain, v, EntryState::Deleted)),
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.live.len() + self.deleted.len();
+
+        (len, Some(len))
+    }
+}
+
+impl<'a, K: Eq + Hash, V> ExactSizeIterator for AllStatesIter<'a, K, V> {
+    #[inline]
+    fn len(&self) -> usize {
+        self.live.len() + self.deleted.len()
+    }
+}
+
+impl<'a, K: Eq + Hash, V> FusedIterator for AllStatesIter<'a, K, V> {}
+
+impl<K: Eq + Hash, V> LeveledHashMap<K, V> {
+    /// Enable tombstones. After that, subtrees removed by `soft_remove` or by the removing methods such as `remove` are kept aside instead of being dropped, until `compact` is called. The removing methods which return the removed subtree keep a clone of it.
+    #[inline]
+    pub fn enable_tombstones(&mut self)
+    where
+        V: Clone, {
+        if self.tombstones.is_none() {
+            self.tombstones = Some(Tombstones {
+                kept: Vec::new(), clone_value: V::clone
+            });
+        }
+    }
+
+    /// Disable tombstones and return the subtrees which are still kept.
+    #[inline]
+    pub fn disable_tombstones(&mut self) -> Vec<Tombstone<K, V>> {
+        self.tombstones.take().map(|t| t.kept).unwrap_or_default()
+    }
+
+    /// Whether tombstones are enabled.
+    #[inline]
+    pub fn is_tombstones_enabled(&self) -> bool {
+        self.tombstones.is_some()
+    }
+
+    /// Get the subtrees which have been soft-removed but not compacted yet, oldest first.
+    #[inline]
+    pub fn tombstones(&self) -> &[Tombstone<K, V>] {
+        self.tombstones.as_ref().map(|t| t.kept.as_slice()).unwrap_or(&[])
+    }
+
+    /// Keep a clone of a removed subtree as a tombstone if tombstones are enabled.
+    pub(crate) fn keep_tombstone(
+        &mut self,
+        key_chain: Vec<Arc<K>>,
+        value: &V,
+        descendants: &[HashMap<Arc<K>, (Option<Arc<K>>, V)>],
+    ) {
+        if let Some(tombstones) = self.tombstones.as_mut() {
+            let clone_value = tombstones.clone_value;
+
+            tombstones.kept.push(Tombstone {
+                key_chain,
+                value: clone_value(value),
+                descendants: descendants
+                    .iter()
+                    .map(|m| {
+                        m.iter()
+                            .map(|(k, (pk, v))| (Arc::clone(k), (pk.clone(), clone_value(v))))
+                            .collect()
+                    })
+                    .collect(),
+            });
+        }
+    }
+
+    /// Remove an entry and its descendants by a key chain which starts at Level 0, and keep the removed subtree as a `Tombstone` until `compact` is called. Unlike `remove`, the removed values are not returned, so they are not cloned. It returns `Err(LeveledHashMapError::TombstonesDisabled)` if tombstones are not enabled.
+    /// ```
+    /// use std::sync::Arc;
+    ///
+    /// use leveled_hash_map::LeveledHashMap;
+    ///
+    /// let mut map: LeveledHashMap<&'static str, u8> = LeveledHashMap::new();
+    ///
+    /// map.enable_tombstones();
+    ///
+    /// map.insert(&[Arc::new("food")], 1).unwrap();
+    /// map.insert(&[Arc::new("food"), Arc::new("dessert")], 2).unwrap();
+    ///
+    /// map.soft_remove(&[Arc::new("food")]).unwrap();
+    ///
+    /// assert_eq!(None, map.get(&[Arc::new("food")]));
+    /// assert_eq!(1, map.tombstones().len());
+    /// assert_eq!(2, map.tombstones()[0].len());
+    ///
+    /// assert_eq!(2, map.compact());
+    /// assert!(map.tombstones().is_empty());
+    /// ```
+    pub fn soft_remove(&mut self, key_chain: &[Arc<K>]) -> Result<(), LeveledHashMapError<K>> {
+        if key_chain.is_empty() {
+            return Err(LeveledHashMapError::KeyChainEmpty);
+        }
+
+        if self.tombstones.is_none() {
+            return Err(LeveledHashMapError::TombstonesDisabled);
+        }
+
+        self.get_inner(key_chain, 0)?;
+
+        let level = key_chain.len() - 1;
+
+        let key_chain = {
+            let key = self.pool[level].get_key_value(&key_chain[level]).unwrap().0;
+
+            self.key_chain_of(level, key)
+        };
+
+        #[cfg(feature = "stats")]
+        self.record_access(level, &key_chain[level], true);
+
+        #[cfg(feature = "log")]
+        let slow_op_key_chain = self.slow_op_key_chain(level, &key_chain[level]);
+
+        let (_, value, descendants) = self.remove_node(level, &key_chain[level]);
+
+        #[cfg(feature = "log")]
+        self.report_slow_op(
+            "remove",
+            slow_op_key_chain,
+            1 + descendants.iter().map(|m| m.len()).sum::<usize>(),
+        );
+
+        self.tombstones.as_mut().unwrap().kept.push(Tombstone {
+            key_chain,
+            value,
+            descendants,
+        });
+
+        Ok(())
+    }
+
+    /// Get a value by a key chain which starts at Level 0, looking into the tombstones if the entry is not in the map. The most recently soft-removed entry wins if the same key chain has been removed more than once.
+    /// ```
+    /// use std::sync::Arc;
+    ///
+    /// use leveled_hash_map::LeveledHashMap;
+    ///
+    /// let mut map: LeveledHashMap<&'static str, u8> = LeveledHashMap::new();
+    ///
+    /// map.enable_tombstones();
+    ///
+    /// map.insert(&[Arc::new("food")], 1).unwrap();
+    /// map.insert(&[Arc::new("food"), Arc::new("dessert")], 2).unwrap();
+    ///
+    /// map.soft_remove(&[Arc::new("food")]).unwrap();
+    ///
+    /// assert_eq!(None, map.get(&[Arc::new("food"), Arc::new("dessert")]));
+    /// assert_eq!(
+    ///     Some(&2),
+    ///     map.get_including_deleted(&[Arc::new("food"), Arc::new("dessert")])
+    /// );
+    /// ```
+    pub fn get_including_deleted(&self, key_chain: &[Arc<K>]) -> Option<&V> {
+        if let Ok((_, v)) = self.get_inner(key_chain, 0) {
+            return Some(v);
+        }
+
+        self.tombstones().iter().rev().find_map(|t| t.get(key_chain))
+    }
+
+    /// Get an iterator over all entries including the soft-removed ones. It yields `(key_chain, value, state)` tuples, where every key chain starts at Level 0. Live entries come first in the order of `iter`, followed by the entries of each tombstone, oldest first and parents before their children.
+    /// ```
+    /// use std::sync::Arc;
+    ///
+    /// use leveled_hash_map::{EntryState, LeveledHashMap};
+    ///
+    /// let mut map: LeveledHashMap<&'static str, u8> = LeveledHashMap::new();
+    ///
+    /// map.enable_tombstones();
+    ///
+    /// map.insert(&[Arc::new("food")], 1).unwrap();
+    /// map.insert(&[Arc::new("food"), Arc::new("dessert")], 2).unwrap();
+    /// map.insert(&[Arc::new("animal")], 3).unwrap();
+    ///
+    /// map.soft_remove(&[Arc::new("food")]).unwrap();
+    ///
+    /// let states: Vec<(u8, EntryState)> =
+    ///     map.iter_all_states().map(|(_, v, s)| (*v, s)).collect();
+    ///
+    /// assert_eq!(
+    ///     vec![
+    ///         (3, EntryState::Live),
+    ///         (1, EntryState::Deleted),
+    ///         (2, EntryState::Deleted)
+    ///     ],
+    ///     states
+    /// );
+    /// ```
+    pub fn iter_all_states(&self) -> AllStatesIter<'_, K, V> {
+        AllStatesIter {
+            live:    self.iter(),
+            deleted: self
+                .tombstones()
+                .iter()
+                .flat_map(|t| t.entries())
+                .collect::<Vec<_>>()
+                .into_iter(),
+        }
+    }
+
+    /// Re-attach the most recently soft-removed subtree whose root has a specific key chain which starts at Level 0. The parent of the root needs to exist, and none of the keys in the subtree may have been inserted or reserved at their levels in the meantime. The subtree is also checked against the level limits and the allowed value types, as insertion does. On failure, the tombstone is kept.
+    /// ```
+    /// use std::sync::Arc;
+    ///
+    /// use leveled_hash_map::{LeveledHashMap, LeveledHashMapError};
+    ///
+    /// let mut map: LeveledHashMap<&'static str, u8> = LeveledHashMap::new();
+    ///
+    /// map.enable_tombstones();
+    ///
+    /// map.insert(&[Arc::new("food")], 1).unwrap();
+    /// map.insert(&[Arc::new("food"), Arc::new("dessert")], 2).unwrap();
+    /// map.insert(&[Arc::new("food"), Arc::new("dessert"), Arc::new("cake")], 3)
+    ///     .unwrap();
+    ///
+    /// map.soft_remove(&[Arc::new("food"), Arc::new("dessert")]).unwrap();
+    ///
+    /// map.insert(&[Arc::new("food"), Arc::new("bread")], 4).unwrap();
+    /// map.insert(&[Arc::new("food"), Arc::new("bread"), Arc::new("cake")], 5)
+    ///     .unwrap();
+    ///
+    /// match map.restore(&[Arc::new("food"), Arc::new("dessert")]) {
+    ///     Err(LeveledHashMapError::KeyDuplicated {
+    ///         level,
+    ///         key,
+    ///     }) => {
+    ///         assert_eq!(2, level);
+    ///         assert_eq!(Arc::new("cake"), key);
+    ///     },
+    ///     _ => unreachable!(),
+    /// }
+    ///
+    /// map.remove(&[Arc::new("food"), Arc::new("bread")]).unwrap();
+    ///
+    /// map.restore(&[Arc::new("food"), Arc::new("dessert")]).unwrap();
+    ///
+    /// assert_eq!(
+    ///     Some(&3),
+    ///     map.get(&[Arc::new("food"), Arc::new("dessert"), Arc::new("cake")])
+    /// );
+    ///
+    /// // only the removed "food/bread" is still kept
+    /// assert_eq!(1, map.tombstones().len());
+    /// ```
+    pub fn restore(&mut self, key_chain: &[Arc<K>]) -> Result<(), LeveledHashMapError<K>> {
+        if key_chain.is_empty() {
+            return Err(LeveledHashMapError::KeyChainEmpty);
+        }
+
+        let level = key_chain.len() - 1;
+
+        let index =
+            self.tombstones().iter().rposition(|t| t.key_chain[..] == key_chain[..]).ok_or_else(
+                || LeveledHashMapError::KeyNotExist {
+                    level,
+                    key: Arc::clone(&key_chain[level]),
+                },
+            )?;
+
+        if level > 0 {
+            self.get_inner(&key_chain[..level], 0)?;
+        }
+
+        {
+            let tombstone = &self.tombstones()[index];
+
+            let mut levels: Vec<Vec<&Arc<K>>> = vec![vec![&tombstone.key_chain[level]]];
+
+            levels.extend(tombstone.descendants.iter().map(|m| m.keys().collect()));
+
+            for (l, keys) in levels.into_iter().enumerate() {
+                let l = level + l;
+
+                for key in keys {
+                    if let Some((k, _)) = self.pool.get(l).and_then(|m| m.get_key_value(key)) {
+                        return Err(LeveledHashMapError::KeyDuplicated {
+                            level: l,
+                            key:   Arc::clone(k),
+                        });
+                    }
+
+                    self.check_reserved(l, key)?;
+                }
+            }
+
+            let root_key = &tombstone.key_chain[level];
+
+            if !self.is_value_allowed(level, &tombstone.value) {
+                return Err(LeveledHashMapError::ValueNotAllowed {
+                    level,
+                    key: Arc::clone(root_key),
+                });
+            }
+
+            self.check_limits(
+                level,
+                if level > 0 { Some(&key_chain[level - 1]) } else { None },
+                1,
+            )?;
+
+            for (i, map) in tombstone.descendants.iter().enumerate() {
+                let l = level + i + 1;
+
+                self.check_limits(l, None, map.len())?;
+
+                let mut children: HashMap<&Arc<K>, usize> = HashMap::new();
+
+                for (k, (pk, v)) in map {
+                    if !self.is_value_allowed(l, v) {
+                        return Err(LeveledHashMapError::ValueNotAllowed {
+                            level: l,
+                            key:   Arc::clone(k),
+                        });
+                    }
+
+                    if let Some(pk) = pk.as_ref() {
+                        *children.entry(pk).or_insert(0) += 1;
+                    }
+                }
+
+                // the restored parents are new entries, so only the restored children count
+                if let Some(limit) = self.level_limits(l - 1).max_children {
+                    if let Some((pk, _)) = children.into_iter().find(|(_, n)| *n > limit) {
+                        return Err(LeveledHashMapError::TooManyChildren {
+                            level: l - 1,
+                            key: Arc::clone(pk),
+                            limit,
+                        });
+                    }
+                }
+            }
+        }
+
+        let Tombstone {
+            key_chain,
+            value,
+            descendants,
+        } = self.tombstones.as_mut().unwrap().kept.remove(index);
+
+        let depth = level + descendants.len() + 1;
+
+        while self.pool.len() < depth {
+            self.pool.push(HashMap::new());
+            self.sub.push(HashMap::new());
+        }
+
+        let key = Arc::clone(&key_chain[level]);
+
+        let parent = if level > 0 {
+            let pk =
+                Arc::clone(self.pool[level - 1].get_key_value(&key_chain[level - 1]).unwrap().0);
+
+            self.sub[level - 1].get_mut(&pk).unwrap().insert(Arc::clone(&key));
+
+            Some(pk)
+        } else {
+            None
+        };
+
+        self.pool[level].insert(Arc::clone(&key), (parent, value));
+        self.sub[level].insert(Arc::clone(&key), HashSet::new());
+
+        if let Some(generations) = self.generations.as_mut() {
+            generations.mark_changed(level, &key);
+        }
+
+        for (i, map) in descendants.into_iter().enumerate() {
+            let l = level + i + 1;
+
+            for (k, (pk, v)) in map {
+                if let Some(pk) = pk.as_ref() {
+                    self.sub[l - 1].get_mut(pk).unwrap().insert(Arc::clone(&k));
+                }
+
+                if let Some(generations) = self.generations.as_mut() {
+                    generations.mark_changed(l, &k);
+                }
+
+                self.sub[l].insert(Arc::clone(&k), HashSet::new());
+                self.pool[l].insert(k, (pk, v));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Drop all tombstones physically, and return the number of entries which are dropped.
+    #[inline]
+    pub fn compact(&mut self) -> usize {
+        match self.tombstones.as_mut() {
+            Some(tombstones) => tombstones.kept.drain(..).map(|t| t.len()).sum(),
+            None => 0,
+        }
+    }
+}
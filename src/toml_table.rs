@@ -0,0 +1,82 @@
+use std::{collections::HashMap, fmt::Display, hash::Hash, str::FromStr};
+
+use serde::de::{DeserializeOwned, Error as _};
+use toml_crate::{de::Error, Table, Value};
+
+use crate::{LeveledHashMap, Node};
+
+impl<K: Eq + Hash, V> LeveledHashMap<K, V> {
+    /// Create a map from a TOML document where nested tables become levels. Every key of a table becomes an entry at the level of the table. A table is an entry whose value is `V::default()` and whose children are the items of the table, and any other item is an entry without children whose value is deserialized into `V`.
+    /// ```
+    /// use std::sync::Arc;
+    ///
+    /// use leveled_hash_map::LeveledHashMap;
+    ///
+    /// let toml = r#"
+    /// name = "app"
+    ///
+    /// [server]
+    /// host = "localhost"
+    ///
+    /// [server.tls]
+    /// cert = "cert.pem"
+    /// "#;
+    ///
+    /// let map: LeveledHashMap<String, String> =
+    ///     LeveledHashMap::from_toml_str(toml).unwrap();
+    ///
+    /// assert_eq!(
+    ///     Some(&"app".to_string()),
+    ///     map.get(&[Arc::new("name".to_string())])
+    /// );
+    /// assert_eq!(
+    ///     Some(&String::new()),
+    ///     map.get(&[Arc::new("server".to_string())])
+    /// );
+    /// assert_eq!(
+    ///     Some(&"cert.pem".to_string()),
+    ///     map.get(&[
+    ///         Arc::new("server".to_string()),
+    ///         Arc::new("tls".to_string()),
+    ///         Arc::new("cert".to_string())
+    ///     ])
+    /// );
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "toml")))]
+    pub fn from_toml_str(s: &str) -> Result<Self, Error>
+    where
+        K: FromStr,
+        K::Err: Display,
+        V: DeserializeOwned + Default, {
+        let table: Table = toml_crate::from_str(s)?;
+
+        let tree = toml_table_to_tree(table)?;
+
+        LeveledHashMap::from_tree(tree).map_err(Error::custom)
+    }
+}
+
+/// Convert the items of a TOML table into nodes.
+fn toml_table_to_tree<K: Eq + Hash + FromStr, V: DeserializeOwned + Default>(
+    table: Table,
+) -> Result<HashMap<K, Node<K, V>>, Error>
+where
+    K::Err: Display, {
+    let mut tree = HashMap::with_capacity(table.len());
+
+    for (name, item) in table {
+        let key = name.parse::<K>().map_err(Error::custom)?;
+
+        let node = match item {
+            Value::Table(table) => Node {
+                value:    V::default(),
+                children: toml_table_to_tree(table)?,
+            },
+            item => Node::new(item.try_into()?),
+        };
+
+        tree.insert(key, node);
+    }
+
+    Ok(tree)
+}
@@ -0,0 +1,146 @@
+use std::{hash::Hash, sync::Arc};
+
+use crate::{LeveledHashMap, LeveledHashMapError};
+
+/// A reusable depth-first traversal over `LeveledHashMap` instances. It owns the path and stack buffers of the traversal, so walking subtrees repeatedly with the same `Traverser` does not allocate once the buffers have grown large enough.
+#[derive(Debug, Clone)]
+pub struct Traverser<K> {
+    path:  Vec<Arc<K>>,
+    stack: Vec<(usize, Arc<K>)>,
+}
+
+impl<K> Traverser<K> {
+    /// Create a new `Traverser` instance with empty buffers.
+    #[inline]
+    pub fn new() -> Traverser<K> {
+        Traverser {
+            path: Vec::new(), stack: Vec::new()
+        }
+    }
+
+    /// Create a new `Traverser` instance whose buffers can hold the key chains of `depth` levels and `pending` entries waiting to be visited without reallocating.
+    #[inline]
+    pub fn with_capacity(depth: usize, pending: usize) -> Traverser<K> {
+        Traverser {
+            path: Vec::with_capacity(depth), stack: Vec::with_capacity(pending)
+        }
+    }
+}
+
+impl<K> Default for Traverser<K> {
+    #[inline]
+    fn default() -> Self {
+        Traverser::new()
+    }
+}
+
+impl<K: Eq + Hash> Traverser<K> {
+    /// Visit an entry and all of its descendants by a key chain which starts at Level 0 in pre-order (parents before their children). If the key chain is empty, the whole map is visited. The closure receives the key chain of each entry, which starts at Level 0, and its value.
+    /// ```
+    /// use std::sync::Arc;
+    ///
+    /// use leveled_hash_map::{LeveledHashMap, Traverser};
+    ///
+    /// let mut map: LeveledHashMap<&'static str, u8> = LeveledHashMap::new();
+    ///
+    /// map.insert(&[Arc::new("food")], 1).unwrap();
+    /// map.insert(&[Arc::new("food"), Arc::new("dessert")], 2).unwrap();
+    /// map.insert(&[Arc::new("animal")], 3).unwrap();
+    ///
+    /// let mut traverser = Traverser::new();
+    ///
+    /// for _ in 0..3 {
+    ///     let mut sum = 0;
+    ///
+    ///     traverser.walk(&map, &[Arc::new("food")], |_, v| sum += *v).unwrap();
+    ///
+    ///     assert_eq!(3, sum);
+    /// }
+    /// ```
+    pub fn walk<V, F: FnMut(&[Arc<K>], &V)>(
+        &mut self,
+        map: &LeveledHashMap<K, V>,
+        key_chain: &[Arc<K>],
+        mut f: F,
+    ) -> Result<(), LeveledHashMapError<K>> {
+        self.search(map, key_chain, |key_chain, v| {
+            f(key_chain, v);
+
+            false
+        })
+        .map(|_| ())
+    }
+
+    /// Search an entry and all of its descendants by a key chain which starts at Level 0 in pre-order (parents before their children), and get the key chain of the first entry accepted by the closure. If the key chain is empty, the whole map is searched.
+    /// ```
+    /// use std::sync::Arc;
+    ///
+    /// use leveled_hash_map::{LeveledHashMap, Traverser};
+    ///
+    /// let mut map: LeveledHashMap<&'static str, u8> = LeveledHashMap::new();
+    ///
+    /// map.insert(&[Arc::new("food")], 1).unwrap();
+    /// map.insert(&[Arc::new("food"), Arc::new("dessert")], 2).unwrap();
+    /// map.insert(&[Arc::new("animal")], 3).unwrap();
+    ///
+    /// let mut traverser = Traverser::new();
+    ///
+    /// assert_eq!(
+    ///     Some(vec![Arc::new("food"), Arc::new("dessert")]),
+    ///     traverser.find(&map, &[], |_, v| *v == 2).unwrap()
+    /// );
+    /// assert_eq!(
+    ///     None,
+    ///     traverser.find(&map, &[Arc::new("food")], |_, v| *v == 3).unwrap()
+    /// );
+    /// ```
+    pub fn find<V, F: FnMut(&[Arc<K>], &V) -> bool>(
+        &mut self,
+        map: &LeveledHashMap<K, V>,
+        key_chain: &[Arc<K>],
+        f: F,
+    ) -> Result<Option<Vec<Arc<K>>>, LeveledHashMapError<K>> {
+        Ok(if self.search(map, key_chain, f)? { Some(self.path.clone()) } else { None })
+    }
+
+    /// Search in pre-order until the closure accepts an entry, whose key chain is then left in the path buffer.
+    fn search<V, F: FnMut(&[Arc<K>], &V) -> bool>(
+        &mut self,
+        map: &LeveledHashMap<K, V>,
+        key_chain: &[Arc<K>],
+        mut f: F,
+    ) -> Result<bool, LeveledHashMapError<K>> {
+        self.path.clear();
+        self.stack.clear();
+
+        if key_chain.is_empty() {
+            if let Some(m) = map.pool.first() {
+                self.stack.extend(m.keys().map(|k| (0, Arc::clone(k))));
+            }
+        } else {
+            map.get_inner(key_chain, 0)?;
+
+            let level = key_chain.len() - 1;
+
+            self.path.extend(key_chain[..level].iter().cloned());
+            self.stack.push((level, Arc::clone(&key_chain[level])));
+        }
+
+        while let Some((level, key)) = self.stack.pop() {
+            let value = &map.pool[level][&key].1;
+
+            self.stack.extend(map.sub[level][&key].iter().map(|c| (level + 1, Arc::clone(c))));
+
+            self.path.truncate(level);
+            self.path.push(key);
+
+            if f(&self.path, value) {
+                self.stack.clear();
+
+                return Ok(true);
+            }
+        }
+
+        Ok(false)
+    }
+}
@@ -0,0 +1,360 @@
+use std::{
+    collections::{HashMap, HashSet},
+    hash::Hash,
+    sync::Arc,
+};
+
+use crate::{LeveledHashMap, LeveledHashMapError};
+
+/// A node of a nested tree structure, which can be converted into a `LeveledHashMap` with `LeveledHashMap::from_tree`.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Node<K: Eq + Hash, V> {
+    /// The value of the node.
+    pub value:    V,
+    /// The child nodes by their keys.
+    #[cfg_attr(
+        any(feature = "serde", feature = "schemars"),
+        serde(default = "HashMap::<K, Node<K, V>>::new")
+    )]
+    pub children: HashMap<K, Node<K, V>>,
+}
+
+impl<K: Eq + Hash, V> Node<K, V> {
+    /// Create a node without children.
+    #[inline]
+    pub fn new(value: V) -> Node<K, V> {
+        Node {
+            value,
+            children: HashMap::new(),
+        }
+    }
+
+    /// Add a child node and return this node, for building trees in expressions.
+    #[inline]
+    pub fn with_child(mut self, key: K, child: Node<K, V>) -> Node<K, V> {
+        self.children.insert(key, child);
+
+        self
+    }
+}
+
+impl<K: Eq + Hash, V> LeveledHashMap<K, V> {
+    /// Convert a nested tree structure into a map. The keys of the given `HashMap` are placed at Level 0, and the keys of their children at Level 1, and so on. Since keys at the same level need to be unique, a `LeveledHashMapError::KeyDuplicated` error is returned if the same key appears twice at a level.
+    /// ```
+    /// use std::{collections::HashMap, sync::Arc};
+    ///
+    /// use leveled_hash_map::{LeveledHashMap, Node};
+    ///
+    /// let mut tree = HashMap::new();
+    ///
+    /// tree.insert(
+    ///     "food",
+    ///     Node::new(1).with_child(
+    ///         "dessert",
+    ///         Node::new(2).with_child("cake", Node::new(3)),
+    ///     ),
+    /// );
+    ///
+    /// let map = LeveledHashMap::from_tree(tree).unwrap();
+    ///
+    /// assert_eq!(
+    ///     Some(&3),
+    ///     map.get(&[Arc::new("food"), Arc::new("dessert"), Arc::new("cake")])
+    /// );
+    /// ```
+    pub fn from_tree(tree: HashMap<K, Node<K, V>>) -> Result<Self, LeveledHashMapError<K>> {
+        let mut map = LeveledHashMap::new();
+
+        // the nodes waiting to be placed, with their levels and parent keys
+        let mut stack: Vec<(usize, Option<Arc<K>>, K, Node<K, V>)> =
+            tree.into_iter().map(|(key, node)| (0, None, key, node)).collect();
+
+        while let Some((level, parent_key, key, node)) = stack.pop() {
+            if map.pool.len() == level {
+                map.pool.push(HashMap::new());
+                map.sub.push(HashMap::new());
+            }
+
+            if let Some((k, _)) = map.pool[level].get_key_value(&key) {
+                return Err(LeveledHashMapError::KeyDuplicated {
+                    level,
+                    key: Arc::clone(k),
+                });
+            }
+
+            let key = Arc::new(key);
+
+            if let Some(parent_key) = parent_key.as_ref() {
+                map.sub[level - 1].get_mut(parent_key).unwrap().insert(Arc::clone(&key));
+            }
+
+            map.sub[level].insert(Arc::clone(&key), HashSet::new());
+            map.pool[level].insert(Arc::clone(&key), (parent_key, node.value));
+
+            stack.extend(
+                node.children.into_iter().map(|(child_key, child)| {
+                    (level + 1, Some(Arc::clone(&key)), child_key, child)
+                }),
+            );
+        }
+
+        Ok(map)
+    }
+
+    /// Convert the map into a nested tree structure without cloning the values. The returned `HashMap` holds the entries at Level 0, and each node holds its children.
+    /// ```
+    /// use std::sync::Arc;
+    ///
+    /// use leveled_hash_map::LeveledHashMap;
+    ///
+    /// let mut map: LeveledHashMap<&'static str, u8> = LeveledHashMap::new();
+    ///
+    /// map.insert(&[Arc::new("food")], 1).unwrap();
+    /// map.insert(&[Arc::new("food"), Arc::new("dessert")], 2).unwrap();
+    ///
+    /// let tree = map.to_tree();
+    ///
+    /// let food = &tree[&Arc::new("food")];
+    ///
+    /// assert_eq!(&1, food.value);
+    /// assert_eq!(&2, food.children[&Arc::new("dessert")].value);
+    /// ```
+    pub fn to_tree(&self) -> HashMap<Arc<K>, Node<Arc<K>, &V>> {
+        match self.pool.first() {
+            Some(map) => map.keys().map(|key| (Arc::clone(key), self.to_node(0, key))).collect(),
+            None => HashMap::new(),
+        }
+    }
+
+    /// Build the node of an existing entry along with its descendants.
+    fn to_node(&self, level: usize, key: &Arc<K>) -> Node<Arc<K>, &V> {
+        Node {
+            value:    &self.pool[level][key].1,
+            children: self.sub[level][key]
+                .iter()
+                .map(|c| (Arc::clone(c), self.to_node(level + 1, c)))
+                .collect(),
+        }
+    }
+
+    /// Convert the map into a nested tree structure, moving the values. The returned `HashMap` holds the entries at Level 0, and each node holds its children.
+    /// ```
+    /// use std::sync::Arc;
+    ///
+    /// use leveled_hash_map::LeveledHashMap;
+    ///
+    /// let mut map: LeveledHashMap<&'static str, String> = LeveledHashMap::new();
+    ///
+    /// map.insert(&[Arc::new("food")], "食物".to_string()).unwrap();
+    /// map.insert(&[Arc::new("food"), Arc::new("dessert")], "甜點".to_string())
+    ///     .unwrap();
+    ///
+    /// let mut tree = map.into_tree();
+    ///
+    /// let food = tree.remove(&Arc::new("food")).unwrap();
+    ///
+    /// assert_eq!("食物", food.value);
+    /// assert_eq!("甜點", food.children[&Arc::new("dessert")].value);
+    /// ```
+    pub fn into_tree(self) -> HashMap<Arc<K>, Node<Arc<K>, V>> {
+        let mut roots = HashMap::new();
+
+        // the nodes built at the level below, grouped by their parent keys
+        let mut below: HashMap<Arc<K>, HashMap<Arc<K>, Node<Arc<K>, V>>> = HashMap::new();
+
+        for map in self.pool.into_iter().rev() {
+            let mut current: HashMap<Arc<K>, HashMap<Arc<K>, Node<Arc<K>, V>>> = HashMap::new();
+
+            for (key, (parent_key, value)) in map {
+                let node = Node {
+                    value,
+                    children: below.remove(&key).unwrap_or_default(),
+                };
+
+                match parent_key {
+                    Some(parent_key) => {
+                        current.entry(parent_key).or_default().insert(key, node);
+                    },
+                    None => {
+                        roots.insert(key, node);
+                    },
+                }
+            }
+
+            below = current;
+        }
+
+        roots
+    }
+}
+
+/// Serializes the children of an entry, or the entries at Level 0, as a map of keys to nodes.
+#[cfg(feature = "serde")]
+struct SerializeChildren<'a, K: Eq + Hash, V> {
+    map:    &'a LeveledHashMap<K, V>,
+    level:  usize,
+    parent: Option<&'a Arc<K>>,
+}
+
+/// Serializes an entry as a node with its value and children.
+#[cfg(feature = "serde")]
+struct SerializeNode<'a, K: Eq + Hash, V> {
+    map:   &'a LeveledHashMap<K, V>,
+    level: usize,
+    key:   &'a Arc<K>,
+}
+
+#[cfg(feature = "serde")]
+impl<'a, K: Eq + Hash + serde::Serialize, V: serde::Serialize> serde::Serialize
+    for SerializeChildren<'a, K, V>
+{
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeMap;
+
+        let keys: Vec<&Arc<K>> = match self.parent {
+            Some(parent) => self.map.sub[self.level - 1][parent].iter().collect(),
+            None => self.map.pool.first().map(|m| m.keys().collect()).unwrap_or_default(),
+        };
+
+        let mut s = serializer.serialize_map(Some(keys.len()))?;
+
+        for key in keys {
+            s.serialize_entry(key.as_ref(), &SerializeNode {
+                map: self.map,
+                level: self.level,
+                key,
+            })?;
+        }
+
+        s.end()
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'a, K: Eq + Hash + serde::Serialize, V: serde::Serialize> serde::Serialize
+    for SerializeNode<'a, K, V>
+{
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeStruct;
+
+        let mut s = serializer.serialize_struct("Node", 2)?;
+
+        s.serialize_field("value", &self.map.pool[self.level][self.key].1)?;
+        s.serialize_field("children", &SerializeChildren {
+            map:    self.map,
+            level:  self.level + 1,
+            parent: Some(self.key),
+        })?;
+
+        s.end()
+    }
+}
+
+/// The map is serialized in the nested representation of `Node`, as a map of the keys at Level 0 to nodes which hold their values and children. It is streamed without being converted into a tree first.
+/// ```
+/// use std::sync::Arc;
+///
+/// use leveled_hash_map::LeveledHashMap;
+///
+/// let mut map: LeveledHashMap<&'static str, u8> = LeveledHashMap::new();
+///
+/// map.insert(&[Arc::new("food")], 1).unwrap();
+/// map.insert(&[Arc::new("food"), Arc::new("dessert")], 2).unwrap();
+///
+/// assert_eq!(
+///     r#"{"food":{"value":1,"children":{"dessert":{"value":2,"children":{}}}}}"#,
+///     serde_json::to_string(&map).unwrap()
+/// );
+/// ```
+#[cfg(feature = "serde")]
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+impl<K: Eq + Hash + serde::Serialize, V: serde::Serialize> serde::Serialize
+    for LeveledHashMap<K, V>
+{
+    #[inline]
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        SerializeChildren {
+            map: self, level: 0, parent: None
+        }
+        .serialize(serializer)
+    }
+}
+
+/// The map is deserialized from the nested representation of `Node` with `LeveledHashMap::from_tree`, so a key which appears twice at the same level is rejected. The `children` of a node can be omitted.
+/// ```
+/// use std::sync::Arc;
+///
+/// use leveled_hash_map::LeveledHashMap;
+///
+/// let map: LeveledHashMap<String, u8> = serde_json::from_str(
+///     r#"{"food":{"value":1,"children":{"dessert":{"value":2}}}}"#,
+/// )
+/// .unwrap();
+///
+/// assert_eq!(
+///     Some(&2),
+///     map.get(&[
+///         Arc::new("food".to_string()),
+///         Arc::new("dessert".to_string())
+///     ])
+/// );
+///
+/// assert!(serde_json::from_str::<LeveledHashMap<String, u8>>(
+///     r#"{"food":{"value":1,"children":{"food":{"value":2}}}}"#,
+/// )
+/// .is_ok());
+///
+/// assert!(serde_json::from_str::<LeveledHashMap<String, u8>>(
+///     r#"{"food":{"value":1,"children":{"cake":{"value":2}}},"drink":{"value":3,"children":{"cake":{"value":4}}}}"#,
+/// )
+/// .is_err());
+/// ```
+#[cfg(feature = "serde")]
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+impl<'de, K: Eq + Hash + serde::Deserialize<'de>, V: serde::Deserialize<'de>>
+    serde::Deserialize<'de> for LeveledHashMap<K, V>
+{
+    #[inline]
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let tree: HashMap<K, Node<K, V>> = serde::Deserialize::deserialize(deserializer)?;
+
+        LeveledHashMap::from_tree(tree).map_err(serde::de::Error::custom)
+    }
+}
+
+/// The schema describes the nested representation used by the `Serialize` and `Deserialize` implementations, which is a map of the keys at Level 0 to `Node`s.
+/// ```
+/// use leveled_hash_map::LeveledHashMap;
+///
+/// let schema = schemars::schema_for!(LeveledHashMap<String, u8>);
+/// let schema = serde_json::to_value(&schema).unwrap();
+///
+/// assert_eq!("object", schema["type"]);
+/// assert_eq!("#/$defs/Node", schema["additionalProperties"]["$ref"]);
+/// assert_eq!(
+///     serde_json::json!(["value"]),
+///     schema["$defs"]["Node"]["required"]
+/// );
+/// ```
+#[cfg(feature = "schemars")]
+#[cfg_attr(docsrs, doc(cfg(feature = "schemars")))]
+impl<K: Eq + Hash + schemars::JsonSchema, V: schemars::JsonSchema> schemars::JsonSchema
+    for LeveledHashMap<K, V>
+{
+    #[inline]
+    fn inline_schema() -> bool {
+        true
+    }
+
+    #[inline]
+    fn schema_name() -> std::borrow::Cow<'static, str> {
+        format!("LeveledHashMap_for_{}_and_{}", K::schema_name(), V::schema_name()).into()
+    }
+
+    #[inline]
+    fn json_schema(generator: &mut schemars::SchemaGenerator) -> schemars::Schema {
+        <HashMap<K, Node<K, V>>>::json_schema(generator)
+    }
+}
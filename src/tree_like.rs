@@ -0,0 +1,182 @@
+use std::{hash::Hash, sync::Arc};
+
+use crate::{LeveledHashMap, LeveledHashSet};
+
+/// A generic view of a forest, so tree algorithms can be written once and applied to `LeveledHashMap`, `LeveledHashSet` and other tree structures.
+/// ```
+/// use std::sync::Arc;
+///
+/// use leveled_hash_map::{LeveledHashMap, TreeLike};
+///
+/// fn height<T: TreeLike>(tree: &T, node: &T::Node) -> usize {
+///     1 + tree
+///         .children(node)
+///         .iter()
+///         .map(|c| height(tree, c))
+///         .max()
+///         .unwrap_or(0)
+/// }
+///
+/// let mut map: LeveledHashMap<&'static str, u8> = LeveledHashMap::new();
+///
+/// map.insert(&[Arc::new("food")], 1).unwrap();
+/// map.insert(&[Arc::new("food"), Arc::new("dessert")], 2).unwrap();
+/// map.insert(&[Arc::new("food"), Arc::new("dessert"), Arc::new("cake")], 3)
+///     .unwrap();
+///
+/// let root = &map.roots()[0];
+///
+/// assert_eq!(3, height(&map, root));
+/// assert_eq!(Some(&1), map.value(root));
+/// ```
+pub trait TreeLike {
+    /// The handle of a node, which is cheap to clone.
+    type Node: Clone;
+    /// The value type carried by nodes.
+    type Value;
+
+    /// Get the nodes without parents.
+    fn roots(&self) -> Vec<Self::Node>;
+
+    /// Get the children of a node.
+    fn children(&self, node: &Self::Node) -> Vec<Self::Node>;
+
+    /// Get the parent of a node, or `None` if it is a root or it does not exist.
+    fn parent(&self, node: &Self::Node) -> Option<Self::Node>;
+
+    /// Get the value of a node, or `None` if it does not exist.
+    fn value(&self, node: &Self::Node) -> Option<&Self::Value>;
+
+    /// Check whether a node has no children.
+    #[inline]
+    fn is_leaf(&self, node: &Self::Node) -> bool {
+        self.children(node).is_empty()
+    }
+}
+
+/// The handle of an entry in a `LeveledHashMap` or a `LeveledHashSet`, which is its level and key. Since keys at the same level are unique, it identifies the entry without its whole key chain.
+#[derive(Debug, PartialEq, Eq, Hash)]
+pub struct NodeRef<K> {
+    /// The level of the entry.
+    pub level: usize,
+    /// The key of the entry.
+    pub key:   Arc<K>,
+}
+
+impl<K> Clone for NodeRef<K> {
+    #[inline]
+    fn clone(&self) -> Self {
+        NodeRef {
+            level: self.level, key: Arc::clone(&self.key)
+        }
+    }
+}
+
+impl<K: Eq + Hash, V> LeveledHashMap<K, V> {
+    /// Get the handle of an entry by a key chain which starts at Level 0, for the `TreeLike` methods.
+    /// ```
+    /// use std::sync::Arc;
+    ///
+    /// use leveled_hash_map::{LeveledHashMap, TreeLike};
+    ///
+    /// let mut map: LeveledHashMap<&'static str, u8> = LeveledHashMap::new();
+    ///
+    /// map.insert(&[Arc::new("food")], 1).unwrap();
+    /// map.insert(&[Arc::new("food"), Arc::new("dessert")], 2).unwrap();
+    ///
+    /// let node = map.node(&[Arc::new("food"), Arc::new("dessert")]).unwrap();
+    ///
+    /// assert_eq!(1, node.level);
+    /// assert_eq!(Some(&1), map.parent(&node).and_then(|p| map.value(&p)));
+    /// ```
+    pub fn node(&self, key_chain: &[Arc<K>]) -> Option<NodeRef<K>> {
+        self.get_inner(key_chain, 0).ok()?;
+
+        let level = key_chain.len() - 1;
+
+        self.pool[level].get_key_value(&key_chain[level]).map(|(key, _)| NodeRef {
+            level,
+            key: Arc::clone(key),
+        })
+    }
+}
+
+impl<K: Eq + Hash, V> TreeLike for LeveledHashMap<K, V> {
+    type Node = NodeRef<K>;
+    type Value = V;
+
+    #[inline]
+    fn roots(&self) -> Vec<NodeRef<K>> {
+        match self.pool.first() {
+            Some(map) => map
+                .keys()
+                .map(|key| NodeRef {
+                    level: 0, key: Arc::clone(key)
+                })
+                .collect(),
+            None => Vec::new(),
+        }
+    }
+
+    #[inline]
+    fn children(&self, node: &NodeRef<K>) -> Vec<NodeRef<K>> {
+        match self.sub.get(node.level).and_then(|m| m.get(&node.key)) {
+            Some(children) => children
+                .iter()
+                .map(|key| NodeRef {
+                    level: node.level + 1, key: Arc::clone(key)
+                })
+                .collect(),
+            None => Vec::new(),
+        }
+    }
+
+    #[inline]
+    fn parent(&self, node: &NodeRef<K>) -> Option<NodeRef<K>> {
+        let (pk, _) = self.pool.get(node.level)?.get(&node.key)?;
+
+        pk.as_ref().map(|pk| NodeRef {
+            level: node.level - 1, key: Arc::clone(pk)
+        })
+    }
+
+    #[inline]
+    fn value(&self, node: &NodeRef<K>) -> Option<&V> {
+        self.pool.get(node.level)?.get(&node.key).map(|(_, v)| v)
+    }
+
+    #[inline]
+    fn is_leaf(&self, node: &NodeRef<K>) -> bool {
+        self.sub.get(node.level).and_then(|m| m.get(&node.key)).map_or(true, |c| c.is_empty())
+    }
+}
+
+impl<K: Eq + Hash> TreeLike for LeveledHashSet<K> {
+    type Node = NodeRef<K>;
+    type Value = ();
+
+    #[inline]
+    fn roots(&self) -> Vec<NodeRef<K>> {
+        self.as_map().roots()
+    }
+
+    #[inline]
+    fn children(&self, node: &NodeRef<K>) -> Vec<NodeRef<K>> {
+        self.as_map().children(node)
+    }
+
+    #[inline]
+    fn parent(&self, node: &NodeRef<K>) -> Option<NodeRef<K>> {
+        self.as_map().parent(node)
+    }
+
+    #[inline]
+    fn value(&self, node: &NodeRef<K>) -> Option<&()> {
+        self.as_map().value(node)
+    }
+
+    #[inline]
+    fn is_leaf(&self, node: &NodeRef<K>) -> bool {
+        self.as_map().is_leaf(node)
+    }
+}
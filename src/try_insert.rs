@@ -0,0 +1,113 @@
+use std::{
+    error::Error,
+    fmt::{self, Debug, Display, Formatter},
+    hash::Hash,
+    sync::Arc,
+};
+
+use crate::{LeveledHashMap, LeveledHashMapError};
+
+/// The error returned by `LeveledHashMap::try_insert` when the entry already exists. It carries the rejected value back along with a reference to the current one.
+pub struct OccupiedError<'a, V> {
+    /// The value which is currently in the map.
+    pub current: &'a V,
+    /// The value which was not inserted.
+    pub value:   V,
+}
+
+impl<'a, V: Debug> Debug for OccupiedError<'a, V> {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter) -> Result<(), fmt::Error> {
+        let mut s = f.debug_struct("OccupiedError");
+        s.field("Current", self.current);
+        s.field("Value", &self.value);
+        s.finish()
+    }
+}
+
+impl<'a, V> Display for OccupiedError<'a, V> {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter) -> Result<(), fmt::Error> {
+        f.write_str("The entry already exists.")
+    }
+}
+
+impl<'a, V: Debug> Error for OccupiedError<'a, V> {}
+
+/// Possible errors come from `LeveledHashMap::try_insert`.
+pub enum TryInsertError<'a, K, V> {
+    /// The entry already exists.
+    Occupied(OccupiedError<'a, V>),
+    /// The value cannot be inserted for another reason.
+    Map(LeveledHashMapError<K>),
+}
+
+impl<'a, K, V: Debug> Debug for TryInsertError<'a, K, V> {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter) -> Result<(), fmt::Error> {
+        match self {
+            TryInsertError::Occupied(err) => Debug::fmt(err, f),
+            TryInsertError::Map(err) => Debug::fmt(err, f),
+        }
+    }
+}
+
+impl<'a, K, V> Display for TryInsertError<'a, K, V> {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter) -> Result<(), fmt::Error> {
+        match self {
+            TryInsertError::Occupied(err) => Display::fmt(err, f),
+            TryInsertError::Map(err) => Display::fmt(err, f),
+        }
+    }
+}
+
+impl<'a, K, V: Debug> Error for TryInsertError<'a, K, V> {}
+
+impl<'a, K, V> From<LeveledHashMapError<K>> for TryInsertError<'a, K, V> {
+    #[inline]
+    fn from(err: LeveledHashMapError<K>) -> Self {
+        TryInsertError::Map(err)
+    }
+}
+
+impl<K: Eq + Hash, V> LeveledHashMap<K, V> {
+    /// Insert a value by a key chain which starts at Level 0 only if the entry does not exist yet, and get a mutable reference to the inserted value. If the entry already exists, nothing is changed and the value is given back in an `OccupiedError` along with a reference to the current value.
+    /// ```
+    /// use std::sync::Arc;
+    ///
+    /// use leveled_hash_map::{LeveledHashMap, TryInsertError};
+    ///
+    /// let mut map: LeveledHashMap<&'static str, u8> = LeveledHashMap::new();
+    ///
+    /// assert_eq!(1, *map.try_insert(&[Arc::new("food")], 1).unwrap());
+    ///
+    /// match map.try_insert(&[Arc::new("food")], 2) {
+    ///     Err(TryInsertError::Occupied(err)) => {
+    ///         assert_eq!(&1, err.current);
+    ///         assert_eq!(2, err.value);
+    ///     },
+    ///     _ => unreachable!(),
+    /// }
+    /// ```
+    pub fn try_insert(
+        &mut self,
+        key_chain: &[Arc<K>],
+        value: V,
+    ) -> Result<&mut V, TryInsertError<'_, K, V>> {
+        if self.get_inner(key_chain, 0).is_ok() {
+            let level = key_chain.len() - 1;
+
+            return Err(TryInsertError::Occupied(OccupiedError {
+                current: &self.pool[level][&key_chain[level]].1,
+                value,
+            }));
+        }
+
+        self.insert(key_chain, value)?;
+
+        let level = key_chain.len() - 1;
+
+        Ok(&mut self.pool[level].get_mut(&key_chain[level]).unwrap().1)
+    }
+}
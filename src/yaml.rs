@@ -0,0 +1,150 @@
+use std::{collections::HashMap, fmt::Display, hash::Hash, str::FromStr, sync::Arc};
+
+use serde::{
+    de::{DeserializeOwned, Error as _},
+    Serialize,
+};
+use serde_yaml::{Error, Mapping, Value};
+
+use crate::{LeveledHashMap, Node};
+
+impl<K: Eq + Hash, V> LeveledHashMap<K, V> {
+    /// Create a map from a YAML document whose root is a mapping, where nested mappings become levels. A mapping is an entry whose value is `V::default()` and whose children are the items of the mapping, and any other item is an entry without children whose value is deserialized into `V`. Strings, numbers and booleans can be used as keys.
+    /// ```
+    /// use std::sync::Arc;
+    ///
+    /// use leveled_hash_map::LeveledHashMap;
+    ///
+    /// let yaml = "
+    /// greeting:
+    ///   hello: Hello!
+    ///   bye: Goodbye!
+    /// title: My App
+    /// ";
+    ///
+    /// let map: LeveledHashMap<String, String> =
+    ///     LeveledHashMap::from_yaml_str(yaml).unwrap();
+    ///
+    /// assert_eq!(
+    ///     Some(&"Hello!".to_string()),
+    ///     map.get(&[
+    ///         Arc::new("greeting".to_string()),
+    ///         Arc::new("hello".to_string())
+    ///     ])
+    /// );
+    /// assert_eq!(
+    ///     Some(&"My App".to_string()),
+    ///     map.get(&[Arc::new("title".to_string())])
+    /// );
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "yaml")))]
+    pub fn from_yaml_str(s: &str) -> Result<Self, Error>
+    where
+        K: FromStr,
+        K::Err: Display,
+        V: DeserializeOwned + Default, {
+        let mapping = match serde_yaml::from_str(s)? {
+            Value::Mapping(mapping) => mapping,
+            Value::Null => Mapping::new(),
+            _ => return Err(Error::custom("The root of the YAML document is not a mapping.")),
+        };
+
+        let tree = yaml_mapping_to_tree(mapping)?;
+
+        LeveledHashMap::from_tree(tree).map_err(Error::custom)
+    }
+
+    /// Convert the map into a YAML document where levels become nested mappings, sorted by their keys. An entry with children is written as the mapping of its children, so its own value is not written, and an entry without children is written as its value.
+    /// ```
+    /// use std::sync::Arc;
+    ///
+    /// use leveled_hash_map::LeveledHashMap;
+    ///
+    /// let mut map: LeveledHashMap<&'static str, &'static str> =
+    ///     LeveledHashMap::new();
+    ///
+    /// map.insert(&[Arc::new("greeting")], "").unwrap();
+    /// map.insert(&[Arc::new("greeting"), Arc::new("hello")], "Hello!").unwrap();
+    /// map.insert(&[Arc::new("greeting"), Arc::new("bye")], "Goodbye!").unwrap();
+    /// map.insert(&[Arc::new("title")], "My App").unwrap();
+    ///
+    /// assert_eq!(
+    ///     "greeting:\n  bye: Goodbye!\n  hello: Hello!\ntitle: My App\n",
+    ///     map.to_yaml_string().unwrap()
+    /// );
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "yaml")))]
+    pub fn to_yaml_string(&self) -> Result<String, Error>
+    where
+        K: Display,
+        V: Serialize, {
+        let mapping = match self.pool.first() {
+            Some(map) => self.to_yaml_mapping(0, map.keys())?,
+            None => Mapping::new(),
+        };
+
+        serde_yaml::to_string(&mapping)
+    }
+
+    /// Convert some entries at a specific level, along with their descendants, into a YAML mapping sorted by the keys.
+    fn to_yaml_mapping<'a, I: Iterator<Item = &'a Arc<K>>>(
+        &'a self,
+        level: usize,
+        keys: I,
+    ) -> Result<Mapping, Error>
+    where
+        K: Display,
+        V: Serialize, {
+        let mut keys: Vec<(String, &Arc<K>)> = keys.map(|key| (key.to_string(), key)).collect();
+
+        keys.sort_by(|a, b| a.0.cmp(&b.0));
+
+        let mut mapping = Mapping::with_capacity(keys.len());
+
+        for (name, key) in keys {
+            let children = &self.sub[level][key];
+
+            let value = if children.is_empty() {
+                serde_yaml::to_value(&self.pool[level][key].1)?
+            } else {
+                Value::Mapping(self.to_yaml_mapping(level + 1, children.iter())?)
+            };
+
+            mapping.insert(Value::String(name), value);
+        }
+
+        Ok(mapping)
+    }
+}
+
+/// Convert the items of a YAML mapping into nodes.
+fn yaml_mapping_to_tree<K: Eq + Hash + FromStr, V: DeserializeOwned + Default>(
+    mapping: Mapping,
+) -> Result<HashMap<K, Node<K, V>>, Error>
+where
+    K::Err: Display, {
+    let mut tree = HashMap::with_capacity(mapping.len());
+
+    for (name, item) in mapping {
+        let name = match name {
+            Value::String(s) => s,
+            Value::Number(n) => n.to_string(),
+            Value::Bool(b) => b.to_string(),
+            _ => return Err(Error::custom("A key is not a string, a number or a boolean.")),
+        };
+
+        let key = name.parse::<K>().map_err(Error::custom)?;
+
+        let node = match item {
+            Value::Mapping(mapping) => Node {
+                value:    V::default(),
+                children: yaml_mapping_to_tree(mapping)?,
+            },
+            item => Node::new(serde_yaml::from_value(item)?),
+        };
+
+        tree.insert(key, node);
+    }
+
+    Ok(tree)
+}
@@ -1,6 +1,6 @@
 use std::{collections::HashMap, sync::Arc};
 
-use leveled_hash_map::LeveledHashMap;
+use leveled_hash_map::{LevelLimits, LeveledHashMap, LeveledHashMapError, Projection};
 
 #[test]
 fn advanced() {
@@ -106,3 +106,516 @@ fn advanced() {
 
     assert_eq!(0, remove_result.len());
 }
+
+#[test]
+fn relevel() {
+    let mut map: LeveledHashMap<&'static str, u8> = LeveledHashMap::new();
+
+    map.insert(&[Arc::new("food")], 10).unwrap();
+    map.insert(&[Arc::new("food"), Arc::new("dessert")], 20).unwrap();
+    map.insert(&[Arc::new("food"), Arc::new("dessert"), Arc::new("cake")], 30).unwrap();
+    map.insert(&[Arc::new("animal")], 11).unwrap();
+    map.insert(&[Arc::new("animal"), Arc::new("cake")], 21).unwrap();
+
+    map.relevel_key(&Arc::new("dessert"), 1, &[Arc::new("animal")]).unwrap();
+
+    assert_eq!(&30, map.get(&[Arc::new("animal"), Arc::new("dessert"), Arc::new("cake")]).unwrap());
+    assert!(map.keys(0).unwrap()[&Arc::new("food")].is_empty());
+
+    map.relevel_key(&Arc::new("dessert"), 1, &[Arc::new("food")]).unwrap();
+
+    // cannot be moved into its own subtree
+    assert!(map
+        .relevel_key(&Arc::new("food"), 0, &[Arc::new("food"), Arc::new("dessert")])
+        .is_err());
+
+    // "dessert" goes to Level 0 and its child "cake" goes to Level 1, where "animal/cake" exists
+    assert!(map.relevel_key(&Arc::new("dessert"), 1, &[]).is_err());
+
+    assert_eq!(&30, map.get(&[Arc::new("food"), Arc::new("dessert"), Arc::new("cake")]).unwrap());
+
+    map.remove(&[Arc::new("animal"), Arc::new("cake")]).unwrap();
+
+    map.relevel_key(&Arc::new("dessert"), 1, &[]).unwrap();
+
+    assert_eq!(&20, map.get(&[Arc::new("dessert")]).unwrap());
+    assert_eq!(&30, map.get(&[Arc::new("dessert"), Arc::new("cake")]).unwrap());
+    assert!(map.get_advanced(&[Arc::new("cake")], 2).is_none());
+
+    map.reserve_key(1, "dessert");
+
+    // "dessert" is reserved at Level 1
+    assert!(map.relevel_key(&Arc::new("dessert"), 0, &[Arc::new("food")]).is_err());
+
+    map.unreserve_key(1, &"dessert");
+
+    map.set_level_limits(0, LevelLimits {
+        max_entries: None, max_children: Some(0)
+    });
+
+    assert!(map.relevel_key(&Arc::new("dessert"), 0, &[Arc::new("food")]).is_err());
+    assert_eq!(&20, map.get(&[Arc::new("dessert")]).unwrap());
+
+    map.clear_limits();
+
+    // "cake" goes to Level 2
+    map.set_level_limits(2, LevelLimits {
+        max_entries: Some(0), max_children: None
+    });
+
+    assert!(map.relevel_key(&Arc::new("dessert"), 0, &[Arc::new("food")]).is_err());
+
+    map.clear_limits();
+
+    map.relevel_key(&Arc::new("dessert"), 0, &[Arc::new("food")]).unwrap();
+
+    assert_eq!(&30, map.get(&[Arc::new("food"), Arc::new("dessert"), Arc::new("cake")]).unwrap());
+}
+
+#[test]
+fn insert_many_all_or_nothing() {
+    let mut map: LeveledHashMap<&'static str, u8> = LeveledHashMap::new();
+
+    map.insert(&[Arc::new("food")], 10).unwrap();
+    map.insert(&[Arc::new("animal")], 11).unwrap();
+    map.insert(&[Arc::new("animal"), Arc::new("mammal")], 20).unwrap();
+
+    let mut batch = HashMap::new();
+
+    batch.insert("dessert", 21);
+    batch.insert("meat", 22);
+    batch.insert("mammal", 23);
+
+    // "mammal" belongs to "animal"
+    assert!(map.insert_many(&[Arc::new("food")], batch, 0).is_err());
+
+    assert!(map.get(&[Arc::new("food"), Arc::new("dessert")]).is_none());
+    assert!(map.get(&[Arc::new("food"), Arc::new("meat")]).is_none());
+    assert_eq!(&20, map.get(&[Arc::new("animal"), Arc::new("mammal")]).unwrap());
+
+    map.reserve_key(2, "__meta");
+
+    let mut batch = HashMap::new();
+
+    batch.insert("cat", 30);
+    batch.insert("__meta", 31);
+
+    // the failed batch does not create a new level
+    assert!(map.insert_many(&[Arc::new("animal"), Arc::new("mammal")], batch, 0).is_err());
+    assert!(map.keys(2).is_none());
+
+    let mut map: LeveledHashMap<&'static str, u8> = LeveledHashMap::new();
+
+    map.reserve_key(0, "__meta");
+
+    let mut batch = HashMap::new();
+
+    batch.insert("food", 10);
+    batch.insert("__meta", 11);
+
+    // the failed batch does not create Level 0 either
+    assert!(map.insert_many(&[], batch, 0).is_err());
+    assert!(map.max_level().is_none());
+}
+
+#[test]
+fn flat_snapshot_round_trip() {
+    let mut map: LeveledHashMap<&'static str, u8> = LeveledHashMap::new();
+
+    map.insert(&[Arc::new("food")], 10).unwrap();
+    map.insert(&[Arc::new("food"), Arc::new("dessert")], 20).unwrap();
+    map.insert(&[Arc::new("food"), Arc::new("dessert"), Arc::new("cake")], 30).unwrap();
+    map.insert(&[Arc::new("animal")], 11).unwrap();
+
+    // Level 2 stays as an empty level
+    map.remove(&[Arc::new("food"), Arc::new("dessert"), Arc::new("cake")]).unwrap();
+
+    let snapshot = map.to_flat_snapshot();
+
+    assert_eq!(3, snapshot.levels.len());
+    assert!(snapshot.levels[2].is_empty());
+
+    let map = LeveledHashMap::from_flat_snapshot(snapshot.clone()).unwrap();
+
+    assert_eq!(&20, map.get(&[Arc::new("food"), Arc::new("dessert")]).unwrap());
+    assert!(map.keys(2).unwrap().is_empty());
+
+    let mut levels = map.into_flat_snapshot().levels;
+
+    let mut expected = snapshot.levels;
+
+    for (a, b) in levels.iter_mut().zip(expected.iter_mut()) {
+        a.sort_unstable();
+        b.sort_unstable();
+    }
+
+    assert_eq!(expected, levels);
+}
+
+#[test]
+fn insert_with_missing_ancestor() {
+    let mut map: LeveledHashMap<&'static str, u8> = LeveledHashMap::new();
+
+    map.insert(&[Arc::new("food")], 10).unwrap();
+    map.insert(&[Arc::new("food"), Arc::new("dessert")], 20).unwrap();
+
+    assert!(map.insert(&[Arc::new("drink"), Arc::new("tea")], 21).is_err());
+    assert!(map.get_advanced(&[Arc::new("tea")], 1).is_none());
+    assert!(map.get(&[Arc::new("drink")]).is_none());
+
+    // a new level under a missing parent
+    assert!(map.insert(&[Arc::new("food"), Arc::new("meat"), Arc::new("beef")], 30).is_err());
+    assert!(map.keys(2).is_none());
+}
+
+#[test]
+fn allowed_types_in_batches() {
+    use std::any::Any;
+
+    let mut map: LeveledHashMap<&'static str, Box<dyn Any>> = LeveledHashMap::new();
+
+    map.allow_type_at_level::<String>(0);
+    map.allow_type_at_level::<u8>(1);
+    map.allow_type_at_level::<u16>(1);
+
+    map.insert(&[Arc::new("food")], Box::new("食物".to_string())).unwrap();
+
+    let mut batch: HashMap<&'static str, Box<dyn Any>> = HashMap::new();
+
+    batch.insert("dessert", Box::new(1u8));
+    batch.insert("meat", Box::new("肉類"));
+
+    assert!(map.insert_many(&[Arc::new("food")], batch, 0).is_err());
+    assert!(map.keys(1).is_none());
+
+    let mut batch: HashMap<&'static str, Box<dyn Any>> = HashMap::new();
+
+    batch.insert("dessert", Box::new(1u8));
+    batch.insert("meat", Box::new(2u16));
+
+    map.insert_many(&[Arc::new("food")], batch, 0).unwrap();
+
+    assert!(map.insert_child(&Arc::new("food"), 0, "drink", Box::new(3u32)).is_err());
+
+    map.clear_allowed_types(1);
+
+    map.insert_child(&Arc::new("food"), 0, "drink", Box::new(3u32)).unwrap();
+}
+
+#[test]
+fn restore_with_allowed_types() {
+    #[derive(Clone)]
+    enum Item {
+        Small(u8),
+        Large(u32),
+    }
+
+    impl Projection<Item> for u8 {
+        fn project(value: &Item) -> Option<&Self> {
+            match value {
+                Item::Small(v) => Some(v),
+                _ => None,
+            }
+        }
+    }
+
+    impl Projection<Item> for u32 {
+        fn project(value: &Item) -> Option<&Self> {
+            match value {
+                Item::Large(v) => Some(v),
+                _ => None,
+            }
+        }
+    }
+
+    let mut map: LeveledHashMap<&'static str, Item> = LeveledHashMap::new();
+
+    map.enable_tombstones();
+
+    map.insert(&[Arc::new("food")], Item::Small(1)).unwrap();
+    map.insert(&[Arc::new("food"), Arc::new("drink")], Item::Large(3)).unwrap();
+
+    map.soft_remove(&[Arc::new("food"), Arc::new("drink")]).unwrap();
+
+    map.allow_type_at_level::<u8>(1);
+
+    assert!(map.restore(&[Arc::new("food"), Arc::new("drink")]).is_err());
+    assert_eq!(1, map.tombstones().len());
+
+    map.allow_type_at_level::<u32>(1);
+
+    map.restore(&[Arc::new("food"), Arc::new("drink")]).unwrap();
+    assert!(map.tombstones().is_empty());
+}
+
+#[test]
+fn remove_with_tombstones() {
+    let mut map: LeveledHashMap<&'static str, u8> = LeveledHashMap::new();
+
+    map.insert(&[Arc::new("food")], 10).unwrap();
+    map.insert(&[Arc::new("food"), Arc::new("dessert")], 20).unwrap();
+    map.insert(&[Arc::new("animal")], 11).unwrap();
+
+    match map.soft_remove(&[Arc::new("food")]) {
+        Err(LeveledHashMapError::TombstonesDisabled) => (),
+        _ => unreachable!(),
+    }
+
+    assert_eq!(&20, map.get(&[Arc::new("food"), Arc::new("dessert")]).unwrap());
+
+    map.enable_tombstones();
+
+    let (value, descendants) = map.remove(&[Arc::new("food")]).unwrap();
+
+    assert_eq!(10, value);
+    assert_eq!(&(Some(Arc::new("food")), 20), descendants[0].get(&Arc::new("dessert")).unwrap());
+    assert!(map.get(&[Arc::new("food")]).is_none());
+    assert_eq!(1, map.tombstones().len());
+    assert_eq!(2, map.tombstones()[0].len());
+    assert_eq!(Some(&20), map.get_including_deleted(&[Arc::new("food"), Arc::new("dessert")]));
+
+    map.remove_with_subtree(&[Arc::new("animal")], 0).unwrap();
+
+    assert_eq!(2, map.tombstones().len());
+
+    map.restore(&[Arc::new("food")]).unwrap();
+
+    assert_eq!(&20, map.get(&[Arc::new("food"), Arc::new("dessert")]).unwrap());
+    assert_eq!(1, map.compact());
+    assert!(map.get(&[Arc::new("animal")]).is_none());
+}
+
+#[test]
+fn insert_many_under_missing_parent() {
+    let mut map: LeveledHashMap<&'static str, u8> = LeveledHashMap::new();
+
+    map.insert(&[Arc::new("food")], 10).unwrap();
+
+    let mut batch = HashMap::new();
+
+    batch.insert("cake", 30);
+    batch.insert("pie", 31);
+
+    match map.insert_many(&[Arc::new("food"), Arc::new("dessert")], batch, 0) {
+        Err(LeveledHashMapError::ChildrenOfMissingParent {
+            parent_chain,
+            children,
+        }) => {
+            assert_eq!(vec![Arc::new("food"), Arc::new("dessert")], parent_chain);
+            assert_eq!(2, children);
+        },
+        _ => unreachable!(),
+    }
+
+    assert!(map.keys(1).is_none());
+
+    let mut batch = HashMap::new();
+
+    batch.insert("cream", 40);
+
+    match map.insert_many(&[Arc::new("food"), Arc::new("dessert"), Arc::new("cake")], batch, 0) {
+        Err(LeveledHashMapError::KeyTooMany) => (),
+        _ => unreachable!(),
+    }
+}
+
+#[test]
+fn extend_all_or_nothing() {
+    use std::panic::{catch_unwind, AssertUnwindSafe};
+
+    let mut map: LeveledHashMap<&'static str, u8> = LeveledHashMap::new();
+
+    map.set_level_limits(0, LevelLimits {
+        max_entries: Some(2), max_children: None
+    });
+
+    map.insert(&[Arc::new("food")], 10).unwrap();
+
+    let result = catch_unwind(AssertUnwindSafe(|| {
+        map.extend(vec![
+            (vec![Arc::new("food"), Arc::new("dessert")], 20),
+            (vec![Arc::new("animal")], 11),
+            (vec![Arc::new("plant")], 12),
+        ])
+    }));
+
+    assert!(result.is_err());
+    assert_eq!(1, map.keys(0).unwrap().len());
+    assert!(map.keys(1).is_none());
+
+    map.try_extend(vec![
+        (vec![Arc::new("food"), Arc::new("dessert")], 20),
+        (vec![Arc::new("animal")], 11),
+    ])
+    .unwrap();
+
+    assert_eq!(&20, map.get(&[Arc::new("food"), Arc::new("dessert")]).unwrap());
+    assert_eq!(&11, map.get(&[Arc::new("animal")]).unwrap());
+}
+
+#[test]
+fn insert_many_with_limits() {
+    let mut map: LeveledHashMap<&'static str, u8> = LeveledHashMap::new();
+
+    map.set_level_limits(0, LevelLimits {
+        max_entries: Some(2), max_children: None
+    });
+
+    let mut batch = HashMap::new();
+
+    batch.insert("food", 10);
+    batch.insert("animal", 11);
+    batch.insert("plant", 12);
+
+    assert!(map.insert_many(&[], batch, 0).is_err());
+    assert!(map.get(&[Arc::new("food")]).is_none());
+
+    let mut batch = HashMap::new();
+
+    batch.insert("food", 10);
+    batch.insert("animal", 11);
+
+    map.insert_many(&[], batch, 0).unwrap();
+
+    let mut batch = HashMap::new();
+
+    batch.insert("food", 20);
+    batch.insert("plant", 12);
+
+    assert!(map.insert_many(&[], batch, 0).is_err());
+    assert_eq!(&10, map.get(&[Arc::new("food")]).unwrap());
+
+    // overwriting is fine
+    let mut batch = HashMap::new();
+
+    batch.insert("food", 20);
+
+    map.insert_many(&[], batch, 0).unwrap();
+    assert_eq!(&20, map.get(&[Arc::new("food")]).unwrap());
+}
+
+#[test]
+fn restore_with_limits() {
+    let mut map: LeveledHashMap<&'static str, u8> = LeveledHashMap::new();
+
+    map.enable_tombstones();
+
+    map.insert(&[Arc::new("food")], 10).unwrap();
+    map.insert(&[Arc::new("food"), Arc::new("dessert")], 20).unwrap();
+    map.insert(&[Arc::new("food"), Arc::new("dessert"), Arc::new("cake")], 30).unwrap();
+    map.insert(&[Arc::new("food"), Arc::new("dessert"), Arc::new("pie")], 31).unwrap();
+
+    map.soft_remove(&[Arc::new("food")]).unwrap();
+
+    map.insert(&[Arc::new("animal")], 11).unwrap();
+
+    map.set_level_limits(0, LevelLimits {
+        max_entries: Some(1), max_children: None
+    });
+
+    assert!(map.restore(&[Arc::new("food")]).is_err());
+    assert_eq!(1, map.keys(0).unwrap().len());
+
+    map.clear_limits();
+
+    map.set_level_limits(1, LevelLimits {
+        max_entries: None, max_children: Some(1)
+    });
+
+    assert!(map.restore(&[Arc::new("food")]).is_err());
+    assert!(map.get(&[Arc::new("food")]).is_none());
+    assert_eq!(1, map.tombstones().len());
+
+    map.clear_limits();
+
+    map.restore(&[Arc::new("food")]).unwrap();
+
+    assert_eq!(&31, map.get(&[Arc::new("food"), Arc::new("dessert"), Arc::new("pie")]).unwrap());
+}
+
+#[test]
+fn get_with_coerced_key_chains() {
+    let mut map: LeveledHashMap<&'static str, u8> = LeveledHashMap::new();
+
+    map.insert(&[Arc::new("food")], 10).unwrap();
+    map.insert(&[Arc::new("food"), Arc::new("dessert")], 20).unwrap();
+
+    let boxed: Box<[Arc<&'static str>]> = vec![Arc::new("food"), Arc::new("dessert")].into();
+    let shared: Arc<[Arc<&'static str>]> = boxed.clone().into();
+
+    assert_eq!(&20, map.get(&boxed).unwrap());
+    assert_eq!(&20, map.get(&shared).unwrap());
+    assert_eq!(&20, map.get_advanced(&shared[1..], 1).unwrap());
+
+    *map.get_mut(&boxed).unwrap() += 1;
+
+    assert_eq!(&21, map.get_by(("food", "dessert")).unwrap());
+}
+
+#[test]
+fn split_level_shifts_settings() {
+    let mut map: LeveledHashMap<&'static str, u8> = LeveledHashMap::new();
+
+    map.track_generations();
+
+    map.insert(&[Arc::new("tw")], 0).unwrap();
+    map.insert(&[Arc::new("tw"), Arc::new("taipei")], 1).unwrap();
+    map.insert(&[Arc::new("tw"), Arc::new("taipei"), Arc::new("xinyi")], 2).unwrap();
+    map.insert(&[Arc::new("tw"), Arc::new("keelung")], 3).unwrap();
+
+    map.remove(&[Arc::new("tw"), Arc::new("keelung")]).unwrap();
+
+    let generation = map.generation().unwrap();
+
+    map.set_level_limits(1, LevelLimits {
+        max_entries: Some(10), max_children: Some(5)
+    });
+    map.reserve_key(2, "__meta");
+
+    map.split_level(1, |_key, _value| "north", |_group| 0).unwrap();
+
+    assert_eq!(LevelLimits::default(), map.level_limits(1));
+    assert_eq!(Some(5), map.level_limits(2).max_children);
+    assert!(!map.is_reserved_key(2, &"__meta"));
+    assert!(map.is_reserved_key(3, &"__meta"));
+
+    let export = map.export_since(generation).unwrap();
+
+    // the old entries at Level 1 are removed from their old position, which covers their descendants
+    let mut removed = export.removed;
+
+    removed.sort_unstable();
+
+    assert_eq!(vec![(1, Arc::new("taipei"))], removed);
+    assert_eq!(3, export.changed.len());
+    assert_eq!(
+        &2,
+        map.get(&[Arc::new("tw"), Arc::new("north"), Arc::new("taipei"), Arc::new("xinyi")])
+            .unwrap()
+    );
+}
+
+#[test]
+fn get_many_mut_at_one_level() {
+    let mut map: LeveledHashMap<&'static str, u8> = LeveledHashMap::new();
+
+    map.insert(&[Arc::new("food")], 10).unwrap();
+    map.insert(&[Arc::new("animal")], 11).unwrap();
+    map.insert(&[Arc::new("plant")], 12).unwrap();
+    map.insert(&[Arc::new("food"), Arc::new("dessert")], 20).unwrap();
+
+    let [plant, dessert, food] = map
+        .get_many_mut([&[Arc::new("plant")], &[Arc::new("food"), Arc::new("dessert")], &[
+            Arc::new("food"),
+        ]])
+        .unwrap();
+
+    *plant += 1;
+    *dessert += 1;
+    *food += 1;
+
+    assert_eq!(&13, map.get(&[Arc::new("plant")]).unwrap());
+    assert_eq!(&21, map.get(&[Arc::new("food"), Arc::new("dessert")]).unwrap());
+    assert_eq!(&11, map.get(&[Arc::new("food")]).unwrap());
+    assert_eq!(&11, map.get(&[Arc::new("animal")]).unwrap());
+}
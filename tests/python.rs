@@ -0,0 +1,52 @@
+#![cfg(feature = "python")]
+
+use leveled_hash_map::python::register;
+use pyo3::{ffi::c_str, prelude::*, types::PyModule};
+
+#[test]
+fn dict_like_access() {
+    Python::initialize();
+
+    Python::attach(|py| {
+        let module = PyModule::new(py, "leveled_hash_map").unwrap();
+
+        register(&module).unwrap();
+
+        let globals = module.dict();
+
+        py.run(
+            c_str!(
+                r#"
+m = LeveledHashMap()
+
+m[("food",)] = 1
+m[("food", "dessert")] = {"name": "cake"}
+m[("animal",)] = [2]
+
+assert len(m) == 3
+assert ("food", "dessert") in m
+assert ("dessert",) not in m
+assert m[("food", "dessert")]["name"] == "cake"
+assert m.get(("drink",), 0) == 0
+
+keys = list(m)
+assert keys.index(("food",)) < keys.index(("food", "dessert"))
+assert dict(m.items())[("animal",)] == [2]
+
+try:
+    m[()] = 3
+    raise AssertionError
+except KeyError:
+    pass
+
+del m[("food",)]
+
+assert len(m) == 1
+"#
+            ),
+            Some(&globals),
+            None,
+        )
+        .unwrap();
+    });
+}
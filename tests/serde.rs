@@ -0,0 +1,34 @@
+#![cfg(feature = "serde")]
+
+use std::sync::Arc;
+
+use leveled_hash_map::LeveledHashMap;
+
+#[test]
+fn serialize_error() {
+    let mut map: LeveledHashMap<&'static str, u8> = LeveledHashMap::new();
+
+    map.insert(&[Arc::new("food")], 10).unwrap();
+    map.insert(&[Arc::new("food"), Arc::new("dessert")], 20).unwrap();
+
+    let err = map.get_professional(&[Arc::new("food"), Arc::new("meat")], 0).unwrap_err();
+
+    assert_eq!(r#"{"KeyNotExist":{"level":1,"key":"meat"}}"#, serde_json::to_string(&err).unwrap());
+
+    let err = map.get_professional(&[], 0).unwrap_err();
+
+    assert_eq!(r#""KeyChainEmpty""#, serde_json::to_string(&err).unwrap());
+}
+
+#[test]
+fn serialize_export_chunk() {
+    let mut map: LeveledHashMap<&'static str, u8> = LeveledHashMap::new();
+
+    map.insert(&[Arc::new("food")], 10).unwrap();
+    map.insert(&[Arc::new("food"), Arc::new("dessert")], 20).unwrap();
+
+    let chunks: Vec<String> =
+        map.export_chunks(1).map(|chunk| serde_json::to_string(&chunk).unwrap()).collect();
+
+    assert_eq!(vec![r#"{"entries":[[["food"],10],[["food","dessert"],20]]}"#], chunks);
+}
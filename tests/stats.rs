@@ -0,0 +1,34 @@
+#![cfg(feature = "stats")]
+
+use std::{sync::Arc, thread, time::Duration};
+
+use leveled_hash_map::LeveledHashMap;
+
+#[test]
+fn prune_idle() {
+    let mut map: LeveledHashMap<&'static str, u8> = LeveledHashMap::new();
+
+    map.track_access_times();
+
+    map.insert(&[Arc::new("session")], 1).unwrap();
+    map.insert(&[Arc::new("session"), Arc::new("cart")], 2).unwrap();
+    map.insert(&[Arc::new("session"), Arc::new("history")], 3).unwrap();
+
+    assert_eq!(0, map.prune_idle(Duration::from_secs(3600)));
+
+    thread::sleep(Duration::from_secs(1));
+
+    map.get(&[Arc::new("session"), Arc::new("cart")]);
+
+    let cart = map.last_access(&[Arc::new("session"), Arc::new("cart")]).unwrap();
+    let history = map.last_access(&[Arc::new("session"), Arc::new("history")]).unwrap();
+
+    // "history" has been idle for at least this long, and "cart" and "session" have just been accessed
+    let idle = cart.duration_since(history);
+
+    assert!(idle >= Duration::from_secs(1));
+    assert_eq!(1, map.prune_idle(idle));
+
+    assert!(map.get(&[Arc::new("session"), Arc::new("cart")]).is_some());
+    assert!(map.get(&[Arc::new("session"), Arc::new("history")]).is_none());
+}
@@ -0,0 +1,12 @@
+#![cfg(feature = "test_util")]
+
+use leveled_hash_map::test_util::run_differential;
+
+#[test]
+fn differential() {
+    for seed in 0..200 {
+        if let Err(mismatch) = run_differential(seed, 500, &["a", "b", "c", "d"], 4) {
+            panic!("seed {}: {:?}", seed, mismatch);
+        }
+    }
+}